@@ -0,0 +1,192 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use emmylua_parser::{LuaAstNode, LuaClosureExpr, LuaFuncStat, LuaLocalFuncStat, LuaTableField};
+use lsp_types::{Diagnostic, NumberOrString};
+use serde::{Deserialize, Serialize};
+
+use crate::output::get_report_path;
+
+const BASELINE_VERSION: u32 = 1;
+
+/// The fingerprint of a diagnostic, it does not contain the position so the baseline survives
+/// the unrelated edits
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineIssue {
+    pub file: String,
+    pub code: String,
+    pub message: String,
+    /// the name of the enclosing function, empty for the main chunk
+    pub function: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineEntry {
+    #[serde(flatten)]
+    issue: BaselineIssue,
+    count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    issues: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Default)]
+pub struct Baseline {
+    issues: HashMap<BaselineIssue, usize>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read baseline {:?}: {}", path, e))?;
+        let baseline_file: BaselineFile = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse baseline {:?}: {}", path, e))?;
+        if baseline_file.version != BASELINE_VERSION {
+            return Err(format!(
+                "Unsupported baseline version {} in {:?}",
+                baseline_file.version, path
+            ));
+        }
+
+        let mut baseline = Baseline::default();
+        for entry in baseline_file.issues {
+            *baseline.issues.entry(entry.issue).or_insert(0) += entry.count;
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<(), String> {
+        let mut issues = self
+            .issues
+            .iter()
+            .map(|(issue, count)| BaselineEntry {
+                issue: issue.clone(),
+                count: *count,
+            })
+            .collect::<Vec<_>>();
+        // keep the file stable for the version control
+        issues.sort_by(|a, b| a.issue.cmp(&b.issue));
+
+        let baseline_file = BaselineFile {
+            version: BASELINE_VERSION,
+            issues,
+        };
+        let content = serde_json::to_string_pretty(&baseline_file).map_err(|e| e.to_string())?;
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+        }
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write baseline {:?}: {}", path, e))
+    }
+
+    pub fn len(&self) -> usize {
+        self.issues.values().sum()
+    }
+
+    pub fn add(&mut self, issue: BaselineIssue) {
+        *self.issues.entry(issue).or_insert(0) += 1;
+    }
+
+    /// Consumes one matched issue, returns false when the issue is new
+    pub fn take(&mut self, issue: &BaselineIssue) -> bool {
+        match self.issues.get_mut(issue) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn make_baseline_issue(
+    db: &DbIndex,
+    workspace: &Path,
+    file_id: FileId,
+    diagnostic: &Diagnostic,
+) -> BaselineIssue {
+    let file_path = get_report_path(db, file_id);
+    let file = file_path
+        .strip_prefix(workspace)
+        .unwrap_or(file_path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    let code = match &diagnostic.code {
+        Some(NumberOrString::String(code)) => code.clone(),
+        Some(NumberOrString::Number(code)) => code.to_string(),
+        None => String::new(),
+    };
+
+    BaselineIssue {
+        file,
+        code,
+        message: normalize_message(&diagnostic.message),
+        function: get_enclosing_function_name(db, file_id, diagnostic).unwrap_or_default(),
+    }
+}
+
+// the numbers in the message are often the line numbers or the counts
+fn normalize_message(message: &str) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut last_is_digit = false;
+    for c in message
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+    {
+        if c.is_ascii_digit() {
+            if !last_is_digit {
+                result.push('#');
+            }
+            last_is_digit = true;
+        } else {
+            result.push(c);
+            last_is_digit = false;
+        }
+    }
+    result
+}
+
+fn get_enclosing_function_name(
+    db: &DbIndex,
+    file_id: FileId,
+    diagnostic: &Diagnostic,
+) -> Option<String> {
+    let vfs = db.get_vfs();
+    let document = vfs.get_document(&file_id)?;
+    let root = vfs.get_syntax_tree(&file_id)?.get_chunk_node();
+    let position = diagnostic.range.start;
+    let offset = document.get_offset(position.line as usize, position.character as usize)?;
+    let token = root.syntax().token_at_offset(offset).right_biased()?;
+
+    // the nearest function which has a name
+    for closure in token.parent_ancestors().filter_map(LuaClosureExpr::cast) {
+        let parent = closure.syntax().parent()?;
+        if let Some(func_stat) = LuaFuncStat::cast(parent.clone()) {
+            return Some(func_stat.get_func_name()?.syntax().text().to_string());
+        } else if let Some(local_func_stat) = LuaLocalFuncStat::cast(parent.clone()) {
+            return Some(
+                local_func_stat
+                    .get_local_name()?
+                    .syntax()
+                    .text()
+                    .to_string(),
+            );
+        } else if let Some(table_field) = LuaTableField::cast(parent) {
+            if let Some(key) = table_field.get_field_key() {
+                return Some(key.get_path_part());
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,151 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaExpr, LuaLiteralToken};
+
+use crate::output::get_report_path;
+
+/// Returns the files changed since the git ref, including the uncommitted and the untracked files
+pub fn get_git_changed_files(workspace: &Path, git_ref: &str) -> Result<Vec<PathBuf>, String> {
+    // the paths are relative to the workspace because of `--relative`, a renamed file is listed
+    // with both its old and new path because of `--no-renames`
+    let diff = run_git(
+        workspace,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--relative",
+            git_ref,
+            "--",
+        ],
+    )?;
+    let untracked = run_git(workspace, &["ls-files", "--others", "--exclude-standard"])?;
+
+    let files = diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .map(|line| workspace.join(line))
+        .collect();
+    Ok(files)
+}
+
+fn run_git(workspace: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workspace)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Selects the changed files and the files which depend on them from the files to check. A
+/// deleted file has no file id, the files which require its module are selected instead.
+pub fn collect_changed_file_ids(
+    db: &DbIndex,
+    need_check_files: Vec<FileId>,
+    changed_files: Vec<PathBuf>,
+) -> Vec<FileId> {
+    // the code blocks of a markdown file are changed with the file
+    let mut path_to_file_ids: HashMap<PathBuf, Vec<FileId>> = HashMap::new();
+    for file_id in &need_check_files {
+        let path = get_report_path(db, *file_id);
+        path_to_file_ids
+            .entry(normalize_path(path))
+            .or_default()
+            .push(*file_id);
+    }
+
+    let mut changed_file_ids = Vec::new();
+    let mut deleted_modules = HashSet::new();
+    for path in changed_files {
+        if let Some(file_ids) = path_to_file_ids.get(&normalize_path(&path)) {
+            changed_file_ids.extend(file_ids);
+        } else if !path.exists() {
+            let module_index = db.get_module_index();
+            let module_path = path
+                .to_str()
+                .and_then(|path| module_index.get_module_path_by_file_path(path))
+                .or_else(|| {
+                    let path = normalize_path(&path);
+                    module_index.get_module_path_by_file_path(path.to_str()?)
+                });
+            if let Some((module_path, _)) = module_path {
+                deleted_modules.insert(module_path);
+            }
+        }
+    }
+    if !deleted_modules.is_empty() {
+        for file_id in &need_check_files {
+            if is_require_any_module(db, *file_id, &deleted_modules) {
+                changed_file_ids.push(*file_id);
+            }
+        }
+    }
+
+    let dependents = db
+        .get_file_dependencies_index()
+        .get_file_dependencies()
+        .collect_file_dependents(changed_file_ids.clone());
+    let mut result: HashSet<FileId> = changed_file_ids.into_iter().collect();
+    let need_check_set: HashSet<FileId> = need_check_files.into_iter().collect();
+    for file_id in dependents {
+        // the dependents in the library are not checked
+        if need_check_set.contains(&file_id) {
+            result.insert(file_id);
+        }
+    }
+
+    let mut result = result.into_iter().collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+// the deleted file can not be canonicalized, its parent directory is canonicalized instead
+fn normalize_path(path: &Path) -> PathBuf {
+    if let Ok(path) = path.canonicalize() {
+        return path;
+    }
+
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(file_name)) => match parent.canonicalize() {
+            Ok(parent) => parent.join(file_name),
+            Err(_) => path.to_path_buf(),
+        },
+        _ => path.to_path_buf(),
+    }
+}
+
+fn is_require_any_module(db: &DbIndex, file_id: FileId, modules: &HashSet<String>) -> bool {
+    let Some(tree) = db.get_vfs().get_syntax_tree(&file_id) else {
+        return false;
+    };
+
+    tree.get_chunk_node()
+        .descendants::<LuaCallExpr>()
+        .filter(|call_expr| call_expr.is_require())
+        .filter_map(|call_expr| call_expr.get_args_list()?.get_args().next())
+        .any(|arg| match arg {
+            LuaExpr::LiteralExpr(literal) => match literal.get_literal() {
+                Some(LuaLiteralToken::String(string_token)) => {
+                    modules.contains(&string_token.get_value())
+                }
+                _ => false,
+            },
+            _ => false,
+        })
+}
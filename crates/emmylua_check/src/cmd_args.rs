@@ -1,3 +1,4 @@
+use emmylua_code_analysis::{DiagnosticCode, DiagnosticSeveritySetting};
 use structopt::StructOpt;
 
 #[allow(unused)]
@@ -20,7 +21,7 @@ pub struct CmdArgs {
 
     #[structopt(
         long,
-        help = "Specify output format (json or text)",
+        help = "Specify output format (json, sarif, checkstyle, junit, github or text)",
         default_value = "text",
         possible_values = &OutputFormat::variants(),
         case_insensitive = true
@@ -29,7 +30,7 @@ pub struct CmdArgs {
 
     #[structopt(
         long,
-        help = "Specify output destination (stdout or a file path, only used when output_format is json)",
+        help = "Specify output destination (stdout or a file path, not used when output_format is text)",
         default_value = "stdout",
         parse(try_from_str)
     )]
@@ -37,11 +38,167 @@ pub struct CmdArgs {
 
     #[structopt(long, help = "Treat warnings as errors")]
     pub warnings_as_errors: bool,
+
+    #[structopt(
+        long,
+        help = "Comma separated list of diagnostic codes to enable",
+        use_delimiter = true,
+        require_delimiter = true,
+        parse(try_from_str = parse_diagnostic_code)
+    )]
+    pub enable: Option<Vec<DiagnosticCode>>,
+
+    #[structopt(
+        long,
+        help = "Comma separated list of diagnostic codes to disable",
+        use_delimiter = true,
+        require_delimiter = true,
+        parse(try_from_str = parse_diagnostic_code)
+    )]
+    pub disable: Option<Vec<DiagnosticCode>>,
+
+    #[structopt(
+        long,
+        help = "Override the severity of a diagnostic code, eg: --severity unused=error",
+        number_of_values = 1,
+        parse(try_from_str = parse_severity_override)
+    )]
+    pub severity: Option<Vec<(DiagnosticCode, DiagnosticSeveritySetting)>>,
+
+    #[structopt(
+        long,
+        help = "Only report the diagnostics at least as severe as the level (hint, info, warning or error)",
+        parse(try_from_str = parse_severity_level)
+    )]
+    pub min_severity: Option<DiagnosticSeveritySetting>,
+
+    #[structopt(
+        long,
+        help = "Exit with an error when the number of the reported warnings exceeds the limit"
+    )]
+    pub max_warnings: Option<usize>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Record the current diagnostics to a baseline file"
+    )]
+    pub write_baseline: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Only report the diagnostics which are not in the baseline file"
+    )]
+    pub baseline: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        help = "Only check the files changed since the git ref and the files depending on them",
+        conflicts_with = "files"
+    )]
+    pub changed_since: Option<String>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Only check the given files and the files depending on them"
+    )]
+    pub files: Option<Vec<std::path::PathBuf>>,
+
+    #[structopt(
+        long,
+        help = "Print the issue counts per diagnostic code and the time spent in every checker"
+    )]
+    pub summary: bool,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Write the summary as json to the file"
+    )]
+    pub summary_json: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        help = "The number of the files with the most issues listed in the summary",
+        default_value = "10"
+    )]
+    pub summary_top: usize,
+
+    #[structopt(
+        long,
+        help = "Keep watching the workspace and recheck the changed files and the files depending on them",
+        conflicts_with_all = &["fix", "fix_dry_run", "coverage", "write_baseline"]
+    )]
+    pub watch: bool,
+
+    #[structopt(
+        long,
+        help = "Report the percentage of the name, index and call expressions, locals, parameters and returns which have a concrete type instead of the diagnostics",
+        conflicts_with_all = &["fix", "fix_dry_run"]
+    )]
+    pub coverage: bool,
+
+    #[structopt(
+        long,
+        help = "Fail when the type coverage in percent is lower than the threshold",
+        requires = "coverage"
+    )]
+    pub min_coverage: Option<f64>,
+
+    #[structopt(
+        long,
+        help = "Also check the fenced lua code blocks of the markdown files",
+        conflicts_with_all = &["fix", "fix_dry_run"]
+    )]
+    pub markdown: bool,
+
+    #[structopt(
+        long,
+        help = "Also check the @example and @usage code of the doc comments, the module is in scope by the name of its export",
+        conflicts_with_all = &["fix", "fix_dry_run", "watch", "coverage"]
+    )]
+    pub examples: bool,
+
+    #[structopt(long, help = "Apply the quick fixes of the diagnostics to the files")]
+    pub fix: bool,
+
+    #[structopt(
+        long,
+        help = "Print the quick fixes as a unified diff without changing the files"
+    )]
+    pub fix_dry_run: bool,
+
+    #[structopt(
+        long,
+        help = "Comma separated list of diagnostic codes to fix, default is the fixable codes enabled by the config",
+        use_delimiter = true
+    )]
+    pub fix_codes: Option<Vec<String>>,
+
+    #[structopt(
+        long,
+        help = "Rewrite the LDoc comments (`-- @tparam`, `@treturn`, `@module`, `@classmod`) of the files into EmmyLua annotations",
+        conflicts_with_all = &["fix", "fix_dry_run", "watch", "coverage"]
+    )]
+    pub convert_ldoc: bool,
+
+    #[structopt(
+        long,
+        help = "Print the LDoc conversion as a unified diff without changing the files",
+        conflicts_with_all = &["fix", "fix_dry_run", "watch", "coverage"]
+    )]
+    pub convert_ldoc_dry_run: bool,
 }
 
 #[derive(Debug, Clone)]
 pub enum OutputFormat {
     Json,
+    Sarif,
+    Checkstyle,
+    Junit,
+    Github,
     Text,
 }
 
@@ -50,6 +207,10 @@ impl std::str::FromStr for OutputFormat {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "checkstyle" => Ok(OutputFormat::Checkstyle),
+            "junit" => Ok(OutputFormat::Junit),
+            "github" => Ok(OutputFormat::Github),
             "text" => Ok(OutputFormat::Text),
             _ => Err(format!("Invalid output format: {}", s)),
         }
@@ -57,8 +218,8 @@ impl std::str::FromStr for OutputFormat {
 }
 
 impl OutputFormat {
-    pub fn variants() -> [&'static str; 2] {
-        ["json", "text"]
+    pub fn variants() -> [&'static str; 6] {
+        ["json", "sarif", "checkstyle", "junit", "github", "text"]
     }
 }
 
@@ -78,3 +239,35 @@ impl std::str::FromStr for OutputDestination {
         }
     }
 }
+
+fn parse_diagnostic_code(s: &str) -> Result<DiagnosticCode, String> {
+    match s.parse::<DiagnosticCode>() {
+        Ok(DiagnosticCode::None) | Err(_) => Err(format!("Invalid diagnostic code: {}", s)),
+        Ok(code) => Ok(code),
+    }
+}
+
+fn parse_severity_level(s: &str) -> Result<DiagnosticSeveritySetting, String> {
+    match s.to_lowercase().as_str() {
+        "hint" => Ok(DiagnosticSeveritySetting::Hint),
+        "info" | "information" => Ok(DiagnosticSeveritySetting::Information),
+        "warning" => Ok(DiagnosticSeveritySetting::Warning),
+        "error" => Ok(DiagnosticSeveritySetting::Error),
+        _ => Err(format!("Invalid severity level: {}", s)),
+    }
+}
+
+// eg: `unused=error`
+fn parse_severity_override(s: &str) -> Result<(DiagnosticCode, DiagnosticSeveritySetting), String> {
+    let Some((code, level)) = s.split_once('=') else {
+        return Err(format!(
+            "Invalid severity override: {}, expected <code>=<level>",
+            s
+        ));
+    };
+
+    Ok((
+        parse_diagnostic_code(code.trim())?,
+        parse_severity_level(level.trim())?,
+    ))
+}
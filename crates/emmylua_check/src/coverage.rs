@@ -0,0 +1,183 @@
+use std::{collections::BTreeMap, path::Path};
+
+use emmylua_code_analysis::{
+    collect_type_coverage, EmmyLuaAnalysis, FileId, LuaCoverageCount, LuaTypeCoverage,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    cmd_args::{OutputDestination, OutputFormat},
+    output::{create_output_file, get_relative_path, write_output},
+};
+
+#[derive(Debug)]
+struct FileCoverage {
+    file: String,
+    module: String,
+    coverage: LuaTypeCoverage,
+}
+
+/// The type coverage of the files, grouped by the files and by the parent modules
+#[derive(Debug)]
+pub struct CoverageReport {
+    files: Vec<FileCoverage>,
+    modules: BTreeMap<String, LuaTypeCoverage>,
+    total: LuaTypeCoverage,
+}
+
+impl CoverageReport {
+    pub fn collect(analysis: &EmmyLuaAnalysis, file_ids: Vec<FileId>, workspace: &Path) -> Self {
+        let db = analysis.compilation.get_db();
+        let mut files = Vec::new();
+        let mut modules: BTreeMap<String, LuaTypeCoverage> = BTreeMap::new();
+        let mut total = LuaTypeCoverage::default();
+        for file_id in file_ids {
+            let Some(semantic_model) = analysis.compilation.get_semantic_model(file_id) else {
+                continue;
+            };
+            let coverage = collect_type_coverage(&semantic_model);
+            let Some(file_path) = db.get_vfs().get_file_path(&file_id) else {
+                continue;
+            };
+            let module = db
+                .get_module_index()
+                .get_module(file_id)
+                .map(|module| module.full_module_name.clone())
+                .unwrap_or_default();
+
+            total.merge(&coverage);
+            modules
+                .entry(get_parent_module(&module))
+                .or_default()
+                .merge(&coverage);
+            files.push(FileCoverage {
+                file: get_relative_path(workspace, file_path),
+                module,
+                coverage,
+            });
+        }
+        files.sort_by(|a, b| a.file.cmp(&b.file));
+
+        Self {
+            files,
+            modules,
+            total,
+        }
+    }
+
+    pub fn get_total_percent(&self) -> f64 {
+        self.total.get_total().get_percent()
+    }
+
+    pub fn write(&self, output_format: &OutputFormat, output: OutputDestination) {
+        match output_format {
+            OutputFormat::Json => {
+                let mut output = create_output_file(output);
+                let content = serde_json::to_string_pretty(&self.to_json()).unwrap();
+                write_output(output.as_mut(), &content);
+            }
+            _ => self.print_table(),
+        }
+    }
+
+    fn print_table(&self) {
+        let mut lines = Vec::new();
+        lines.push(format!(
+            "{:<40} {:>8} {:>8} {:>8} {:>8} {:>8}",
+            "file", "exprs", "locals", "params", "returns", "total"
+        ));
+        for file in &self.files {
+            lines.push(format_row(&file.file, &file.coverage));
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "{:<40} {:>8} {:>8} {:>8} {:>8} {:>8}",
+            "module", "exprs", "locals", "params", "returns", "total"
+        ));
+        for (module, coverage) in &self.modules {
+            lines.push(format_row(get_module_label(module), coverage));
+        }
+
+        lines.push(String::new());
+        let total = self.total.get_total();
+        lines.push(format!(
+            "Type coverage: {:.2}% ({} of {} typed)",
+            total.get_percent(),
+            total.typed,
+            total.total
+        ));
+        println!("{}", lines.join("\n"));
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "total": coverage_to_json(&self.total),
+            "files": self
+                .files
+                .iter()
+                .map(|file| {
+                    let mut value = coverage_to_json(&file.coverage);
+                    value["file"] = json!(file.file);
+                    value["module"] = json!(file.module);
+                    value
+                })
+                .collect::<Vec<_>>(),
+            "modules": self
+                .modules
+                .iter()
+                .map(|(module, coverage)| {
+                    let mut value = coverage_to_json(coverage);
+                    value["module"] = json!(module);
+                    value
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+// `a.b.c` is grouped into `a.b`, the modules at the root are grouped into ``
+fn get_parent_module(module: &str) -> String {
+    match module.rsplit_once('.') {
+        Some((parent, _)) => parent.to_string(),
+        None => String::new(),
+    }
+}
+
+fn get_module_label(module: &str) -> &str {
+    if module.is_empty() {
+        "(root)"
+    } else {
+        module
+    }
+}
+
+fn format_row(name: &str, coverage: &LuaTypeCoverage) -> String {
+    format!(
+        "{:<40} {:>7.1}% {:>7.1}% {:>7.1}% {:>7.1}% {:>7.1}%",
+        name,
+        coverage.exprs.get_percent(),
+        coverage.locals.get_percent(),
+        coverage.params.get_percent(),
+        coverage.returns.get_percent(),
+        coverage.get_total().get_percent()
+    )
+}
+
+fn coverage_to_json(coverage: &LuaTypeCoverage) -> Value {
+    json!({
+        "exprs": count_to_json(&coverage.exprs),
+        "locals": count_to_json(&coverage.locals),
+        "params": count_to_json(&coverage.params),
+        "returns": count_to_json(&coverage.returns),
+        "total": count_to_json(&coverage.get_total()),
+    })
+}
+
+fn count_to_json(count: &LuaCoverageCount) -> Value {
+    json!({
+        "typed": count.typed,
+        "total": count.total,
+        "percent": count.get_percent(),
+    })
+}
@@ -0,0 +1,71 @@
+use std::{collections::HashMap, path::Path};
+
+use emmylua_code_analysis::{
+    extract_doc_examples_lua, file_path_to_uri, DbIndex, FileId, LuaFileInfo, VirtualUrlGenerator,
+};
+
+/// Collects the examples of the doc comments in the loaded files of the workspace, every example
+/// is loaded as the isolated document `<file>.lua/example-<n>.lua`, so its globals and its
+/// exports stay out of the workspace. The export of the module is bound to its local name in the
+/// examples, eg: `local M = require("lib")`
+pub fn collect_example_files(db: &DbIndex, workspace: &Path) -> Vec<LuaFileInfo> {
+    let vfs = db.get_vfs();
+    let mut file_ids = vfs.get_all_file_ids();
+    file_ids.sort();
+    file_ids
+        .into_iter()
+        .filter(|file_id| !vfs.is_isolated(file_id))
+        .filter_map(|file_id| vfs.get_document(&file_id))
+        .filter(|document| {
+            let path = document.get_file_path();
+            path.extension().is_some_and(|ext| ext == "lua") && path.starts_with(workspace)
+        })
+        .flat_map(|document| {
+            let module_name = db
+                .get_module_index()
+                .get_module(document.get_file_id())
+                .map(|module| module.full_module_name.as_str());
+            let url_generator = VirtualUrlGenerator {
+                base: document.get_file_path().clone(),
+            };
+            extract_doc_examples_lua(document.get_text(), module_name)
+                .into_iter()
+                .enumerate()
+                .map(move |(idx, content)| LuaFileInfo {
+                    path: url_generator
+                        .new_path(&format!("example-{}.lua", idx + 1))
+                        .to_string_lossy()
+                        .to_string(),
+                    content,
+                })
+        })
+        .collect()
+}
+
+/// Splits the example files from the files to check, the examples are grouped by their files
+pub fn split_example_file_ids(
+    db: &DbIndex,
+    file_ids: Vec<FileId>,
+) -> (Vec<FileId>, HashMap<FileId, Vec<FileId>>) {
+    let vfs = db.get_vfs();
+    let mut files = Vec::new();
+    let mut examples: HashMap<FileId, Vec<FileId>> = HashMap::new();
+    for file_id in file_ids {
+        // the examples are the isolated documents under the path of a lua file
+        let source_id = vfs
+            .get_file_path(&file_id)
+            .filter(|_| vfs.is_isolated(&file_id))
+            .and_then(|path| path.parent())
+            .and_then(|path| file_path_to_uri(&path.to_path_buf()))
+            .and_then(|uri| vfs.get_file_id(&uri));
+        match source_id {
+            Some(source_id) => examples.entry(source_id).or_default().push(file_id),
+            None => files.push(file_id),
+        }
+    }
+
+    for example_ids in examples.values_mut() {
+        example_ids.sort();
+    }
+    (files, examples)
+}
@@ -0,0 +1,181 @@
+use std::{path::Path, str::FromStr};
+
+use emmylua_code_analysis::{
+    apply_text_edits, build_quick_fix, is_code_default_enable, DiagnosticCode, EmmyLuaAnalysis,
+    Emmyrc, FileId, LuaQuickFix, QUICK_FIX_CODES,
+};
+use lsp_types::NumberOrString;
+use similar::TextDiff;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Default)]
+pub struct FixSummary {
+    pub fixed_count: usize,
+    pub conflict_count: usize,
+    pub file_count: usize,
+}
+
+/// Parses the codes of `--fix-codes`, they must have quick fixes
+pub fn parse_fix_codes(codes: &[String]) -> Result<Vec<DiagnosticCode>, String> {
+    let mut result = Vec::new();
+    for code in codes {
+        let diagnostic_code = DiagnosticCode::from_str(code).unwrap_or(DiagnosticCode::None);
+        if !QUICK_FIX_CODES.contains(&diagnostic_code) {
+            return Err(format!(
+                "No quick fix for diagnostic code `{}`, the fixable codes are: {}",
+                code,
+                QUICK_FIX_CODES
+                    .iter()
+                    .map(|code| code.get_name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        result.push(diagnostic_code);
+    }
+    Ok(result)
+}
+
+/// The fixable codes which are enabled by the config, they are fixed when `--fix-codes` is not
+/// given
+pub fn get_enabled_fix_codes(emmyrc: &Emmyrc) -> Vec<DiagnosticCode> {
+    QUICK_FIX_CODES
+        .iter()
+        .filter(|code| {
+            !emmyrc.diagnostics.disable.contains(code)
+                && (emmyrc.diagnostics.enables.contains(code) || is_code_default_enable(code))
+        })
+        .copied()
+        .collect()
+}
+
+/// Applies the quick fixes of the codes to the files, prints the unified diff instead of writing
+/// the files when `dry_run` is true
+pub fn run_fix(
+    analysis: &EmmyLuaAnalysis,
+    file_ids: Vec<FileId>,
+    workspace: &Path,
+    codes: &[DiagnosticCode],
+    dry_run: bool,
+) -> Result<FixSummary, String> {
+    let mut summary = FixSummary::default();
+    for file_id in file_ids {
+        let Some(mut fixes) = collect_file_fixes(analysis, file_id, codes) else {
+            continue;
+        };
+        if fixes.is_empty() {
+            continue;
+        }
+
+        // the fixes conflicting with the accepted ones are skipped, they can be applied by running
+        // `--fix` again
+        fixes.sort_by_key(|fix| fix.edits.iter().map(|edit| edit.range.start()).min());
+        let mut accepted_fixes: Vec<LuaQuickFix> = Vec::new();
+        for fix in fixes {
+            // the same fix is shared by several diagnostics
+            if accepted_fixes
+                .iter()
+                .any(|accepted| accepted.edits == fix.edits)
+            {
+                continue;
+            }
+            if accepted_fixes
+                .iter()
+                .any(|accepted| accepted.is_conflict(&fix))
+            {
+                summary.conflict_count += 1;
+                continue;
+            }
+
+            summary.fixed_count += 1;
+            accepted_fixes.push(fix);
+        }
+
+        let db = analysis.compilation.get_db();
+        let Some(document) = db.get_vfs().get_document(&file_id) else {
+            continue;
+        };
+        let text = document.get_text();
+        let edits = accepted_fixes
+            .into_iter()
+            .flat_map(|fix| fix.edits)
+            .collect::<Vec<_>>();
+        let Some(new_text) = apply_text_edits(text, &edits) else {
+            return Err(format!(
+                "Failed to apply the fixes to {:?}, the edits overlap",
+                document.get_file_path()
+            ));
+        };
+        if new_text == text {
+            continue;
+        }
+
+        summary.file_count += 1;
+        write_file_text(document.get_file_path(), workspace, text, new_text, dry_run)?;
+    }
+
+    Ok(summary)
+}
+
+/// Writes the new text to the file, prints the unified diff of the file instead when `dry_run` is
+/// true
+pub fn write_file_text(
+    file_path: &Path,
+    workspace: &Path,
+    text: &str,
+    new_text: String,
+    dry_run: bool,
+) -> Result<(), String> {
+    if dry_run {
+        let relative_path = file_path
+            .strip_prefix(workspace)
+            .unwrap_or(file_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let diff = TextDiff::from_lines(text, &new_text);
+        print!(
+            "{}",
+            diff.unified_diff().header(
+                &format!("a/{}", relative_path),
+                &format!("b/{}", relative_path)
+            )
+        );
+    } else {
+        std::fs::write(file_path, new_text)
+            .map_err(|e| format!("Failed to write {:?}: {}", file_path, e))?;
+    }
+
+    Ok(())
+}
+
+fn collect_file_fixes(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    codes: &[DiagnosticCode],
+) -> Option<Vec<LuaQuickFix>> {
+    let diagnostics = analysis.diagnose_file(file_id, CancellationToken::new())?;
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let document = semantic_model.get_document();
+
+    let mut fixes = Vec::new();
+    for diagnostic in diagnostics {
+        let Some(NumberOrString::String(code)) = &diagnostic.code else {
+            continue;
+        };
+        let Ok(code) = DiagnosticCode::from_str(code) else {
+            continue;
+        };
+        if !codes.contains(&code) {
+            continue;
+        }
+
+        let Some(range) = document.to_rowan_range(diagnostic.range) else {
+            continue;
+        };
+        if let Some(fix) = build_quick_fix(&semantic_model, code, range) {
+            fixes.push(fix);
+        }
+    }
+
+    Some(fixes)
+}
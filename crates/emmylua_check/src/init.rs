@@ -1,13 +1,21 @@
-use std::{path::PathBuf, str::FromStr, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
+use crate::doc_examples::collect_example_files;
 use emmylua_code_analysis::{
-    load_configs, load_workspace_files, update_code_style, EmmyLuaAnalysis, Emmyrc, LuaFileInfo,
+    extract_markdown_lua_blocks, load_configs, load_workspace_files, update_code_style,
+    EmmyLuaAnalysis, Emmyrc, LuaFileInfo, VirtualUrlGenerator,
 };
 
 pub fn load_workspace(
     workspace_folder: PathBuf,
     config_path: Option<PathBuf>,
     ignore: Option<Vec<String>>,
+    markdown: bool,
+    examples: bool,
 ) -> Option<EmmyLuaAnalysis> {
     let mut analysis = EmmyLuaAnalysis::new();
     analysis.init_std_lib(None);
@@ -47,7 +55,12 @@ pub fn load_workspace(
 
     analysis.update_config(Arc::new(emmyrc));
 
-    let file_infos = collect_files(&workspace_folders, &analysis.emmyrc, ignore);
+    let file_infos = collect_files(&workspace_folders, &analysis.emmyrc, ignore.clone());
+    let mut isolated_infos = if markdown {
+        collect_markdown_files(&main_path, &analysis.emmyrc, ignore)
+    } else {
+        Vec::new()
+    };
     let files = file_infos
         .into_iter()
         .filter_map(|file| {
@@ -69,6 +82,18 @@ pub fn load_workspace(
         })
         .collect();
     analysis.update_files_by_path(files);
+    // the examples use the module names of the loaded files
+    if examples {
+        let db = analysis.compilation.get_db();
+        isolated_infos.extend(collect_example_files(db, &main_path));
+    }
+    // the code blocks and the examples can not define globals for the workspace
+    analysis.update_isolated_files_by_path(
+        isolated_infos
+            .into_iter()
+            .map(|file| file.into_tuple())
+            .collect(),
+    );
 
     Some(analysis)
 }
@@ -100,6 +125,47 @@ pub fn collect_files(
     files
 }
 
+/// Collects the lua code blocks of the markdown files in the workspace, every block is loaded as
+/// the isolated document `<file>.md/block-<n>.lua`
+pub fn collect_markdown_files(
+    workspace: &Path,
+    emmyrc: &Emmyrc,
+    ignore: Option<Vec<String>>,
+) -> Vec<LuaFileInfo> {
+    let (_, exclude, exclude_dir) = calculate_include_and_exclude(emmyrc, ignore);
+    let encoding = &emmyrc.workspace.encoding;
+    let files = load_workspace_files(
+        workspace,
+        &vec!["**/*.md".to_string()],
+        &exclude,
+        &exclude_dir,
+        Some(encoding),
+    )
+    .unwrap_or_default();
+
+    files
+        .into_iter()
+        .flat_map(|file| extract_markdown_files(Path::new(&file.path), &file.content))
+        .collect()
+}
+
+pub fn extract_markdown_files(path: &Path, text: &str) -> Vec<LuaFileInfo> {
+    let url_generator = VirtualUrlGenerator {
+        base: path.to_path_buf(),
+    };
+    extract_markdown_lua_blocks(text)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, content)| LuaFileInfo {
+            path: url_generator
+                .new_path(&format!("block-{}.lua", idx + 1))
+                .to_string_lossy()
+                .to_string(),
+            content,
+        })
+        .collect()
+}
+
 pub fn calculate_include_and_exclude(
     emmyrc: &Emmyrc,
     ignore: Option<Vec<String>>,
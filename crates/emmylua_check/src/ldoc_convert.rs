@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use emmylua_code_analysis::{convert_ldoc_comments, EmmyLuaAnalysis, FileId};
+
+use crate::fix::write_file_text;
+
+/// Rewrites the LDoc comments of the files into the EmmyLua annotations, prints the unified diff
+/// instead of writing the files when `dry_run` is true. Returns the count of the changed files.
+pub fn run_convert_ldoc(
+    analysis: &EmmyLuaAnalysis,
+    file_ids: Vec<FileId>,
+    workspace: &Path,
+    dry_run: bool,
+) -> Result<usize, String> {
+    let db = analysis.compilation.get_db();
+    let mut file_count = 0;
+    for file_id in file_ids {
+        let Some(document) = db.get_vfs().get_document(&file_id) else {
+            continue;
+        };
+        let text = document.get_text();
+        let new_text = convert_ldoc_comments(text);
+        if new_text == text {
+            continue;
+        }
+
+        file_count += 1;
+        write_file_text(document.get_file_path(), workspace, text, new_text, dry_run)?;
+    }
+
+    Ok(file_count)
+}
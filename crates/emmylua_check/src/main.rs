@@ -1,23 +1,52 @@
+mod baseline;
+mod changed_files;
 mod cmd_args;
+mod coverage;
+mod doc_examples;
+mod fix;
 mod init;
+mod ldoc_convert;
 mod output;
+mod summary;
+mod watch;
 
+use baseline::Baseline;
 use cmd_args::CmdArgs;
-use emmylua_code_analysis::{DbIndex, FileId};
+use emmylua_code_analysis::{CheckerTimings, DbIndex, EmmyLuaAnalysis, Emmyrc, FileId};
+use lsp_types::Diagnostic;
 use output::output_result;
-use std::{error::Error, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, error::Error, path::PathBuf, sync::Arc, time::Instant};
 use structopt::StructOpt;
+use summary::Summary;
 use tokio_util::sync::CancellationToken;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
     let cmd_args = CmdArgs::from_args();
-    let mut workspace = cmd_args.workspace;
+    let mut workspace = cmd_args.workspace.clone();
     if !workspace.is_absolute() {
         workspace = std::env::current_dir()?.join(workspace);
     }
 
-    let analysis = match init::load_workspace(workspace.clone(), cmd_args.config, cmd_args.ignore) {
+    let baseline = match &cmd_args.baseline {
+        Some(path) => Some(Baseline::load(path)?),
+        None => None,
+    };
+
+    // the codes of `--fix-codes` are checked before the workspace is loaded
+    let explicit_fix_codes = match &cmd_args.fix_codes {
+        Some(codes) => Some(fix::parse_fix_codes(codes)?),
+        None => None,
+    };
+
+    let indexing_start = Instant::now();
+    let mut analysis = match init::load_workspace(
+        workspace.clone(),
+        cmd_args.config.clone(),
+        cmd_args.ignore.clone(),
+        cmd_args.markdown,
+        cmd_args.examples,
+    ) {
         Some(analysis) => analysis,
         None => {
             eprintln!("Failed to load workspace");
@@ -25,9 +54,118 @@ async fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
         }
     };
 
+    let indexing_time = indexing_start.elapsed();
+
+    if has_diagnostic_args(&cmd_args) {
+        let mut emmyrc = analysis.emmyrc.as_ref().clone();
+        apply_diagnostic_args(&mut emmyrc, &cmd_args);
+        analysis.update_config(Arc::new(emmyrc));
+    }
+
     let files = analysis.compilation.get_db().get_vfs().get_all_file_ids();
     let db = analysis.compilation.get_db();
-    let need_check_files = get_need_check_ids(db, files, &workspace);
+    let mut need_check_files = get_need_check_ids(db, files, &workspace);
+    let mut example_files = HashMap::new();
+    // the diagnostics of the examples are reported with their files
+    if cmd_args.examples {
+        let (files, examples) = doc_examples::split_example_file_ids(db, need_check_files);
+        need_check_files = files;
+        example_files = examples;
+    }
+    // the whole workspace is indexed, but only the changed files are diagnosed
+    if let Some(git_ref) = &cmd_args.changed_since {
+        let changed_files = changed_files::get_git_changed_files(&workspace, git_ref)?;
+        need_check_files =
+            changed_files::collect_changed_file_ids(db, need_check_files, changed_files);
+    } else if let Some(files) = &cmd_args.files {
+        let current_dir = std::env::current_dir()?;
+        let changed_files = files.iter().map(|file| current_dir.join(file)).collect();
+        need_check_files =
+            changed_files::collect_changed_file_ids(db, need_check_files, changed_files);
+    }
+    if (cmd_args.changed_since.is_some() || cmd_args.files.is_some()) && need_check_files.is_empty()
+    {
+        eprintln!("No changed files to check");
+        return Ok(());
+    }
+
+    if cmd_args.convert_ldoc || cmd_args.convert_ldoc_dry_run {
+        let file_count = ldoc_convert::run_convert_ldoc(
+            &analysis,
+            get_source_file_ids(db, need_check_files),
+            &workspace,
+            cmd_args.convert_ldoc_dry_run,
+        )?;
+        eprintln!("Converted the LDoc comments of {} files", file_count);
+        return Ok(());
+    }
+
+    if cmd_args.watch {
+        watch::run_watch(analysis, workspace, &cmd_args, need_check_files).await?;
+        return Ok(());
+    }
+
+    if cmd_args.coverage {
+        let need_check_files = get_source_file_ids(analysis.compilation.get_db(), need_check_files);
+        let report = coverage::CoverageReport::collect(&analysis, need_check_files, &workspace);
+        report.write(&cmd_args.output_format, cmd_args.output.clone());
+        if let Some(min_coverage) = cmd_args.min_coverage {
+            let percent = report.get_total_percent();
+            if percent < min_coverage {
+                eprintln!(
+                    "Type coverage {:.2}% is lower than the minimum of {:.2}%",
+                    percent, min_coverage
+                );
+                return Err("exit code: 1".into());
+            }
+        }
+        return Ok(());
+    }
+
+    if cmd_args.fix || cmd_args.fix_dry_run {
+        let fix_codes = match explicit_fix_codes {
+            Some(fix_codes) => {
+                // the codes given explicitly are fixed even if they are disabled
+                let mut emmyrc = analysis.emmyrc.as_ref().clone();
+                emmyrc.diagnostics.enables.extend(fix_codes.iter().copied());
+                emmyrc
+                    .diagnostics
+                    .disable
+                    .retain(|code| !fix_codes.contains(code));
+                analysis.update_config(Arc::new(emmyrc));
+                fix_codes
+            }
+            None => fix::get_enabled_fix_codes(&analysis.emmyrc),
+        };
+
+        let need_check_files = get_source_file_ids(analysis.compilation.get_db(), need_check_files);
+        let summary = fix::run_fix(
+            &analysis,
+            need_check_files,
+            &workspace,
+            &fix_codes,
+            cmd_args.fix_dry_run,
+        )?;
+        if cmd_args.fix_dry_run {
+            eprintln!(
+                "Found {} fixable problems in {} files, {} fixes skipped because of conflicts",
+                summary.fixed_count, summary.file_count, summary.conflict_count
+            );
+        } else {
+            eprintln!(
+                "Fixed {} problems in {} files, {} fixes skipped because of conflicts",
+                summary.fixed_count, summary.file_count, summary.conflict_count
+            );
+        }
+        return Ok(());
+    }
+
+    let summary = if cmd_args.summary || cmd_args.summary_json.is_some() {
+        Some(Summary::new(indexing_time))
+    } else {
+        None
+    };
+    let with_timings = summary.is_some();
 
     let (sender, receiver) = tokio::sync::mpsc::channel(100);
     let analysis = Arc::new(analysis);
@@ -35,21 +173,33 @@ async fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
     for file_id in need_check_files.clone() {
         let sender = sender.clone();
         let analysis = analysis.clone();
+        let example_ids = example_files.get(&file_id).cloned().unwrap_or_default();
         tokio::spawn(async move {
-            let cancel_token = CancellationToken::new();
-            let diagnostics = analysis.diagnose_file(file_id, cancel_token);
-            sender.send((file_id, diagnostics)).await.unwrap();
+            let (mut diagnostics, mut timings) = diagnose_file(&analysis, file_id, with_timings);
+            for example_id in example_ids {
+                let (example_diagnostics, example_timings) =
+                    diagnose_file(&analysis, example_id, with_timings);
+                if let Some(example_diagnostics) = example_diagnostics {
+                    diagnostics
+                        .get_or_insert_with(Vec::new)
+                        .extend(example_diagnostics);
+                }
+                timings.extend(example_timings);
+            }
+            sender.send((file_id, diagnostics, timings)).await.unwrap();
         });
     }
+    // the receiver stops when all the senders are dropped
+    drop(sender);
 
     let exit_code = output_result(
         need_check_files.len(),
         db,
         workspace,
         receiver,
-        cmd_args.output_format,
-        cmd_args.output,
-        cmd_args.warnings_as_errors,
+        &cmd_args,
+        baseline,
+        summary,
     )
     .await;
 
@@ -61,7 +211,23 @@ async fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
     Ok(())
 }
 
-fn get_need_check_ids(db: &DbIndex, files: Vec<FileId>, workspace: &PathBuf) -> Vec<FileId> {
+fn diagnose_file(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    with_timings: bool,
+) -> (Option<Vec<Diagnostic>>, CheckerTimings) {
+    let cancel_token = CancellationToken::new();
+    if with_timings {
+        match analysis.diagnose_file_with_timings(file_id, cancel_token) {
+            Some((diagnostics, timings)) => (Some(diagnostics), timings),
+            None => (None, Vec::new()),
+        }
+    } else {
+        (analysis.diagnose_file(file_id, cancel_token), Vec::new())
+    }
+}
+
+pub fn get_need_check_ids(db: &DbIndex, files: Vec<FileId>, workspace: &PathBuf) -> Vec<FileId> {
     let mut need_check_files = Vec::new();
     for file_id in files {
         let file_path = db.get_vfs().get_file_path(&file_id).unwrap();
@@ -72,3 +238,39 @@ fn get_need_check_ids(db: &DbIndex, files: Vec<FileId>, workspace: &PathBuf) ->
 
     need_check_files
 }
+
+// the isolated documents like the markdown code blocks are only checked, they are not files which
+// can be rewritten or measured
+fn get_source_file_ids(db: &DbIndex, file_ids: Vec<FileId>) -> Vec<FileId> {
+    let vfs = db.get_vfs();
+    file_ids
+        .into_iter()
+        .filter(|file_id| !vfs.is_isolated(file_id))
+        .collect()
+}
+
+fn has_diagnostic_args(cmd_args: &CmdArgs) -> bool {
+    cmd_args.enable.is_some() || cmd_args.disable.is_some() || cmd_args.severity.is_some()
+}
+
+// the command line arguments are layered over the loaded config
+fn apply_diagnostic_args(emmyrc: &mut Emmyrc, cmd_args: &CmdArgs) {
+    let diagnostics = &mut emmyrc.diagnostics;
+    for code in cmd_args.enable.iter().flatten() {
+        diagnostics.disable.retain(|disabled| disabled != code);
+        if !diagnostics.enables.contains(code) {
+            diagnostics.enables.push(*code);
+        }
+    }
+
+    for code in cmd_args.disable.iter().flatten() {
+        diagnostics.enables.retain(|enabled| enabled != code);
+        if !diagnostics.disable.contains(code) {
+            diagnostics.disable.push(*code);
+        }
+    }
+
+    for (code, severity) in cmd_args.severity.iter().flatten() {
+        diagnostics.severity.insert(*code, *severity);
+    }
+}
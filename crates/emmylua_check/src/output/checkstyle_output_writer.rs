@@ -0,0 +1,67 @@
+use std::{fs::File, path::PathBuf};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::cmd_args::OutputDestination;
+
+use super::{
+    create_output_file, escape_xml, get_diagnostic_code, get_relative_path, get_report_path,
+    write_output, OutputWriter,
+};
+
+#[derive(Debug)]
+pub struct CheckstyleOutputWriter {
+    workspace: PathBuf,
+    output: Option<File>,
+    content: String,
+}
+
+impl CheckstyleOutputWriter {
+    pub fn new(workspace: PathBuf, output: OutputDestination) -> Self {
+        let output = create_output_file(output);
+        let mut content = String::new();
+        content.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        content.push_str("<checkstyle version=\"4.3\">\n");
+        CheckstyleOutputWriter {
+            workspace,
+            output,
+            content,
+        }
+    }
+}
+
+impl OutputWriter for CheckstyleOutputWriter {
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        if diagnostics.is_empty() {
+            return;
+        }
+
+        let file_path = get_report_path(db, file_id);
+        let file_path = get_relative_path(&self.workspace, file_path);
+        self.content
+            .push_str(&format!("  <file name=\"{}\">\n", escape_xml(&file_path)));
+        for diagnostic in &diagnostics {
+            let severity = match diagnostic.severity {
+                Some(DiagnosticSeverity::WARNING) => "warning",
+                Some(DiagnosticSeverity::INFORMATION) | Some(DiagnosticSeverity::HINT) => "info",
+                _ => "error",
+            };
+            // checkstyle lines and columns are 1-based
+            self.content.push_str(&format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"emmylua.{}\"/>\n",
+                diagnostic.range.start.line + 1,
+                diagnostic.range.start.character + 1,
+                severity,
+                escape_xml(&diagnostic.message),
+                escape_xml(&get_diagnostic_code(diagnostic)),
+            ));
+        }
+        self.content.push_str("  </file>\n");
+    }
+
+    fn finish(&mut self) {
+        self.content.push_str("</checkstyle>");
+        write_output(self.output.as_mut(), &self.content);
+    }
+}
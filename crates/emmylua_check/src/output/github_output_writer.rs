@@ -0,0 +1,68 @@
+use std::{fs::File, io::Write, path::PathBuf};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::cmd_args::OutputDestination;
+
+use super::{
+    create_output_file, get_diagnostic_code, get_relative_path, get_report_path, OutputWriter,
+};
+
+/// Writes the diagnostics as the workflow commands of GitHub Actions, eg:
+/// `::warning file=a.lua,line=1,col=7,endLine=1,endColumn=8,title=unused::message`
+#[derive(Debug)]
+pub struct GithubOutputWriter {
+    workspace: PathBuf,
+    output: Option<File>,
+}
+
+impl GithubOutputWriter {
+    pub fn new(workspace: PathBuf, output: OutputDestination) -> Self {
+        let output = create_output_file(output);
+        GithubOutputWriter { workspace, output }
+    }
+}
+
+impl OutputWriter for GithubOutputWriter {
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        let file_path = get_report_path(db, file_id);
+        let file_path = get_relative_path(&self.workspace, file_path);
+        for diagnostic in &diagnostics {
+            let command = match diagnostic.severity {
+                Some(DiagnosticSeverity::WARNING) => "warning",
+                Some(DiagnosticSeverity::INFORMATION) | Some(DiagnosticSeverity::HINT) => "notice",
+                _ => "error",
+            };
+            let range = diagnostic.range;
+            let line = format!(
+                "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+                command,
+                escape_property(&file_path),
+                range.start.line + 1,
+                range.start.character + 1,
+                range.end.line + 1,
+                range.end.character + 1,
+                escape_property(&get_diagnostic_code(diagnostic)),
+                escape_data(&diagnostic.message),
+            );
+
+            match self.output.as_mut() {
+                Some(output) => writeln!(output, "{}", line).unwrap(),
+                None => println!("{}", line),
+            }
+        }
+    }
+
+    fn finish(&mut self) {}
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
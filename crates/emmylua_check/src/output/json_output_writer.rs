@@ -6,7 +6,7 @@ use serde_json::{json, Value};
 
 use crate::cmd_args::OutputDestination;
 
-use super::OutputWriter;
+use super::{create_output_file, get_report_path, OutputWriter};
 
 #[derive(Debug)]
 pub struct JsonOutputWriter {
@@ -17,18 +17,7 @@ pub struct JsonOutputWriter {
 
 impl JsonOutputWriter {
     pub fn new(output: OutputDestination) -> Self {
-        let output = match output {
-            OutputDestination::Stdout => None,
-            OutputDestination::File(path) => {
-                if let Some(parent) = path.parent() {
-                    if !parent.exists() {
-                        std::fs::create_dir_all(parent).unwrap();
-                    }
-                }
-
-                Some(std::fs::File::create(path).unwrap())
-            }
-        };
+        let output = create_output_file(output);
         JsonOutputWriter {
             output,
             first_write: true,
@@ -39,7 +28,7 @@ impl JsonOutputWriter {
 
 impl OutputWriter for JsonOutputWriter {
     fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
-        let file_path = db.get_vfs().get_file_path(&file_id).unwrap();
+        let file_path = get_report_path(db, file_id);
         let file_path = file_path.to_str().unwrap();
         let mut json_diagnostics = Vec::new();
         for diagnostic in diagnostics {
@@ -0,0 +1,102 @@
+use std::{fs::File, path::PathBuf};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::cmd_args::OutputDestination;
+
+use super::{
+    create_output_file, escape_xml, get_diagnostic_code, get_relative_path, get_report_path,
+    write_output, OutputWriter,
+};
+
+#[derive(Debug)]
+pub struct JunitOutputWriter {
+    workspace: PathBuf,
+    output: Option<File>,
+    test_cases: Vec<String>,
+    failures: usize,
+}
+
+impl JunitOutputWriter {
+    pub fn new(workspace: PathBuf, output: OutputDestination) -> Self {
+        let output = create_output_file(output);
+        JunitOutputWriter {
+            workspace,
+            output,
+            test_cases: Vec::new(),
+            failures: 0,
+        }
+    }
+}
+
+impl OutputWriter for JunitOutputWriter {
+    // every checked file is a test case, it fails when there are errors or warnings
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        let file_path = get_report_path(db, file_id);
+        let file_path = escape_xml(&get_relative_path(&self.workspace, file_path));
+
+        let mut failure_count = 0;
+        let mut details = String::new();
+        for diagnostic in &diagnostics {
+            let severity = match diagnostic.severity {
+                Some(DiagnosticSeverity::WARNING) => "warning",
+                Some(DiagnosticSeverity::INFORMATION) => "info",
+                Some(DiagnosticSeverity::HINT) => "hint",
+                _ => "error",
+            };
+            if matches!(severity, "error" | "warning") {
+                failure_count += 1;
+            }
+            details.push_str(&format!(
+                "{}:{}:{}: {} [{}] {}\n",
+                file_path,
+                diagnostic.range.start.line + 1,
+                diagnostic.range.start.character + 1,
+                severity,
+                escape_xml(&get_diagnostic_code(diagnostic)),
+                escape_xml(&diagnostic.message),
+            ));
+        }
+
+        let mut test_case = format!(
+            "    <testcase name=\"{}\" classname=\"emmylua_check\"",
+            file_path
+        );
+        if failure_count > 0 {
+            self.failures += 1;
+            test_case.push_str(&format!(
+                ">\n      <failure message=\"{} problems\" type=\"emmylua_check\">{}</failure>\n    </testcase>\n",
+                failure_count, details
+            ));
+        } else if !details.is_empty() {
+            test_case.push_str(&format!(
+                ">\n      <system-out>{}</system-out>\n    </testcase>\n",
+                details
+            ));
+        } else {
+            test_case.push_str("/>\n");
+        }
+        self.test_cases.push(test_case);
+    }
+
+    fn finish(&mut self) {
+        let tests = self.test_cases.len();
+        let mut content = String::new();
+        content.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        content.push_str(&format!(
+            "<testsuites name=\"emmylua_check\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+            tests, self.failures
+        ));
+        content.push_str(&format!(
+            "  <testsuite name=\"emmylua_check\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+            tests, self.failures
+        ));
+        for test_case in &self.test_cases {
+            content.push_str(test_case);
+        }
+        content.push_str("  </testsuite>\n");
+        content.push_str("</testsuites>");
+        write_output(self.output.as_mut(), &content);
+    }
+}
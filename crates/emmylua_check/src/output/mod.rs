@@ -1,33 +1,98 @@
+mod checkstyle_output_writer;
+mod github_output_writer;
 mod json_output_writer;
+mod junit_output_writer;
+mod sarif_output_writer;
 mod text_output_writer;
 
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
-use emmylua_code_analysis::{DbIndex, FileId};
-use lsp_types::Diagnostic;
+use emmylua_code_analysis::{CheckerTimings, DbIndex, DiagnosticCode, FileId};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
 use tokio::sync::mpsc::Receiver;
 
-use crate::cmd_args::{OutputDestination, OutputFormat};
+use crate::{
+    baseline::{make_baseline_issue, Baseline},
+    cmd_args::{CmdArgs, OutputDestination, OutputFormat},
+    summary::Summary,
+};
 
 pub async fn output_result(
     total_count: usize,
     db: &DbIndex,
     workspace: PathBuf,
-    mut receiver: Receiver<(FileId, Option<Vec<Diagnostic>>)>,
-    output_format: OutputFormat,
-    output: OutputDestination,
-    warnings_as_errors: bool,
+    mut receiver: Receiver<(FileId, Option<Vec<Diagnostic>>, CheckerTimings)>,
+    cmd_args: &CmdArgs,
+    mut baseline: Option<Baseline>,
+    mut summary: Option<Summary>,
 ) -> i32 {
-    let mut writer: Box<dyn OutputWriter> = match output_format {
+    let output = cmd_args.output.clone();
+    let warnings_as_errors = cmd_args.warnings_as_errors;
+    let min_severity = cmd_args.min_severity.map(DiagnosticSeverity::from);
+    let output_workspace = workspace.clone();
+    let mut writer: Box<dyn OutputWriter> = match cmd_args.output_format {
         OutputFormat::Json => Box::new(json_output_writer::JsonOutputWriter::new(output)),
-        OutputFormat::Text => Box::new(text_output_writer::TextOutputWriter::new(workspace)),
+        OutputFormat::Checkstyle => Box::new(
+            checkstyle_output_writer::CheckstyleOutputWriter::new(output_workspace, output),
+        ),
+        OutputFormat::Junit => Box::new(junit_output_writer::JunitOutputWriter::new(
+            output_workspace,
+            output,
+        )),
+        OutputFormat::Github => Box::new(github_output_writer::GithubOutputWriter::new(
+            output_workspace,
+            output,
+        )),
+        OutputFormat::Sarif => Box::new(sarif_output_writer::SarifOutputWriter::new(
+            output_workspace,
+            output,
+        )),
+        OutputFormat::Text => Box::new(text_output_writer::TextOutputWriter::new(output_workspace)),
     };
 
+    let mut new_baseline = cmd_args
+        .write_baseline
+        .as_ref()
+        .map(|_| Baseline::default());
+
     let mut has_error = false;
+    let mut warning_count = 0;
     let mut count = 0;
-    while let Some((file_id, diagnostics)) = receiver.recv().await {
+    while let Some((file_id, diagnostics, timings)) = receiver.recv().await {
         count += 1;
-        if let Some(diagnostics) = diagnostics {
+        if let Some(summary) = summary.as_mut() {
+            summary.add_timings(&timings);
+        }
+        if let Some(mut diagnostics) = diagnostics {
+            if let Some(min_severity) = min_severity {
+                diagnostics.retain(|diagnostic| is_severe_enough(diagnostic, min_severity));
+            }
+
+            if new_baseline.is_some() || baseline.is_some() {
+                let issues = diagnostics
+                    .iter()
+                    .map(|diagnostic| make_baseline_issue(db, &workspace, file_id, diagnostic))
+                    .collect::<Vec<_>>();
+                if let Some(new_baseline) = new_baseline.as_mut() {
+                    for issue in &issues {
+                        new_baseline.add(issue.clone());
+                    }
+                }
+                // only the diagnostics which are not in the baseline are reported
+                if let Some(baseline) = baseline.as_mut() {
+                    let mut issues = issues.iter();
+                    diagnostics.retain(|_| !baseline.take(issues.next().unwrap()));
+                }
+            }
+
+            warning_count += diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::WARNING))
+                .count();
             for diagnostic in &diagnostics {
                 if diagnostic.severity == Some(lsp_types::DiagnosticSeverity::ERROR) {
                     has_error = true;
@@ -39,6 +104,10 @@ pub async fn output_result(
                     break;
                 }
             }
+            if let Some(summary) = summary.as_mut() {
+                let file_path = get_report_path(db, file_id);
+                summary.add_file(get_relative_path(&workspace, file_path), &diagnostics);
+            }
             writer.write(db, file_id, diagnostics);
         }
 
@@ -49,6 +118,43 @@ pub async fn output_result(
 
     writer.finish();
 
+    if let Some(summary) = summary.as_mut() {
+        summary.finish();
+        if cmd_args.summary {
+            summary.print(cmd_args.summary_top);
+        }
+        if let Some(path) = &cmd_args.summary_json {
+            if let Err(e) = summary.write_json(path, cmd_args.summary_top) {
+                eprintln!("{}", e);
+                return 1;
+            }
+        }
+    }
+
+    if let (Some(path), Some(new_baseline)) = (&cmd_args.write_baseline, &new_baseline) {
+        if let Err(e) = new_baseline.save(path) {
+            eprintln!("{}", e);
+            return 1;
+        }
+        eprintln!(
+            "Baseline with {} issues written to {:?}",
+            new_baseline.len(),
+            path
+        );
+        // all the current diagnostics are recorded
+        return 0;
+    }
+
+    if let Some(max_warnings) = cmd_args.max_warnings {
+        if warning_count > max_warnings {
+            eprintln!(
+                "Found {} warnings, which exceeds the maximum of {}",
+                warning_count, max_warnings
+            );
+            has_error = true;
+        }
+    }
+
     if has_error {
         1
     } else {
@@ -56,8 +162,84 @@ pub async fn output_result(
     }
 }
 
+// the diagnostics without severity are treated as errors
+fn is_severe_enough(diagnostic: &Diagnostic, min_severity: DiagnosticSeverity) -> bool {
+    let rank = |severity: DiagnosticSeverity| match severity {
+        DiagnosticSeverity::ERROR => 4,
+        DiagnosticSeverity::WARNING => 3,
+        DiagnosticSeverity::INFORMATION => 2,
+        _ => 1,
+    };
+    let severity = diagnostic.severity.unwrap_or(DiagnosticSeverity::ERROR);
+    rank(severity) >= rank(min_severity)
+}
+
 trait OutputWriter {
     fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>);
 
     fn finish(&mut self);
 }
+
+pub fn create_output_file(output: OutputDestination) -> Option<File> {
+    match output {
+        OutputDestination::Stdout => None,
+        OutputDestination::File(path) => {
+            if let Some(parent) = path.parent() {
+                if !parent.exists() {
+                    std::fs::create_dir_all(parent).unwrap();
+                }
+            }
+
+            Some(std::fs::File::create(path).unwrap())
+        }
+    }
+}
+
+// write the whole report to the output file or stdout
+pub fn write_output(output: Option<&mut File>, content: &str) {
+    match output {
+        Some(output) => output.write_all(content.as_bytes()).unwrap(),
+        None => println!("{}", content),
+    }
+}
+
+/// The isolated documents are loaded under the path of the file which contains them, eg:
+/// `README.md/block-1.lua`, their diagnostics are reported at that file
+pub fn get_report_path(db: &DbIndex, file_id: FileId) -> &Path {
+    let vfs = db.get_vfs();
+    let file_path = vfs.get_file_path(&file_id).unwrap();
+    match file_path.parent() {
+        Some(parent) if vfs.is_isolated(&file_id) => parent,
+        _ => file_path,
+    }
+}
+
+pub fn get_relative_path(workspace: &Path, file_path: &Path) -> String {
+    let file_path = file_path.strip_prefix(workspace).unwrap_or(file_path);
+    file_path.to_string_lossy().replace('\\', "/")
+}
+
+fn get_diagnostic_code(diagnostic: &Diagnostic) -> String {
+    match &diagnostic.code {
+        Some(lsp_types::NumberOrString::String(code)) => code.clone(),
+        Some(lsp_types::NumberOrString::Number(code)) => code.to_string(),
+        None => DiagnosticCode::None.get_name().to_string(),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            '\n' => result.push_str("&#10;"),
+            '\r' => result.push_str("&#13;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,184 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use emmylua_code_analysis::{
+    file_path_to_uri, get_default_severity, is_code_default_enable, uri_to_file_path, DbIndex,
+    DiagnosticCode, FileId,
+};
+use lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+use serde_json::{json, Value};
+
+use crate::cmd_args::OutputDestination;
+
+use super::{create_output_file, get_diagnostic_code, get_report_path, write_output, OutputWriter};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SRC_ROOT: &str = "%SRCROOT%";
+
+#[derive(Debug)]
+pub struct SarifOutputWriter {
+    workspace: PathBuf,
+    output: Option<File>,
+    rules: Vec<DiagnosticCode>,
+    results: Vec<Value>,
+}
+
+impl SarifOutputWriter {
+    pub fn new(workspace: PathBuf, output: OutputDestination) -> Self {
+        let output = create_output_file(output);
+        let rules = DiagnosticCode::all()
+            .into_iter()
+            .filter(|code| *code != DiagnosticCode::None)
+            .collect();
+        SarifOutputWriter {
+            workspace,
+            output,
+            rules,
+            results: Vec::new(),
+        }
+    }
+
+    fn make_location(&self, path: &Path, range: &Range, message: Option<&str>) -> Value {
+        let mut location = json!({
+            "physicalLocation": {
+                "artifactLocation": self.make_artifact_location(path),
+                "region": make_region(range),
+            }
+        });
+        if let Some(message) = message {
+            location["message"] = json!({ "text": message });
+        }
+        location
+    }
+
+    // the files in the workspace are relative to `%SRCROOT%`
+    fn make_artifact_location(&self, path: &Path) -> Value {
+        match path.strip_prefix(&self.workspace) {
+            Ok(relative_path) => json!({
+                "uri": relative_path.to_string_lossy().replace('\\', "/"),
+                "uriBaseId": SRC_ROOT,
+            }),
+            Err(_) => json!({
+                "uri": file_path_to_uri(&path.to_path_buf())
+                    .map(|uri| uri.as_str().to_string())
+                    .unwrap_or_else(|| path.to_string_lossy().to_string()),
+            }),
+        }
+    }
+
+    fn make_result(&self, file_path: &Path, diagnostic: Diagnostic) -> Value {
+        let code = get_diagnostic_code(&diagnostic);
+
+        let mut result = json!({
+            "ruleId": code,
+            "level": get_level(diagnostic.severity),
+            "message": { "text": diagnostic.message },
+            "locations": [self.make_location(file_path, &diagnostic.range, None)],
+        });
+        if let Some(index) = self.rules.iter().position(|rule| rule.get_name() == code) {
+            result["ruleIndex"] = json!(index);
+        }
+
+        if let Some(related_information) = &diagnostic.related_information {
+            let related_locations = related_information
+                .iter()
+                .enumerate()
+                .filter_map(|(id, info)| {
+                    let path = uri_to_file_path(&info.location.uri)?;
+                    let mut location =
+                        self.make_location(&path, &info.location.range, Some(&info.message));
+                    location["id"] = json!(id);
+                    Some(location)
+                })
+                .collect::<Vec<_>>();
+            if !related_locations.is_empty() {
+                result["relatedLocations"] = json!(related_locations);
+            }
+        }
+
+        result
+    }
+
+    fn make_sarif_log(&mut self) -> Value {
+        let rules = self
+            .rules
+            .iter()
+            .map(|code| {
+                let description = code.get_description();
+                json!({
+                    "id": code.get_name(),
+                    "name": format!("{:?}", code),
+                    "shortDescription": { "text": description },
+                    "fullDescription": { "text": description },
+                    "defaultConfiguration": {
+                        "enabled": is_code_default_enable(code),
+                        "level": get_level(Some(get_default_severity(*code))),
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut run = json!({
+            "tool": {
+                "driver": {
+                    "name": "emmylua_check",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": std::mem::take(&mut self.results),
+        });
+        if let Some(uri) = file_path_to_uri(&self.workspace) {
+            // the base uri must end with a slash
+            let mut uri = uri.as_str().to_string();
+            if !uri.ends_with('/') {
+                uri.push('/');
+            }
+            run["originalUriBaseIds"] = json!({ SRC_ROOT: { "uri": uri } });
+        }
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [run],
+        })
+    }
+}
+
+impl OutputWriter for SarifOutputWriter {
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        let file_path = get_report_path(db, file_id).to_path_buf();
+        for diagnostic in diagnostics {
+            let result = self.make_result(&file_path, diagnostic);
+            self.results.push(result);
+        }
+    }
+
+    fn finish(&mut self) {
+        let sarif_log = self.make_sarif_log();
+        let pretty_json = serde_json::to_string_pretty(&sarif_log).unwrap();
+        write_output(self.output.as_mut(), &pretty_json);
+    }
+}
+
+fn get_level(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) | None => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        _ => "note",
+    }
+}
+
+// sarif lines and columns are 1-based
+fn make_region(range: &Range) -> Value {
+    json!({
+        "startLine": range.start.line + 1,
+        "startColumn": range.start.character + 1,
+        "endLine": range.end.line + 1,
+        "endColumn": range.end.character + 1,
+    })
+}
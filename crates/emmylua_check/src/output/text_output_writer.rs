@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use emmylua_code_analysis::{DbIndex, FileId};
 use lsp_types::Diagnostic;
 
-use super::OutputWriter;
+use super::{get_report_path, OutputWriter};
 use ariadne::{Color, Label, Report, ReportKind, Source};
 
 #[derive(Debug)]
@@ -23,7 +23,7 @@ impl OutputWriter for TextOutputWriter {
             return;
         }
 
-        let mut file_path = db.get_vfs().get_file_path(&file_id).unwrap().clone();
+        let mut file_path = get_report_path(db, file_id).to_path_buf();
         if let Ok(new_file_path) = file_path.strip_prefix(&self.workspace) {
             file_path = new_file_path.to_path_buf();
         }
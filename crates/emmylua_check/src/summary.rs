@@ -0,0 +1,219 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+use serde::Serialize;
+
+/// The statistics of a check run, it is printed as a table or written as json at the end
+#[derive(Debug)]
+pub struct Summary {
+    indexing_time: Duration,
+    check_start: Instant,
+    check_time: Duration,
+    file_count: usize,
+    code_counts: HashMap<String, usize>,
+    severity_counts: [usize; 4],
+    file_issue_counts: Vec<(String, usize)>,
+    checker_timings: HashMap<&'static str, Duration>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SummaryJson {
+    files: usize,
+    severity: BTreeMap<&'static str, usize>,
+    codes: BTreeMap<String, usize>,
+    top_files: Vec<FileIssueCount>,
+    timing: TimingJson,
+}
+
+#[derive(Debug, Serialize)]
+struct FileIssueCount {
+    file: String,
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimingJson {
+    indexing_ms: f64,
+    checking_ms: f64,
+    checkers_ms: BTreeMap<&'static str, f64>,
+}
+
+const SEVERITY_NAMES: [&str; 4] = ["error", "warning", "information", "hint"];
+
+impl Summary {
+    pub fn new(indexing_time: Duration) -> Self {
+        Self {
+            indexing_time,
+            check_start: Instant::now(),
+            check_time: Duration::default(),
+            file_count: 0,
+            code_counts: HashMap::new(),
+            severity_counts: [0; 4],
+            file_issue_counts: Vec::new(),
+            checker_timings: HashMap::new(),
+        }
+    }
+
+    pub fn add_timings(&mut self, timings: &[(&'static str, Duration)]) {
+        for (name, duration) in timings {
+            *self.checker_timings.entry(name).or_default() += *duration;
+        }
+    }
+
+    pub fn add_file(&mut self, file: String, diagnostics: &[Diagnostic]) {
+        self.file_count += 1;
+        for diagnostic in diagnostics {
+            let code = match &diagnostic.code {
+                Some(lsp_types::NumberOrString::String(code)) => code.clone(),
+                Some(lsp_types::NumberOrString::Number(code)) => code.to_string(),
+                None => "none".to_string(),
+            };
+            *self.code_counts.entry(code).or_insert(0) += 1;
+            self.severity_counts[get_severity_index(diagnostic.severity)] += 1;
+        }
+
+        if !diagnostics.is_empty() {
+            self.file_issue_counts.push((file, diagnostics.len()));
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.check_time = self.check_start.elapsed();
+    }
+
+    pub fn print(&self, top: usize) {
+        let mut lines = Vec::new();
+        lines.push(format!(
+            "Summary: {} files checked, {} errors, {} warnings, {} information, {} hints",
+            self.file_count,
+            self.severity_counts[0],
+            self.severity_counts[1],
+            self.severity_counts[2],
+            self.severity_counts[3]
+        ));
+
+        let code_counts = self.get_sorted_code_counts();
+        if !code_counts.is_empty() {
+            lines.push(String::from("Issues by code:"));
+            for (code, count) in code_counts {
+                lines.push(format!("  {:<40} {:>8}", code, count));
+            }
+        }
+
+        let top_files = self.get_top_files(top);
+        if !top_files.is_empty() {
+            lines.push(String::from("Top files:"));
+            for (file, count) in top_files {
+                lines.push(format!("  {:<40} {:>8}", file, count));
+            }
+        }
+
+        lines.push(String::from("Timing:"));
+        lines.push(format!(
+            "  {:<40} {:>8}",
+            "indexing",
+            format_duration(self.indexing_time)
+        ));
+        lines.push(format!(
+            "  {:<40} {:>8}",
+            "checking (wall clock)",
+            format_duration(self.check_time)
+        ));
+        // the time of the checkers is summed over all the files and threads
+        for (name, duration) in self.get_sorted_checker_timings() {
+            lines.push(format!("  {:<40} {:>8}", name, format_duration(duration)));
+        }
+
+        eprintln!("{}", lines.join("\n"));
+    }
+
+    pub fn write_json(&self, path: &PathBuf, top: usize) -> Result<(), String> {
+        let summary_json = SummaryJson {
+            files: self.file_count,
+            severity: SEVERITY_NAMES
+                .iter()
+                .zip(self.severity_counts)
+                .map(|(name, count)| (*name, count))
+                .collect(),
+            codes: self
+                .code_counts
+                .iter()
+                .map(|(code, count)| (code.clone(), *count))
+                .collect(),
+            top_files: self
+                .get_top_files(top)
+                .into_iter()
+                .map(|(file, count)| FileIssueCount { file, count })
+                .collect(),
+            timing: TimingJson {
+                indexing_ms: to_millis(self.indexing_time),
+                checking_ms: to_millis(self.check_time),
+                checkers_ms: self
+                    .checker_timings
+                    .iter()
+                    .map(|(name, duration)| (*name, to_millis(*duration)))
+                    .collect(),
+            },
+        };
+
+        let content = serde_json::to_string_pretty(&summary_json).map_err(|e| e.to_string())?;
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+        }
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write summary {:?}: {}", path, e))
+    }
+
+    fn get_sorted_code_counts(&self) -> Vec<(String, usize)> {
+        let mut code_counts = self
+            .code_counts
+            .iter()
+            .map(|(code, count)| (code.clone(), *count))
+            .collect::<Vec<_>>();
+        code_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        code_counts
+    }
+
+    fn get_top_files(&self, top: usize) -> Vec<(String, usize)> {
+        let mut file_counts = self.file_issue_counts.clone();
+        file_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        file_counts.truncate(top);
+        file_counts
+    }
+
+    fn get_sorted_checker_timings(&self) -> Vec<(&'static str, Duration)> {
+        let mut timings = self
+            .checker_timings
+            .iter()
+            .map(|(name, duration)| (*name, *duration))
+            .collect::<Vec<_>>();
+        timings.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        timings
+    }
+}
+
+// the diagnostics without severity are counted as errors
+fn get_severity_index(severity: Option<DiagnosticSeverity>) -> usize {
+    match severity {
+        Some(DiagnosticSeverity::WARNING) => 1,
+        Some(DiagnosticSeverity::INFORMATION) => 2,
+        Some(DiagnosticSeverity::HINT) => 3,
+        _ => 0,
+    }
+}
+
+fn to_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}ms", to_millis(duration))
+}
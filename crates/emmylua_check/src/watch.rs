@@ -0,0 +1,263 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use emmylua_code_analysis::{file_path_to_uri, read_file_with_encoding, EmmyLuaAnalysis, FileId};
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio_util::sync::CancellationToken;
+use wax::{Glob, Pattern};
+
+use crate::{
+    baseline::Baseline,
+    cmd_args::CmdArgs,
+    get_need_check_ids,
+    init::{calculate_include_and_exclude, extract_markdown_files},
+    output::output_result,
+};
+
+// the editors often write a file in several steps
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Checks the files, then keeps watching the workspace and rechecks the changed files and the
+/// files depending on them
+pub async fn run_watch(
+    mut analysis: EmmyLuaAnalysis,
+    workspace: PathBuf,
+    cmd_args: &CmdArgs,
+    need_check_files: Vec<FileId>,
+) -> Result<(), String> {
+    check_files(&analysis, &workspace, cmd_args, need_check_files).await?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = RecommendedWatcher::new(
+        move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        },
+        Config::default(),
+    )
+    .map_err(|e| format!("Failed to create the file watcher: {}", e))?;
+    watcher
+        .watch(&workspace, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {:?}: {}", workspace, e))?;
+
+    let file_filter = WatchFileFilter::new(&analysis, cmd_args.ignore.clone(), cmd_args.markdown);
+    eprintln!("Watching for changes in {:?}...", workspace);
+    while let Some(changed_paths) = recv_changed_paths(&mut rx).await {
+        let changed_paths = changed_paths
+            .into_iter()
+            .filter(|path| file_filter.is_match(&workspace, path))
+            .collect::<Vec<_>>();
+        if changed_paths.is_empty() {
+            continue;
+        }
+
+        let file_ids = update_changed_files(&mut analysis, &workspace, changed_paths);
+        eprintln!("\nChange detected, checking {} files", file_ids.len());
+        check_files(&analysis, &workspace, cmd_args, file_ids).await?;
+        eprintln!("Watching for changes in {:?}...", workspace);
+    }
+
+    Ok(())
+}
+
+async fn recv_changed_paths(rx: &mut UnboundedReceiver<notify::Event>) -> Option<Vec<PathBuf>> {
+    let mut changed_paths = HashSet::new();
+    let event = rx.recv().await?;
+    add_event_paths(&mut changed_paths, event);
+    // collect the events until the workspace is quiet
+    while let Ok(Some(event)) = tokio::time::timeout(DEBOUNCE_INTERVAL, rx.recv()).await {
+        add_event_paths(&mut changed_paths, event);
+    }
+
+    let mut changed_paths = changed_paths.into_iter().collect::<Vec<_>>();
+    changed_paths.sort();
+    Some(changed_paths)
+}
+
+fn add_event_paths(changed_paths: &mut HashSet<PathBuf>, event: notify::Event) {
+    match event.kind {
+        notify::EventKind::Create(_)
+        | notify::EventKind::Modify(_)
+        | notify::EventKind::Remove(_) => {
+            changed_paths.extend(event.paths);
+        }
+        _ => {}
+    }
+}
+
+// returns the changed files and their dependents which need to be checked
+fn update_changed_files(
+    analysis: &mut EmmyLuaAnalysis,
+    workspace: &PathBuf,
+    changed_paths: Vec<PathBuf>,
+) -> Vec<FileId> {
+    let encoding = analysis.emmyrc.workspace.encoding.clone();
+    let mut changed_file_ids = Vec::new();
+    let mut removed_file_ids = HashSet::new();
+    let mut files = Vec::new();
+    let mut markdown_files = Vec::new();
+    for path in changed_paths {
+        let text = read_file_with_encoding(&path, &encoding);
+        if is_markdown_file(&path) {
+            // the blocks are reloaded, the old blocks which are gone are removed
+            let vfs = analysis.compilation.get_db().get_vfs();
+            for file_id in get_markdown_block_ids(analysis, &path) {
+                if let Some(block_path) = vfs.get_file_path(&file_id) {
+                    files.push((block_path.clone(), None));
+                }
+                changed_file_ids.push(file_id);
+                removed_file_ids.insert(file_id);
+            }
+            if let Some(text) = text {
+                markdown_files.extend(extract_markdown_files(&path, &text));
+            }
+            continue;
+        }
+        // the removed files are still known by the vfs before the update
+        if text.is_none() {
+            let vfs = analysis.compilation.get_db().get_vfs();
+            if let Some(file_id) = file_path_to_uri(&path).and_then(|uri| vfs.get_file_id(&uri)) {
+                changed_file_ids.push(file_id);
+                removed_file_ids.insert(file_id);
+            }
+        }
+        files.push((path, text));
+    }
+    changed_file_ids.extend(analysis.update_files_by_path(files));
+    let markdown_files = markdown_files
+        .into_iter()
+        .map(|file| file.into_tuple())
+        .collect();
+    for file_id in analysis.update_isolated_files_by_path(markdown_files) {
+        removed_file_ids.remove(&file_id);
+        changed_file_ids.push(file_id);
+    }
+
+    let db = analysis.compilation.get_db();
+    let files = db.get_vfs().get_all_file_ids();
+    let need_check_set: HashSet<FileId> = get_need_check_ids(db, files, workspace)
+        .into_iter()
+        .collect();
+    let dependents = db
+        .get_file_dependencies_index()
+        .get_file_dependencies()
+        .collect_file_dependents(changed_file_ids.clone());
+
+    let mut result = changed_file_ids
+        .into_iter()
+        .chain(dependents)
+        .filter(|file_id| need_check_set.contains(file_id) && !removed_file_ids.contains(file_id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+async fn check_files(
+    analysis: &EmmyLuaAnalysis,
+    workspace: &Path,
+    cmd_args: &CmdArgs,
+    file_ids: Vec<FileId>,
+) -> Result<(), String> {
+    // the baseline is consumed by every check
+    let baseline = match &cmd_args.baseline {
+        Some(path) => Some(Baseline::load(path)?),
+        None => None,
+    };
+
+    let (sender, receiver) = tokio::sync::mpsc::channel(file_ids.len().max(1));
+    for file_id in &file_ids {
+        let diagnostics = analysis.diagnose_file(*file_id, CancellationToken::new());
+        sender
+            .try_send((*file_id, diagnostics, Vec::new()))
+            .map_err(|e| e.to_string())?;
+    }
+    drop(sender);
+
+    let exit_code = output_result(
+        file_ids.len(),
+        analysis.compilation.get_db(),
+        workspace.to_path_buf(),
+        receiver,
+        cmd_args,
+        baseline,
+        None,
+    )
+    .await;
+    if exit_code == 0 {
+        eprintln!("Check finished");
+    } else {
+        eprintln!("Check finished with errors");
+    }
+
+    Ok(())
+}
+
+/// Matches the changed paths with the same include and exclude patterns as the workspace loading
+struct WatchFileFilter {
+    include: Vec<Glob<'static>>,
+    exclude: Vec<Glob<'static>>,
+    exclude_dirs: Vec<PathBuf>,
+}
+
+impl WatchFileFilter {
+    fn new(analysis: &EmmyLuaAnalysis, ignore: Option<Vec<String>>, markdown: bool) -> Self {
+        let (mut include, exclude, exclude_dirs) =
+            calculate_include_and_exclude(&analysis.emmyrc, ignore);
+        if markdown {
+            include.push("**/*.md".to_string());
+        }
+        Self {
+            include: compile_globs(include),
+            exclude: compile_globs(exclude),
+            exclude_dirs,
+        }
+    }
+
+    fn is_match(&self, workspace: &Path, path: &Path) -> bool {
+        if self.exclude_dirs.iter().any(|dir| path.starts_with(dir)) {
+            return false;
+        }
+
+        let Ok(relative_path) = path.strip_prefix(workspace) else {
+            return false;
+        };
+        // the `.editorconfig` files are only loaded at the startup
+        if relative_path.ends_with(".editorconfig") {
+            return false;
+        }
+
+        !self.exclude.iter().any(|glob| glob.is_match(relative_path))
+            && self.include.iter().any(|glob| glob.is_match(relative_path))
+    }
+}
+
+// the code blocks of the markdown file are loaded as the isolated documents under its path
+fn get_markdown_block_ids(analysis: &EmmyLuaAnalysis, path: &Path) -> Vec<FileId> {
+    let vfs = analysis.compilation.get_db().get_vfs();
+    vfs.get_isolated_file_ids()
+        .into_iter()
+        .filter(|file_id| {
+            vfs.get_file_path(file_id)
+                .and_then(|block_path| block_path.parent())
+                .is_some_and(|parent| parent == path)
+        })
+        .collect()
+}
+
+fn is_markdown_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "md")
+}
+
+fn compile_globs(patterns: Vec<String>) -> Vec<Glob<'static>> {
+    patterns
+        .iter()
+        .filter_map(|pattern| Glob::new(pattern).ok().map(|glob| glob.into_owned()))
+        .collect()
+}
@@ -60,6 +60,10 @@
 --- used to implement coroutines. Lua threads are not related to 
 --- operating-system threads. Lua supports coroutines on all systems, even those
 --- that do not support threads natively.
+---
+--- `thread<Yield, Resume, Return>` describes a coroutine with the types of the
+--- values passed to `coroutine.yield`, passed to `coroutine.resume` and
+--- returned by the body function.
 ---@class thread
 
 ---
@@ -116,6 +120,14 @@
 --- built-in type for Unpack function
 ---@alias std.Unpack<T, Start, End> unknown
 
+--- built-in type for the format string of `string.format`, the specifiers of
+--- the string passed to it are checked and highlighted
+---@alias std.FormatString string
+
+--- built-in type for the Lua patterns, the items of the string passed to it are
+--- checked and highlighted
+---@alias std.Pattern string
+
 --- compact luals
 
 ---@alias type std.type
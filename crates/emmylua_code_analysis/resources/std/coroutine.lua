@@ -19,8 +19,9 @@ coroutine = {}
 ---
 --- Creates a new coroutine, with body `f`. `f` must be a Lua function. Returns
 --- this new coroutine, an object with type `"thread"`.
----@param f async fun(...):any...
----@return thread
+---@generic R, T
+---@param f async fun(...: R...): T...
+---@return thread<any, R, T>
 ---@nodiscard
 function coroutine.create(f) end
 
@@ -57,12 +58,12 @@ function coroutine.close(co) end
 --- values passed to `yield` (when the coroutine yields) or any values returned
 --- by the body function (when the coroutine terminates). If there is any error,
 --- `resume` returns **false** plus the error message.
----@param co thread
----@param val1? any
----@param ... any
+---@generic Y, R, T
+---@param co thread<Y, R, T>
+---@param ... R
 ---@return boolean success
----@return any ...
-function coroutine.resume(co, val1, ...) end
+---@return Y|T ...
+function coroutine.resume(co, ...) end
 
 ---
 --- Returns the running coroutine plus a boolean, true when the running
@@ -93,15 +94,21 @@ function coroutine.status(co) end
 --- passed to the function behave as the extra arguments to `resume`. Returns
 --- the same values returned by `resume`, except the first
 --- boolean. In case of error, propagates the error.
----@param f async fun(...):any...
----@return fun(...):any...
+---@generic R, T
+---@param f async fun(...: R...): T...
+---@return fun(...: R...): T...
 ---@nodiscard
 function coroutine.wrap(f) end
 
 ---
 --- Suspends the execution of the calling coroutine. Any arguments to `yield`
 --- are passed as extra results to `resume`.
+---
+--- Inside the body of a coroutine whose type is declared as
+--- `thread<Yield, Resume, Return>`, the values are checked against `Yield`, and
+--- `yield` returns the `Resume` values.
 ---@async
----@param ... any
----@return any ...
+---@generic Y, R
+---@param ... Y
+---@return R ...
 function coroutine.yield(...) end
@@ -74,7 +74,7 @@ function string.dump(func, strip) end
 --- If the pattern has captures, then in a successful match the captured values
 --- are also returned, after the two indices.
 ---@param s       string|number
----@param pattern string|number
+---@param pattern std.Pattern|number
 ---@param init?   integer
 ---@param plain?  boolean
 ---@return integer|nil start
@@ -112,7 +112,7 @@ function string.find(s, pattern, init, plain) end
 --- converted to one following the same rules of `tostring`. If the option
 --- has any modifier (flags, width, length), the string argument should not
 --- contain embedded zeros.
----@param fmt string
+---@param fmt std.FormatString
 ---@param ... any
 ---@return string
 ---@nodiscard
@@ -143,7 +143,7 @@ function string.format(fmt, ...) end
 --- For this function, a caret '`^`' at the start of a pattern does not work as
 --- an anchor, as this would prevent the iteration.
 ---@param s string
----@param pattern string
+---@param pattern std.Pattern
 ---@return fun():string, table
 function string.gmatch(s, pattern) end
 
@@ -191,7 +191,7 @@ function string.gmatch(s, pattern) end
 --- `x = string.gsub("$name-$version.tar.gz", "%$(%w+)", t)`
 --- > x="lua-5.3.tar.gz"
 ---@param s string
----@param pattern string
+---@param pattern std.Pattern
 ---@param repl string|fun(param:string)
 ---@param n? integer
 ---@return string, integer
@@ -219,7 +219,7 @@ function string.lower(s) end
 --- is returned. A third, optional numerical argument `init` specifies where
 --- to start the search; its default value is 1 and can be negative.
 ---@param s string
----@param pattern string
+---@param pattern std.Pattern
 ---@param init? integer
 ---@return string...
 function string.match(s, pattern, init) end
@@ -1,13 +1,14 @@
 use emmylua_parser::{
-    LuaAst, LuaAstNode, LuaAstToken, LuaCallExpr, LuaClosureExpr, LuaExpr, LuaIndexExpr,
-    LuaIndexKey, LuaLiteralExpr, LuaLiteralToken, LuaNameExpr, LuaTableExpr, LuaVarExpr,
+    LuaAst, LuaAstNode, LuaAstToken, LuaBlock, LuaCallExpr, LuaCallExprStat, LuaChunk,
+    LuaClosureExpr, LuaExpr, LuaIndexExpr, LuaIndexKey, LuaLiteralExpr, LuaLiteralToken,
+    LuaNameExpr, LuaTableExpr, LuaVarExpr, PathTrait,
 };
 
 use crate::{
     compilation::analyzer::unresolve::UnResolveTableField,
     db_index::{LuaDecl, LuaMember, LuaMemberKey, LuaMemberOwner},
-    FileId, InFiled, InferFailReason, LuaDeclExtra, LuaDeclId, LuaMemberFeature, LuaMemberId,
-    LuaSignatureId,
+    EmmyrcLuaVersion, FileId, InFiled, InferFailReason, LuaChunkEnv, LuaDeclExtra, LuaDeclId,
+    LuaMemberFeature, LuaMemberId, LuaSignatureId, LuaType,
 };
 
 use super::DeclAnalyzer;
@@ -298,7 +299,139 @@ pub fn analyze_call_expr(analyzer: &mut DeclAnalyzer, expr: LuaCallExpr) -> Opti
                     .add_required_file(file_id, module_file_id);
             }
         }
+    } else if let Some(LuaExpr::NameExpr(name_expr)) = expr.get_prefix_expr() {
+        match name_expr.get_name_text()?.as_str() {
+            "module" => analyze_module_call(analyzer, expr),
+            "setfenv" => analyze_setfenv_call(analyzer, expr),
+            _ => None,
+        };
+    }
+
+    Some(())
+}
+
+// `module("a.b", package.seeall)` declares the file as module `a.b`, the globals defined after it
+// are the fields of the module table
+fn analyze_module_call(analyzer: &mut DeclAnalyzer, call_expr: LuaCallExpr) -> Option<()> {
+    if !is_chunk_env_call(analyzer, &call_expr, "module") {
+        return None;
+    }
+
+    let file_id = analyzer.get_file_id();
+    let mut args = call_expr.get_args_list()?.get_args();
+    let first_arg = args.next()?;
+    let literal_expr = if let LuaExpr::LiteralExpr(literal_expr) = first_arg {
+        literal_expr
+    } else {
+        return None;
+    };
+    match literal_expr.get_literal()? {
+        LuaLiteralToken::String(string_token) => {
+            let module_path = string_token.get_value();
+            if module_path.is_empty() {
+                return None;
+            }
+            analyzer
+                .db
+                .get_module_index_mut()
+                .set_module_path(file_id, &module_path);
+        }
+        // `module(...)` keeps the module path of the file
+        LuaLiteralToken::Dots(_) => {}
+        _ => return None,
+    }
+
+    let range = call_expr.get_range();
+    if let Some(module_info) = analyzer.db.get_module_index_mut().get_module_mut(file_id) {
+        module_info.export_type = Some(LuaType::TableConst(InFiled::new(file_id, range)));
+    }
+    // the globals are only visible with the `package.seeall` option
+    let seeall = args.any(|arg| {
+        matches!(arg, LuaExpr::IndexExpr(index_expr)
+            if index_expr.get_access_path().as_deref() == Some("package.seeall"))
+    });
+    analyzer
+        .decl
+        .add_chunk_env(range.end(), LuaChunkEnv::Module { range, seeall });
+
+    Some(())
+}
+
+// `setfenv(1, env)` switches the global environment of the rest of the chunk
+fn analyze_setfenv_call(analyzer: &mut DeclAnalyzer, call_expr: LuaCallExpr) -> Option<()> {
+    if !is_chunk_env_call(analyzer, &call_expr, "setfenv") {
+        return None;
+    }
+
+    let mut args = call_expr.get_args_list()?.get_args();
+    let level_expr = if let LuaExpr::LiteralExpr(level_expr) = args.next()? {
+        level_expr
+    } else {
+        return None;
+    };
+    match level_expr.get_literal()? {
+        LuaLiteralToken::Number(number) if number.is_int() && number.get_int_value() == 1 => {}
+        _ => return None,
     }
 
+    let env_expr = args.next()?;
+    let chunk_env = match get_seeall_env_expr(&env_expr) {
+        Some(env_expr) => LuaChunkEnv::Table {
+            syntax_id: env_expr.get_syntax_id(),
+            seeall: true,
+        },
+        None => LuaChunkEnv::Table {
+            syntax_id: env_expr.get_syntax_id(),
+            seeall: false,
+        },
+    };
+    analyzer
+        .decl
+        .add_chunk_env(call_expr.get_range().end(), chunk_env);
+
     Some(())
 }
+
+// `setmetatable(env, { __index = _G })` returns the env which sees the globals
+fn get_seeall_env_expr(env_expr: &LuaExpr) -> Option<LuaExpr> {
+    let LuaExpr::CallExpr(call_expr) = env_expr else {
+        return None;
+    };
+    if call_expr.get_access_path().as_deref() != Some("setmetatable") {
+        return None;
+    }
+
+    let mut args = call_expr.get_args_list()?.get_args();
+    let table_expr = args.next()?;
+    let LuaExpr::TableExpr(meta_table) = args.next()? else {
+        return None;
+    };
+    let is_index_global = meta_table.get_fields().any(|field| {
+        field
+            .get_field_key()
+            .is_some_and(|key| key.get_path_part() == "__index")
+            && matches!(field.get_value_expr(), Some(LuaExpr::NameExpr(name_expr))
+                if name_expr.get_name_text().as_deref() == Some("_G"))
+    });
+    is_index_global.then_some(table_expr)
+}
+
+fn is_chunk_env_call(analyzer: &DeclAnalyzer, call_expr: &LuaCallExpr, name: &str) -> bool {
+    if !matches!(
+        analyzer.db.get_emmyrc().runtime.version,
+        EmmyrcLuaVersion::Lua51 | EmmyrcLuaVersion::LuaJIT
+    ) {
+        return false;
+    }
+
+    // the function may be shadowed by a local variable
+    if analyzer.find_decl(name, call_expr.get_position()).is_some() {
+        return false;
+    }
+
+    call_expr
+        .get_parent::<LuaCallExprStat>()
+        .and_then(|call_stat| call_stat.get_parent::<LuaBlock>())
+        .and_then(|block| block.get_parent::<LuaChunk>())
+        .is_some()
+}
@@ -6,6 +6,7 @@ mod stats;
 use crate::{
     db_index::{DbIndex, LuaScopeKind},
     profile::Profile,
+    LuaChunkEnv,
 };
 
 use super::{unresolve::UnResolve, AnalyzeContext};
@@ -38,6 +39,12 @@ fn walk_node_enter(analyzer: &mut DeclAnalyzer, node: LuaAst) {
     match node {
         LuaAst::LuaChunk(chunk) => {
             analyzer.create_scope(chunk.get_range(), LuaScopeKind::Normal);
+            if analyzer.db.get_vfs().is_isolated(&analyzer.get_file_id()) {
+                let range = chunk.get_range();
+                analyzer
+                    .decl
+                    .add_chunk_env(range.start(), LuaChunkEnv::Isolated { range });
+            }
         }
         LuaAst::LuaBlock(block) => {
             analyzer.create_scope(block.get_range(), LuaScopeKind::Normal);
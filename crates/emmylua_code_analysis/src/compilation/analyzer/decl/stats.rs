@@ -1,12 +1,14 @@
 use emmylua_parser::{
     LuaAssignStat, LuaAstNode, LuaAstToken, LuaExpr, LuaForRangeStat, LuaForStat, LuaFuncStat,
-    LuaIndexExpr, LuaLocalFuncStat, LuaLocalStat, LuaSyntaxId, LuaSyntaxKind, LuaVarExpr,
+    LuaIndexExpr, LuaLocalFuncStat, LuaLocalStat, LuaNameExpr, LuaSyntaxId, LuaSyntaxKind,
+    LuaVarExpr,
 };
 
 use crate::{
     compilation::analyzer::bind_type::bind_type,
     db_index::{LocalAttribute, LuaDecl, LuaMember, LuaMemberKey},
-    LuaDeclExtra, LuaMemberFeature, LuaMemberId, LuaSemanticDeclId, LuaSignatureId, LuaType,
+    InFiled, LuaChunkEnv, LuaDeclExtra, LuaMemberFeature, LuaMemberId, LuaMemberOwner,
+    LuaSemanticDeclId, LuaSignatureId, LuaType,
 };
 
 use super::{members::find_index_owner, DeclAnalyzer};
@@ -67,8 +69,8 @@ pub fn analyze_assign_stat(analyzer: &mut DeclAnalyzer, stat: LuaAssignStat) ->
         };
 
         match &var {
-            LuaVarExpr::NameExpr(name) => {
-                let name_token = name.get_name_token()?;
+            LuaVarExpr::NameExpr(name_expr) => {
+                let name_token = name_expr.get_name_token()?;
                 let position = name_token.get_position();
                 let name = name_token.get_name_text();
                 let file_id = analyzer.get_file_id();
@@ -79,6 +81,13 @@ pub fn analyze_assign_stat(analyzer: &mut DeclAnalyzer, stat: LuaAssignStat) ->
                         .db
                         .get_reference_index_mut()
                         .add_decl_reference(decl_id, file_id, range, true);
+                } else if analyzer.decl.get_chunk_env(position).is_some() {
+                    let decl_feature = if analyzer.is_meta {
+                        LuaMemberFeature::MetaDefine
+                    } else {
+                        LuaMemberFeature::FileDefine
+                    };
+                    add_chunk_env_member(analyzer, name_expr, decl_feature);
                 } else {
                     let decl = LuaDecl::new(
                         name,
@@ -135,6 +144,10 @@ fn analyze_maybe_global_index_expr(
         let name_token_text = name_token.get_name_text();
         if name_token_text == "_G" || name_token_text == "_ENV" {
             let position = index_expr.get_position();
+            // an isolated document can not define globals for the other files
+            if let Some(LuaChunkEnv::Isolated { .. }) = analyzer.decl.get_chunk_env(position) {
+                return None;
+            }
             let name = name_token.get_name_text();
             let range = index_expr.get_range();
             if let Some(decl) = analyzer.find_decl(&name, position) {
@@ -162,6 +175,37 @@ fn analyze_maybe_global_index_expr(
     Some(())
 }
 
+// After `module` or `setfenv`, a global assignment defines a field of the environment table
+fn add_chunk_env_member(
+    analyzer: &mut DeclAnalyzer,
+    name_expr: &LuaNameExpr,
+    decl_feature: LuaMemberFeature,
+) -> Option<LuaMemberId> {
+    let name = name_expr.get_name_text()?;
+    let file_id = analyzer.get_file_id();
+    let owner = match analyzer.decl.get_chunk_env(name_expr.get_position())? {
+        LuaChunkEnv::Module { range, .. } | LuaChunkEnv::Isolated { range } => {
+            LuaMemberOwner::Element(InFiled::new(file_id, *range))
+        }
+        // resolved when the env expr is inferred
+        LuaChunkEnv::Table { .. } => LuaMemberOwner::LocalUnresolve,
+    };
+
+    let syntax_id = name_expr.get_syntax_id();
+    let key = LuaMemberKey::Name(name.into());
+    analyzer
+        .db
+        .get_reference_index_mut()
+        .add_index_reference(key.clone(), file_id, syntax_id);
+    let member = LuaMember::new(
+        LuaMemberId::new(syntax_id, file_id),
+        key,
+        decl_feature,
+        None,
+    );
+    Some(analyzer.db.get_member_index_mut().add_member(owner, member))
+}
+
 pub fn analyze_for_stat(analyzer: &mut DeclAnalyzer, stat: LuaForStat) -> Option<()> {
     let it_var = stat.get_var_name()?;
     let name = it_var.get_name_text();
@@ -219,7 +263,17 @@ pub fn analyze_func_stat(analyzer: &mut DeclAnalyzer, stat: LuaFuncStat) -> Opti
             let position = name_token.get_position();
             let name = name_token.get_name_text();
             let range = name_token.get_range();
-            if analyzer.find_decl(&name, position).is_none() {
+            if analyzer.find_decl(name, position).is_some() {
+                return Some(());
+            } else if analyzer.decl.get_chunk_env(position).is_some() {
+                let decl_feature = if analyzer.is_meta {
+                    LuaMemberFeature::MetaMethodDecl
+                } else {
+                    LuaMemberFeature::FileMethodDecl
+                };
+                let member_id = add_chunk_env_member(analyzer, &name_expr, decl_feature)?;
+                LuaSemanticDeclId::Member(member_id)
+            } else {
                 let decl = LuaDecl::new(
                     name,
                     file_id,
@@ -232,8 +286,6 @@ pub fn analyze_func_stat(analyzer: &mut DeclAnalyzer, stat: LuaFuncStat) -> Opti
 
                 let decl_id = analyzer.add_decl(decl);
                 LuaSemanticDeclId::LuaDecl(decl_id)
-            } else {
-                return Some(());
             }
         }
         LuaVarExpr::IndexExpr(index_expr) => {
@@ -1,12 +1,13 @@
 use crate::LuaSignatureId;
 
 use super::{
+    preprocess_description,
     tags::{find_owner_closure, get_owner_id},
     DocAnalyzer,
 };
 use emmylua_parser::{
-    LuaDocDescriptionOwner, LuaDocTagDeprecated, LuaDocTagSource, LuaDocTagVersion,
-    LuaDocTagVisibility,
+    LuaDocDescriptionOwner, LuaDocTagDeprecated, LuaDocTagExample, LuaDocTagSince, LuaDocTagSource,
+    LuaDocTagVersion, LuaDocTagVisibility,
 };
 
 pub fn analyze_visibility(
@@ -37,6 +38,62 @@ pub fn analyze_source(analyzer: &mut DocAnalyzer, source: LuaDocTagSource) -> Op
     Some(())
 }
 
+pub fn analyze_since(analyzer: &mut DocAnalyzer, since: LuaDocTagSince) -> Option<()> {
+    let version = since.get_version_text()?;
+    let owner_id = get_owner_id(analyzer)?;
+
+    analyzer
+        .db
+        .get_property_index_mut()
+        .add_since(analyzer.file_id, owner_id, version);
+
+    Some(())
+}
+
+// the example without the fenced code block is treated as the lua code
+pub fn analyze_example(analyzer: &mut DocAnalyzer, example: LuaDocTagExample) -> Option<()> {
+    let description = example.get_description()?;
+    let text = preprocess_description(&dedent_example(&description.get_description_text()));
+    if text.is_empty() {
+        return None;
+    }
+
+    let example = if text.contains("```") || text.contains("~~~") {
+        text
+    } else {
+        format!("```lua\n{}\n```", text)
+    };
+    let owner_id = get_owner_id(analyzer)?;
+
+    analyzer
+        .db
+        .get_property_index_mut()
+        .add_example(analyzer.file_id, owner_id, example);
+
+    Some(())
+}
+
+// the space after `---` is not a part of the code
+fn dedent_example(text: &str) -> String {
+    let mut lines = text.lines();
+    let Some(first_line) = lines.next() else {
+        return String::new();
+    };
+    let rest_lines = lines.collect::<Vec<_>>();
+    let indent = rest_lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let mut result = vec![first_line.trim_start()];
+    for line in rest_lines {
+        result.push(line.get(indent..).unwrap_or(line.trim_start()));
+    }
+    result.join("\n")
+}
+
 pub fn analyze_nodiscard(analyzer: &mut DocAnalyzer) -> Option<()> {
     let closure = find_owner_closure(analyzer)?;
     let signature_id = LuaSignatureId::from_closure(analyzer.file_id, &closure);
@@ -11,8 +11,8 @@ use super::{
     diagnostic_tags::analyze_diagnostic,
     field_or_operator_def_tags::{analyze_field, analyze_operator},
     property_tags::{
-        analyze_async, analyze_deprecated, analyze_nodiscard, analyze_source, analyze_version,
-        analyze_visibility,
+        analyze_async, analyze_deprecated, analyze_example, analyze_nodiscard, analyze_since,
+        analyze_source, analyze_version, analyze_visibility,
     },
     type_def_tags::{analyze_alias, analyze_class, analyze_enum, analyze_func_generic},
     type_ref_tags::{
@@ -62,6 +62,12 @@ pub fn analyze_tag(analyzer: &mut DocAnalyzer, tag: LuaDocTag) -> Option<()> {
         LuaDocTag::Source(source) => {
             analyze_source(analyzer, source)?;
         }
+        LuaDocTag::Example(example) => {
+            analyze_example(analyzer, example)?;
+        }
+        LuaDocTag::Since(since) => {
+            analyze_since(analyzer, since)?;
+        }
         LuaDocTag::Nodiscard(_) => {
             analyze_nodiscard(analyzer)?;
         }
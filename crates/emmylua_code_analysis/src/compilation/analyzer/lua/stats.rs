@@ -1,6 +1,6 @@
 use emmylua_parser::{
-    BinaryOperator, LuaAssignStat, LuaAstNode, LuaExpr, LuaFuncStat, LuaIndexExpr,
-    LuaLocalFuncStat, LuaLocalStat, LuaTableField, LuaVarExpr, PathTrait,
+    BinaryOperator, LuaAssignStat, LuaAstNode, LuaExpr, LuaFuncStat, LuaLocalFuncStat,
+    LuaLocalStat, LuaNameExpr, LuaTableField, LuaVarExpr, PathTrait,
 };
 
 use crate::{
@@ -9,7 +9,7 @@ use crate::{
         unresolve::{UnResolveDecl, UnResolveMember},
     },
     db_index::{LuaDeclId, LuaMemberId, LuaMemberOwner, LuaType},
-    InferFailReason, LuaTypeCache, LuaTypeOwner,
+    InferFailReason, LuaChunkEnv, LuaTypeCache, LuaTypeOwner,
 };
 
 use super::LuaAnalyzer;
@@ -172,6 +172,19 @@ fn get_var_owner(analyzer: &mut LuaAnalyzer, var: LuaVarExpr) -> LuaTypeOwner {
         LuaVarExpr::NameExpr(var_name) => {
             let position = var_name.get_position();
             let decl_id = LuaDeclId::new(file_id, position);
+            if analyzer.db.get_decl_index().get_decl(&decl_id).is_none() {
+                // field of the chunk env
+                let member_id = LuaMemberId::new(var_name.get_syntax_id(), file_id);
+                if analyzer
+                    .db
+                    .get_member_index()
+                    .get_member(&member_id)
+                    .is_some()
+                {
+                    return LuaTypeOwner::Member(member_id);
+                }
+            }
+
             LuaTypeOwner::Decl(decl_id)
         }
         LuaVarExpr::IndexExpr(index_expr) => {
@@ -193,13 +206,17 @@ fn get_var_owner(analyzer: &mut LuaAnalyzer, var: LuaVarExpr) -> LuaTypeOwner {
 
 fn set_index_expr_owner(analyzer: &mut LuaAnalyzer, var_expr: LuaVarExpr) -> Option<()> {
     let file_id = analyzer.file_id;
-    let index_expr = LuaIndexExpr::cast(var_expr.syntax().clone())?;
-    let prefix_expr = index_expr.get_prefix_expr()?;
+    let prefix_expr = match &var_expr {
+        LuaVarExpr::IndexExpr(index_expr) => {
+            index_expr.get_index_key()?;
+            index_expr.get_prefix_expr()?
+        }
+        LuaVarExpr::NameExpr(name_expr) => get_chunk_env_expr(analyzer, name_expr)?,
+    };
+    let member_id = LuaMemberId::new(var_expr.get_syntax_id(), file_id);
 
-    match analyzer.infer_expr(&prefix_expr.clone().into()) {
+    match analyzer.infer_expr(&prefix_expr) {
         Ok(prefix_type) => {
-            index_expr.get_index_key()?;
-            let member_id = LuaMemberId::new(index_expr.get_syntax_id(), file_id);
             let member_owner = match prefix_type {
                 LuaType::TableConst(in_file_range) => LuaMemberOwner::Element(in_file_range),
                 LuaType::Def(def_id) => LuaMemberOwner::Type(def_id),
@@ -228,9 +245,9 @@ fn set_index_expr_owner(analyzer: &mut LuaAnalyzer, var_expr: LuaVarExpr) -> Opt
             // record unresolve
             let unresolve_member = UnResolveMember {
                 file_id: analyzer.file_id,
-                member_id: LuaMemberId::new(var_expr.get_syntax_id(), file_id),
+                member_id,
                 expr: None,
-                prefix: Some(prefix_expr.into()),
+                prefix: Some(prefix_expr),
                 ret_idx: 0,
                 reason,
             };
@@ -241,6 +258,24 @@ fn set_index_expr_owner(analyzer: &mut LuaAnalyzer, var_expr: LuaVarExpr) -> Opt
     Some(())
 }
 
+// the env expr of `setfenv(1, env)` is the prefix of the globals defined after it
+fn get_chunk_env_expr(analyzer: &LuaAnalyzer, name_expr: &LuaNameExpr) -> Option<LuaExpr> {
+    let member_id = LuaMemberId::new(name_expr.get_syntax_id(), analyzer.file_id);
+    analyzer.db.get_member_index().get_member(&member_id)?;
+    let env = analyzer
+        .db
+        .get_decl_index()
+        .get_decl_tree(&analyzer.file_id)?
+        .get_chunk_env(name_expr.get_position())?;
+    match env {
+        LuaChunkEnv::Table { syntax_id, .. } => {
+            let root = name_expr.get_root();
+            LuaExpr::cast(syntax_id.to_node_from_root(&root)?)
+        }
+        LuaChunkEnv::Module { .. } | LuaChunkEnv::Isolated { .. } => None,
+    }
+}
+
 // assign stat is toooooooooo complex
 pub fn analyze_assign_stat(analyzer: &mut LuaAnalyzer, assign_stat: LuaAssignStat) -> Option<()> {
     let (var_list, expr_list) = assign_stat.get_var_and_expr_list();
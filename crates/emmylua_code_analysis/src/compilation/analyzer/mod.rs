@@ -5,9 +5,9 @@ mod flow;
 mod lua;
 mod unresolve;
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
-use crate::{db_index::DbIndex, profile::Profile, Emmyrc, InFiled, LuaType, WorkspaceId};
+use crate::{db_index::DbIndex, profile::Profile, Emmyrc, FileId, InFiled, LuaType, WorkspaceId};
 use emmylua_parser::{LuaChunk, LuaSyntaxId};
 use unresolve::UnResolve;
 
@@ -38,10 +38,7 @@ fn module_analyze(
         let in_filed_tree = need_analyzed_files[0].clone();
         let file_id = in_filed_tree.file_id;
         if let Some(path) = db.get_vfs().get_file_path(&file_id).cloned() {
-            let workspace_id = db
-                .get_module_index_mut()
-                .add_module_by_path(file_id, path.to_str().unwrap());
-            let workspace_id = workspace_id.unwrap_or(WorkspaceId::MAIN);
+            let workspace_id = add_module_by_path(db, file_id, &path);
             let mut context = AnalyzeContext::new(config);
             context.add_tree_chunk(in_filed_tree);
             return vec![(workspace_id, context)];
@@ -55,10 +52,7 @@ fn module_analyze(
     for in_filed_tree in need_analyzed_files {
         let file_id = in_filed_tree.file_id;
         if let Some(path) = db.get_vfs().get_file_path(&file_id).cloned() {
-            let workspace_id = db
-                .get_module_index_mut()
-                .add_module_by_path(file_id, path.to_str().unwrap());
-            let workspace_id = workspace_id.unwrap_or(WorkspaceId::MAIN);
+            let workspace_id = add_module_by_path(db, file_id, &path);
             file_tree_map
                 .entry(workspace_id)
                 .or_default()
@@ -75,6 +69,17 @@ fn module_analyze(
     contexts
 }
 
+// the isolated documents are analyzed with the main workspace, but they are not modules
+fn add_module_by_path(db: &mut DbIndex, file_id: FileId, path: &Path) -> WorkspaceId {
+    if db.get_vfs().is_isolated(&file_id) {
+        return WorkspaceId::MAIN;
+    }
+
+    db.get_module_index_mut()
+        .add_module_by_path(file_id, path.to_str().unwrap())
+        .unwrap_or(WorkspaceId::MAIN)
+}
+
 #[derive(Debug)]
 pub struct AnalyzeContext {
     tree_list: Vec<InFiled<LuaChunk>>,
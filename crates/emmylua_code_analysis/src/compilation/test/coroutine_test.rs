@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, LuaType, VirtualWorkspace};
+
+    #[test]
+    fn test_coroutine_create() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        co = coroutine.create(
+            ---@param a string
+            ---@return integer
+            function(a)
+                return 1
+            end
+        )
+        "#,
+        );
+
+        let ty = ws.expr_ty("co");
+        assert_eq!(ws.humanize_type(ty), "thread<any,string,integer>");
+    }
+
+    #[test]
+    fn test_coroutine_create_multi() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        co = coroutine.create(
+            ---@param a string
+            ---@param b boolean
+            ---@return integer, string
+            function(a, b)
+                received = coroutine.yield(1)
+                return 1, a
+            end
+        )
+        "#,
+        );
+
+        // every param and every return is kept, not only the first ones
+        let ty = ws.expr_ty("co");
+        assert_eq!(ty, ws.ty("thread<any, string|boolean, integer|string>"));
+        let ty = ws.expr_ty("received");
+        assert_eq!(ty, ws.ty("string|boolean"));
+    }
+
+    #[test]
+    fn test_coroutine_resume() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        ---@type thread<integer, string, boolean>
+        local co
+
+        ok, value = coroutine.resume(co, "start")
+        "#,
+        );
+
+        let ty = ws.expr_ty("ok");
+        assert_eq!(ty, ws.ty("boolean"));
+        let ty = ws.expr_ty("value");
+        assert_eq!(ty, ws.ty("integer|boolean"));
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r#"
+        ---@type thread<integer, string, boolean>
+        local co
+
+        coroutine.resume(co, 1)
+        "#
+        ));
+    }
+
+    #[test]
+    fn test_coroutine_status() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r#"
+        ---@type thread<integer, string, boolean>
+        local co
+
+        local status = coroutine.status(co)
+        "#
+        ));
+
+        ws.def(
+            r#"
+        ---@type thread<integer, string, boolean>
+        local co
+
+        status = coroutine.status(co)
+        "#,
+        );
+        let ty = ws.expr_ty("status");
+        let LuaType::MultiLineUnion(status) = ty else {
+            panic!("expected the status enum, got {:?}", ty);
+        };
+        assert_eq!(
+            status.to_union(),
+            ws.ty(r#""running"|"suspended"|"normal"|"dead""#)
+        );
+    }
+
+    #[test]
+    fn test_coroutine_create_unannotated() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        co = coroutine.create(function(a)
+            return a
+        end)
+        "#,
+        );
+
+        let ty = ws.expr_ty("co");
+        assert_eq!(ws.humanize_type(ty), "thread<any,any,any>");
+    }
+
+    #[test]
+    fn test_coroutine_yield_unbound() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        local co = coroutine.create(function()
+            received = coroutine.yield(1)
+        end)
+        "#,
+        );
+
+        // the resume values of an unannotated coroutine are unknown
+        let ty = match ws.expr_ty("received") {
+            LuaType::Instance(instance) => instance.get_base().clone(),
+            ty => ty,
+        };
+        assert_eq!(ty, LuaType::Any);
+    }
+
+    #[test]
+    fn test_coroutine_wrap() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        local gen = coroutine.wrap(
+            ---@param n integer
+            ---@return string
+            function(n)
+                return "done"
+            end
+        )
+
+        result = gen(1)
+        "#,
+        );
+
+        let ty = ws.expr_ty("result");
+        assert_eq!(ty, ws.ty("string"));
+    }
+
+    #[test]
+    fn test_coroutine_yield() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        ---@type thread<integer, string, boolean>
+        local co = coroutine.create(function()
+            received = coroutine.yield(1)
+            return true
+        end)
+        "#,
+        );
+
+        let ty = ws.expr_ty("received");
+        assert_eq!(ty, ws.ty("string"));
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r#"
+        ---@type thread<integer, string, boolean>
+        local co = coroutine.create(function()
+            coroutine.yield("not a number")
+            return true
+        end)
+        "#
+        ));
+    }
+
+    #[test]
+    fn test_thread_compact() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        let generic = ws.ty("thread<integer, string, boolean>");
+        let thread = ws.ty("thread");
+        assert!(ws.check_type(&thread, &generic));
+        assert!(ws.check_type(&generic, &thread));
+    }
+}
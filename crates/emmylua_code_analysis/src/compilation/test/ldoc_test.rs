@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod test {
+    use crate::{
+        convert_ldoc_comments, DiagnosticCode, Emmyrc, LuaType, LuaTypeDeclId, VirtualWorkspace,
+    };
+
+    fn new_ldoc_workspace() -> VirtualWorkspace {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.doc.ldoc = true;
+        ws.analysis.update_config(emmyrc.into());
+        ws
+    }
+
+    #[test]
+    fn test_ldoc_tparam_treturn() {
+        let mut ws = new_ldoc_workspace();
+        ws.def_file(
+            "strutil.lua",
+            r#"
+            --- String helpers
+            -- @module strutil
+            local M = {}
+
+            --- Repeats the text
+            -- @tparam string text the text
+            -- @tparam[opt] integer count the count
+            -- @treturn string the repeated text
+            function M.rep(text, count)
+                return string.rep(text, count or 1)
+            end
+
+            return M
+            "#,
+        );
+
+        let ty = ws.expr_ty(r#"require("strutil").rep("a")"#);
+        assert_eq!(ty, LuaType::String);
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r#"
+            local strutil = require("strutil")
+            strutil.rep(1)
+            "#,
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::MissingParameter,
+            r#"
+            local strutil = require("strutil")
+            strutil.rep("a")
+            "#,
+        ));
+        // the module is the class of the module name
+        assert_eq!(
+            ws.expr_ty(r#"require("strutil")"#),
+            LuaType::Def(LuaTypeDeclId::new("strutil"))
+        );
+    }
+
+    #[test]
+    fn test_ldoc_disabled() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def_file(
+            "strutil.lua",
+            r#"
+            local M = {}
+
+            --- Repeats the text
+            -- @tparam string text the text
+            function M.rep(text)
+                return text
+            end
+
+            return M
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r#"
+            local strutil = require("strutil")
+            strutil.rep(1)
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_convert_ldoc_comments() {
+        let code = r#"--- String helpers
+-- @module strutil
+local M = {}
+
+--- Joins the texts
+-- @tparam {string,...} texts the texts
+-- @tparam[opt] ?string|int sep the separator
+-- @param options not typed
+-- @treturn ?string the joined text
+-- @return the count
+-- @usage strutil.join({"a", "b"})
+---@see table.concat
+function M.join(texts, sep, options) end
+
+--- @classmod Buffer
+return M
+"#;
+        let expected = r#"--- String helpers
+---@class strutil
+local M = {}
+
+--- Joins the texts
+---@param texts string[] the texts
+---@param sep? string|integer the separator
+---@param options any not typed
+---@return string? # the joined text
+---@return any # the count
+---@usage strutil.join({"a", "b"})
+---@see table.concat
+function M.join(texts, sep, options) end
+
+---@class Buffer
+return M
+"#;
+        assert_eq!(convert_ldoc_comments(code), expected);
+    }
+}
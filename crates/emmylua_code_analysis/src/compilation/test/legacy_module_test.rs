@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod test {
+    use smol_str::SmolStr;
+
+    use crate::{DiagnosticCode, Emmyrc, EmmyrcLuaVersion, LuaType, VirtualWorkspace};
+
+    fn new_lua51_workspace() -> VirtualWorkspace {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.runtime.version = EmmyrcLuaVersion::Lua51;
+        ws.analysis.update_config(emmyrc.into());
+        ws
+    }
+
+    #[test]
+    fn test_module_call() {
+        let mut ws = new_lua51_workspace();
+        ws.def_file(
+            "legacy/net.lua",
+            r#"
+            module("socket.net", package.seeall)
+
+            version = "1.0"
+
+            ---@param host string
+            ---@return integer
+            function connect(host)
+                return 1
+            end
+            "#,
+        );
+
+        let ty = ws.expr_ty(r#"require("socket.net").version"#);
+        assert_eq!(ty, LuaType::StringConst(SmolStr::new("1.0").into()));
+        let ty = ws.expr_ty(r#"require("socket.net").connect("localhost")"#);
+        assert_eq!(ty, LuaType::Integer);
+    }
+
+    #[test]
+    fn test_module_call_dots() {
+        let mut ws = new_lua51_workspace();
+        ws.def_file(
+            "util.lua",
+            r#"
+            module(..., package.seeall)
+
+            function add(a, b)
+                return a + b
+            end
+            "#,
+        );
+
+        let ty = ws.expr_ty(r#"require("util").add"#);
+        assert!(ty.is_function());
+    }
+
+    #[test]
+    fn test_module_env_globals() {
+        let mut ws = new_lua51_workspace();
+        assert!(ws.check_code_for(
+            DiagnosticCode::UndefinedGlobal,
+            r#"
+            module("a.b", package.seeall)
+
+            count = 0
+
+            function inc()
+                count = count + 1
+                print(count)
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_module_env_without_seeall() {
+        let mut ws = new_lua51_workspace();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UndefinedGlobal,
+            r#"
+            module("a.c")
+
+            count = 0
+
+            function inc()
+                count = count + 1
+                print(count)
+            end
+            "#
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::UndefinedGlobal,
+            r#"
+            module("a.d")
+
+            count = 0
+
+            function inc()
+                count = count + 1
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_module_call_need_lua51() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def_file(
+            "legacy/old.lua",
+            r#"
+            module("old")
+            value = 1
+            "#,
+        );
+
+        let ty = ws.expr_ty("value");
+        assert_eq!(ty, LuaType::IntegerConst(1));
+    }
+
+    #[test]
+    fn test_setfenv() {
+        let mut ws = new_lua51_workspace();
+        ws.def(
+            r#"
+            local env = {}
+            setfenv(1, env)
+
+            name = "env"
+            "#,
+        );
+        assert!(ws.check_code_for(
+            DiagnosticCode::UndefinedGlobal,
+            r#"
+            local M = {}
+            setfenv(1, setmetatable(M, { __index = _G }))
+
+            flag = true
+            print(flag)
+            "#
+        ));
+        // the env table does not see the globals of `_G`
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UndefinedGlobal,
+            r#"
+            local M = {}
+            setfenv(1, M)
+
+            flag = true
+            print(flag)
+            "#
+        ));
+
+        // globals defined after `setfenv` are not in `_G`
+        assert!(!ws
+            .analysis
+            .compilation
+            .get_db()
+            .get_global_index()
+            .is_exist_global_decl("name"));
+    }
+
+    #[test]
+    fn test_setfenv_export() {
+        let mut ws = new_lua51_workspace();
+        ws.def_file(
+            "config.lua",
+            r#"
+            local M = {}
+            setfenv(1, M)
+
+            port = 8080
+
+            return M
+            "#,
+        );
+
+        let ty = ws.expr_ty(r#"require("config").port"#);
+        assert_eq!(ty, LuaType::IntegerConst(8080));
+    }
+}
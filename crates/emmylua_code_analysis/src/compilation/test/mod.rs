@@ -3,11 +3,14 @@ mod annotation_test;
 mod closure_generic;
 mod closure_param_infer_test;
 mod closure_return_test;
+mod coroutine_test;
 mod diagnostic_disable_test;
 mod flow;
 mod for_range_var_infer_test;
 mod infer_str_tpl_test;
 mod inherit_type;
+mod ldoc_test;
+mod legacy_module_test;
 mod mathlib_test;
 mod member_infer_test;
 mod metatable_test;
@@ -18,5 +21,8 @@ mod overload_field;
 mod overload_test;
 mod pcall_test;
 mod static_cal_cmp;
+mod string_syntax_test;
 mod syntax_error_test;
 mod tuple_test;
+mod type_coverage_test;
+mod vararg_test;
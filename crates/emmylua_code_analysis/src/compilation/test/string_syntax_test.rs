@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod test {
+    use emmylua_parser::{LuaAstNode, LuaAstToken, LuaStringToken};
+
+    use crate::{classify_string_literal, LuaStringArgKind, VirtualWorkspace};
+
+    fn classify_all(
+        ws: &mut VirtualWorkspace,
+        content: &str,
+    ) -> Vec<(String, Option<LuaStringArgKind>)> {
+        let file_id = ws.def(content);
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let root = semantic_model.get_root().clone();
+        root.syntax()
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token().and_then(LuaStringToken::cast))
+            .map(|token| {
+                let kind = classify_string_literal(&semantic_model, &token);
+                (token.get_value(), kind)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classify_string_literal() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        let result = classify_all(
+            &mut ws,
+            r#"
+            local s = "hello"
+            string.format("%d", 1)
+            s:match(("%a+"))
+            local f = ("%s"):format(s)
+            string.find(s, ".", 1, true)
+            string.gsub(s, "l", "%%")
+            print("%d")
+            "#,
+        );
+
+        assert_eq!(
+            result,
+            vec![
+                ("hello".to_string(), None),
+                ("%d".to_string(), Some(LuaStringArgKind::FormatString)),
+                ("%a+".to_string(), Some(LuaStringArgKind::Pattern)),
+                ("%s".to_string(), Some(LuaStringArgKind::FormatString)),
+                (".".to_string(), None),
+                ("l".to_string(), Some(LuaStringArgKind::Pattern)),
+                ("%%".to_string(), None),
+                ("%d".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_by_param_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        let result = classify_all(
+            &mut ws,
+            r#"
+            ---@param fmt std.FormatString
+            ---@param ... any
+            local function log(fmt, ...) end
+
+            ---@param s string
+            ---@param pattern? std.Pattern
+            local function split(s, pattern) end
+
+            log("%s", 1)
+            split("a,b", ",")
+            "#,
+        );
+
+        assert_eq!(
+            result,
+            vec![
+                ("%s".to_string(), Some(LuaStringArgKind::FormatString)),
+                ("a,b".to_string(), None),
+                (",".to_string(), Some(LuaStringArgKind::Pattern)),
+            ]
+        );
+    }
+}
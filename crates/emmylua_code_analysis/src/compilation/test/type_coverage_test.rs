@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod test {
+    use crate::{collect_type_coverage, LuaCoverageCount, VirtualWorkspace};
+
+    #[test]
+    fn test_type_coverage() {
+        let mut ws = VirtualWorkspace::new();
+
+        let file_id = ws.def(
+            r#"
+            ---@param a integer
+            ---@return integer
+            local function f(a, b)
+                return a
+            end
+
+            local x = f(1, 2)
+            local y = unknown_global
+            "#,
+        );
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let coverage = collect_type_coverage(&semantic_model);
+        // `unknown_global`, `y` and `b` are not typed, the literals `1` and `2` are not counted
+        assert_eq!(coverage.exprs, LuaCoverageCount { typed: 3, total: 4 });
+        assert_eq!(coverage.locals, LuaCoverageCount { typed: 2, total: 3 });
+        assert_eq!(coverage.params, LuaCoverageCount { typed: 1, total: 2 });
+        assert_eq!(coverage.returns, LuaCoverageCount { typed: 1, total: 1 });
+        assert_eq!(
+            coverage.get_total(),
+            LuaCoverageCount {
+                typed: 7,
+                total: 10
+            }
+        );
+    }
+
+    #[test]
+    fn test_empty_coverage() {
+        let mut ws = VirtualWorkspace::new();
+
+        let file_id = ws.def("");
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let coverage = collect_type_coverage(&semantic_model);
+        assert_eq!(coverage.get_total().total, 0);
+        assert_eq!(coverage.get_total().get_percent(), 100.0);
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod test {
+    use smol_str::SmolStr;
+
+    use crate::{DiagnosticCode, LuaType, VirtualWorkspace};
+
+    #[test]
+    fn test_vararg_param_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r##"
+        ---@param ... string
+        function log(...)
+            first = ...
+            list = {...}
+            count = select("#", ...)
+            second = select(2, ...)
+            packed = table.pack(...)
+        end
+        "##,
+        );
+
+        let ty = ws.expr_ty("first");
+        assert_eq!(ty, ws.ty("string"));
+        let ty = ws.expr_ty("list");
+        assert_eq!(ty, ws.ty("string[]"));
+        let ty = ws.expr_ty("count");
+        assert_eq!(ty, ws.ty("integer"));
+        let ty = ws.expr_ty("second");
+        assert_eq!(ty, ws.ty("string"));
+        let ty = ws.expr_ty("packed");
+        let LuaType::Union(union) = ty else {
+            panic!("expected a union, got {:?}", ty);
+        };
+        let types = union.get_types();
+        assert_eq!(types.len(), 2);
+        assert_eq!(types[0], ws.ty("string[]"));
+        assert!(matches!(types[1], LuaType::Object(_)));
+    }
+
+    #[test]
+    fn test_pack_args_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r##"
+        ---@generic T
+        ---@param ... T...
+        ---@return [T...]
+        local function pack(...) end
+
+        packed = pack(1, "a")
+        "##,
+        );
+
+        let ty = ws.expr_ty("packed");
+        let LuaType::Tuple(tuple) = ty else {
+            panic!("expected a tuple, got {:?}", ty);
+        };
+        assert_eq!(
+            tuple.get_types(),
+            &[
+                LuaType::IntegerConst(1),
+                LuaType::StringConst(SmolStr::new("a").into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vararg_tuple_param_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r##"
+        ---@param ... [integer, string]
+        function dispatch(...)
+            id, name = ...
+            list = {...}
+            count = select("#", ...)
+            rest = select(2, ...)
+        end
+        "##,
+        );
+
+        let ty = ws.expr_ty("id");
+        assert_eq!(ty, ws.ty("integer"));
+        let ty = ws.expr_ty("name");
+        assert_eq!(ty, ws.ty("string"));
+        let ty = ws.expr_ty("list");
+        assert_eq!(ty, ws.ty("[integer, string]"));
+        let ty = ws.expr_ty("count");
+        assert_eq!(ty, LuaType::IntegerConst(2));
+        let ty = ws.expr_ty("rest");
+        assert_eq!(ty, ws.ty("string"));
+    }
+
+    #[test]
+    fn test_vararg_param_check() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r##"
+        ---@param level integer
+        ---@param ... string
+        function log(level, ...)
+        end
+
+        ---@param ... [integer, string]
+        function dispatch(...)
+        end
+        "##,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r##"
+        log(1, "a", "b", "c")
+        dispatch(1, "event")
+        "##
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r##"
+        log(1, "a", 2)
+        "##
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r##"
+        dispatch(1, 2)
+        "##
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r##"
+        dispatch("event", "1")
+        "##
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeNotMatch,
+            r##"
+        dispatch(1, "event", true)
+        "##
+        ));
+    }
+}
@@ -0,0 +1,11 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmmyrcDoc {
+    /// Whether to treat the LDoc tags as the EmmyLua tags, eg: `-- @tparam string name`,
+    /// `-- @treturn string`, `-- @module name` and `-- @classmod name`.
+    #[serde(default)]
+    pub ldoc: bool,
+}
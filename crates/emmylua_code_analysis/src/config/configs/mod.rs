@@ -1,6 +1,7 @@
 mod codelen;
 mod completion;
 mod diagnostics;
+mod doc;
 mod document_color;
 mod hover;
 mod inlayhint;
@@ -14,7 +15,8 @@ mod workspace;
 
 pub use codelen::EmmyrcCodeLen;
 pub use completion::{EmmyrcCompletion, EmmyrcFilenameConvention};
-pub use diagnostics::EmmyrcDiagnostic;
+pub use diagnostics::{DiagnosticSeveritySetting, EmmyrcDiagnostic};
+pub use doc::EmmyrcDoc;
 pub use document_color::EmmyrcDocumentColor;
 pub use hover::EmmyrcHover;
 pub use inlayhint::EmmyrcInlayHint;
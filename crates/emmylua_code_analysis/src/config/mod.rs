@@ -8,7 +8,7 @@ use std::{
 };
 
 pub use config_loader::load_configs;
-use configs::EmmyrcDocumentColor;
+pub use configs::DiagnosticSeveritySetting;
 pub use configs::EmmyrcFilenameConvention;
 pub use configs::EmmyrcLuaVersion;
 use configs::{
@@ -16,13 +16,14 @@ use configs::{
     EmmyrcReference, EmmyrcResource, EmmyrcRuntime, EmmyrcSemanticToken, EmmyrcSignature,
     EmmyrcStrict, EmmyrcWorkspace,
 };
+use configs::{EmmyrcDoc, EmmyrcDocumentColor};
 use emmylua_parser::{LuaLanguageLevel, ParserConfig, SpecialFunction};
 use regex::Regex;
 use rowan::NodeCache;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema, Default)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Emmyrc {
     #[serde(rename = "$schema")]
@@ -54,6 +55,8 @@ pub struct Emmyrc {
     pub hover: EmmyrcHover,
     #[serde(default)]
     pub document_color: EmmyrcDocumentColor,
+    #[serde(default)]
+    pub doc: EmmyrcDoc,
 }
 
 impl Emmyrc {
@@ -77,6 +80,7 @@ impl Emmyrc {
             special_like.insert(name.clone(), SpecialFunction::Require);
         }
         ParserConfig::new(lua_language_level, Some(node_cache), special_like)
+            .with_ldoc(self.doc.ldoc)
     }
 
     pub fn pre_process_emmyrc(&mut self, workspace_root: &Path) {
@@ -0,0 +1,30 @@
+use emmylua_parser::LuaSyntaxId;
+use rowan::TextRange;
+
+/// The global environment of a chunk, switched by the Lua 5.1 `module` and `setfenv` functions.
+/// After the switch, global names in the rest of the chunk refer to fields of the environment table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LuaChunkEnv {
+    /// `module("a.b", package.seeall)`, the environment is the module table, the range is the
+    /// range of the call expression
+    Module { range: TextRange, seeall: bool },
+    /// `setfenv(1, env)`, the syntax id is the env expression
+    Table {
+        syntax_id: LuaSyntaxId,
+        seeall: bool,
+    },
+    /// The environment of an isolated document, eg: a code block of a markdown file, the range is
+    /// the range of the chunk. Its globals are private to the document, but it still sees `_G`
+    Isolated { range: TextRange },
+}
+
+impl LuaChunkEnv {
+    /// The names which are not fields of the environment table are looked up in `_G`, eg:
+    /// `package.seeall` or `setmetatable(env, { __index = _G })`
+    pub fn is_seeall(&self) -> bool {
+        match self {
+            LuaChunkEnv::Module { seeall, .. } | LuaChunkEnv::Table { seeall, .. } => *seeall,
+            LuaChunkEnv::Isolated { .. } => true,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use super::{decl, scope, LuaDeclId};
+use super::{decl, scope, LuaChunkEnv, LuaDeclId};
 use crate::{db_index::LuaMemberId, DbIndex, FileId};
 use decl::LuaDecl;
 use emmylua_parser::{
@@ -14,6 +14,7 @@ pub struct LuaDeclarationTree {
     file_id: FileId,
     decls: HashMap<LuaDeclId, LuaDecl>,
     scopes: Vec<LuaScope>,
+    chunk_envs: Vec<(TextSize, LuaChunkEnv)>,
 }
 
 impl LuaDeclarationTree {
@@ -22,6 +23,7 @@ impl LuaDeclarationTree {
             file_id,
             decls: HashMap::new(),
             scopes: Vec::new(),
+            chunk_envs: Vec::new(),
         }
     }
 
@@ -320,6 +322,19 @@ impl LuaDeclarationTree {
     pub fn get_decls(&self) -> &HashMap<LuaDeclId, LuaDecl> {
         &self.decls
     }
+
+    pub fn add_chunk_env(&mut self, position: TextSize, env: LuaChunkEnv) {
+        self.chunk_envs.push((position, env));
+    }
+
+    /// Get the global environment which is active at the position, `None` means `_G`
+    pub fn get_chunk_env(&self, position: TextSize) -> Option<&LuaChunkEnv> {
+        self.chunk_envs
+            .iter()
+            .rev()
+            .find(|(env_position, _)| *env_position <= position)
+            .map(|(_, env)| env)
+    }
 }
 
 #[derive(Debug)]
@@ -1,8 +1,10 @@
+mod chunk_env;
 mod decl;
 mod decl_id;
 mod decl_tree;
 mod scope;
 
+pub use chunk_env::LuaChunkEnv;
 pub use decl::LuaDeclExtra;
 pub use decl::{LocalAttribute, LuaDecl};
 pub use decl_id::LuaDeclId;
@@ -98,14 +98,20 @@ impl LuaModuleIndex {
             self.remove(file_id);
         }
 
-        let (module_path, workspace_id) = self.extract_module_path(&path)?;
+        let (module_path, workspace_id) = self.get_module_path_by_file_path(path)?;
+        self.add_module_by_module_path(file_id, module_path, workspace_id);
+        Some(workspace_id)
+    }
+
+    /// The module path of the file path, the file does not need to be loaded, eg: a deleted file
+    pub fn get_module_path_by_file_path(&self, path: &str) -> Option<(String, WorkspaceId)> {
+        let (module_path, workspace_id) = self.extract_module_path(path)?;
         let mut module_path = module_path.replace(['\\', '/'], ".");
         if !self.module_replace_vec.is_empty() {
             module_path = self.replace_module_path(&module_path);
         }
 
-        self.add_module_by_module_path(file_id, module_path, workspace_id);
-        Some(workspace_id)
+        Some((module_path, workspace_id))
     }
 
     pub fn add_module_by_module_path(
@@ -181,6 +187,24 @@ impl LuaModuleIndex {
         Some(())
     }
 
+    /// Move the module of the file to another module path, for the legacy `module("a.b")`
+    /// declaration which does not follow the file path.
+    pub fn set_module_path(&mut self, file_id: FileId, module_path: &str) -> Option<()> {
+        let module_info = self.file_module_map.get(&file_id)?;
+        if module_info.full_module_name == module_path {
+            return Some(());
+        }
+
+        let workspace_id = module_info.workspace_id;
+        let is_meta = module_info.is_meta;
+        self.add_module_by_module_path(file_id, module_path.to_string(), workspace_id)?;
+        if is_meta {
+            self.set_meta(file_id);
+        }
+
+        Some(())
+    }
+
     pub fn get_module(&self, file_id: FileId) -> Option<&ModuleInfo> {
         self.file_module_map.get(&file_id)
     }
@@ -192,6 +192,43 @@ impl LuaPropertyIndex {
         Some(())
     }
 
+    pub fn add_example(
+        &mut self,
+        file_id: FileId,
+        owner_id: LuaSemanticDeclId,
+        example: String,
+    ) -> Option<()> {
+        let property = self.get_or_create_property(owner_id.clone())?;
+        property
+            .examples
+            .get_or_insert_with(Default::default)
+            .push(example);
+
+        self.in_filed_owner
+            .entry(file_id)
+            .or_default()
+            .insert(owner_id);
+
+        Some(())
+    }
+
+    pub fn add_since(
+        &mut self,
+        file_id: FileId,
+        owner_id: LuaSemanticDeclId,
+        since: String,
+    ) -> Option<()> {
+        let property = self.get_or_create_property(owner_id.clone())?;
+        property.since = Some(since);
+
+        self.in_filed_owner
+            .entry(file_id)
+            .or_default()
+            .insert(owner_id);
+
+        Some(())
+    }
+
     pub fn get_property(&self, owner_id: &LuaSemanticDeclId) -> Option<&LuaDeclProperty> {
         self.property_owners_map
             .get(&owner_id)
@@ -11,6 +11,10 @@ pub struct LuaDeclProperty {
     pub version_conds: Option<Box<Vec<LuaVersionCondition>>>,
     pub see_content: Option<Box<String>>,
     pub other_content: Option<Box<String>>,
+    /// The markdown of the `@example` and the `@usage` tags
+    pub examples: Option<Vec<String>>,
+    /// The version in which the item appeared
+    pub since: Option<String>,
 }
 
 impl LuaDeclProperty {
@@ -25,6 +29,8 @@ impl LuaDeclProperty {
             version_conds: None,
             see_content: None,
             other_content: None,
+            examples: None,
+            since: None,
         }
     }
 }
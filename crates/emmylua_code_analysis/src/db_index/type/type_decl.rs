@@ -163,6 +163,13 @@ impl LuaTypeDecl {
         }
     }
 
+    pub fn get_enum_base(&self) -> Option<&LuaType> {
+        match &self.extra {
+            LuaTypeExtra::Enum { base } => base.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn merge_decl(&mut self, other: LuaTypeDecl) {
         self.locations.extend(other.locations);
     }
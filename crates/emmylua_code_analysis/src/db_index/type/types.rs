@@ -798,6 +798,11 @@ impl LuaGenericType {
     pub fn contain_tpl(&self) -> bool {
         self.params.iter().any(|t| t.contain_tpl())
     }
+
+    /// `thread<Yield, Resume, Return>`, the coroutine type with the values passed across yields
+    pub fn is_thread(&self) -> bool {
+        self.base.get_name() == "thread"
+    }
 }
 
 impl From<LuaGenericType> for LuaType {
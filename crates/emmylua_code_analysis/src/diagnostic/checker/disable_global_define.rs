@@ -0,0 +1,46 @@
+use emmylua_parser::LuaSyntaxKind;
+
+use crate::{DiagnosticCode, SemanticModel};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct DisableGlobalDefineChecker;
+
+impl Checker for DisableGlobalDefineChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::DisableGlobalDefine];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let file_id = semantic_model.get_file_id();
+        let db = semantic_model.get_db();
+        // the meta files declare the globals on purpose
+        if db.get_module_index().is_meta_file(&file_id) {
+            return;
+        }
+
+        let Some(decl_tree) = db.get_decl_index().get_decl_tree(&file_id) else {
+            return;
+        };
+
+        let mut decls = decl_tree
+            .get_decls()
+            .values()
+            .filter(|decl| {
+                // `_G.a = 1` defines the global explicitly
+                decl.is_global() && decl.get_syntax_id().get_kind() == LuaSyntaxKind::NameExpr
+            })
+            .collect::<Vec<_>>();
+        decls.sort_by_key(|decl| decl.get_position());
+        for decl in decls {
+            context.add_diagnostic(
+                DiagnosticCode::DisableGlobalDefine,
+                decl.get_range(),
+                t!(
+                    "global variable `%{name}` is defined, add `local` if it is not intended",
+                    name = decl.get_name()
+                )
+                .to_string(),
+                None,
+            );
+        }
+    }
+}
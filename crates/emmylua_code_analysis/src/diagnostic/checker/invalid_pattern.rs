@@ -0,0 +1,66 @@
+use emmylua_parser::{LuaAstNode, LuaAstToken, LuaCallExpr};
+
+use crate::{
+    find_string_call_arg, get_string_literal, get_string_value_range, parse_lua_pattern,
+    DiagnosticCode, LuaPatternErrorKind, LuaStringArgKind, SemanticModel,
+};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct InvalidPatternChecker;
+
+impl Checker for InvalidPatternChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::InvalidPattern];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            check_pattern_call(context, semantic_model, call_expr);
+        }
+    }
+}
+
+fn check_pattern_call(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    call_expr: LuaCallExpr,
+) -> Option<()> {
+    let string_arg = find_string_call_arg(semantic_model, &call_expr)?;
+    if string_arg.kind != LuaStringArgKind::Pattern {
+        return None;
+    }
+
+    let string_token = get_string_literal(&string_arg.string_expr)?;
+    let pattern = parse_lua_pattern(&string_token.get_value());
+    for error in pattern.errors {
+        let message = match error.kind {
+            LuaPatternErrorKind::EndsWithPercent => {
+                t!("malformed pattern (ends with '%')").to_string()
+            }
+            LuaPatternErrorKind::MissingBalanceArgs => {
+                t!("malformed pattern (missing arguments to '%b')").to_string()
+            }
+            LuaPatternErrorKind::MissingFrontierSet => {
+                t!("missing '[' after '%f' in pattern").to_string()
+            }
+            LuaPatternErrorKind::UnclosedSet => t!("malformed pattern (missing ']')").to_string(),
+            LuaPatternErrorKind::UnfinishedCapture => t!("unfinished capture").to_string(),
+            LuaPatternErrorKind::InvalidCaptureClose => t!("invalid pattern capture").to_string(),
+            LuaPatternErrorKind::InvalidCaptureIndex(c) => {
+                t!("invalid capture index `%%{index}`", index = c).to_string()
+            }
+            LuaPatternErrorKind::InvalidEscape(c) => {
+                t!("invalid character class `%%{class}`", class = c).to_string()
+            }
+        };
+
+        context.add_diagnostic(
+            DiagnosticCode::InvalidPattern,
+            get_string_value_range(&string_token, error.range).unwrap_or(string_token.get_range()),
+            message,
+            None,
+        );
+    }
+
+    Some(())
+}
@@ -9,16 +9,19 @@ mod circle_doc_class;
 mod code_style;
 mod code_style_check;
 mod deprecated;
+mod disable_global_define;
 mod discard_returns;
 mod duplicate_require;
 mod duplicate_type;
 mod incomplete_signature_doc;
+mod invalid_pattern;
 mod local_const_reassign;
 mod missing_fields;
 mod need_check_nil;
 mod param_type_check;
 mod redefined_local;
 mod return_type_mismatch;
+mod string_format;
 mod syntax_error;
 mod unbalanced_assignments;
 mod undefined_doc_param;
@@ -31,7 +34,10 @@ use emmylua_parser::{
 };
 use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, NumberOrString};
 use rowan::TextRange;
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
     db_index::DbIndex, humanize_type, semantic::SemanticModel, FileId, LuaType, RenderLevel,
@@ -43,6 +49,9 @@ use super::{
     DiagnosticCode,
 };
 
+/// The time spent in every checker, in the order the checkers are run
+pub type CheckerTimings = Vec<(&'static str, Duration)>;
+
 pub trait Checker {
     const CODES: &[DiagnosticCode];
 
@@ -54,10 +63,21 @@ fn run_check<T: Checker>(context: &mut DiagnosticContext, semantic_model: &Seman
         .iter()
         .any(|code| context.is_checker_enable_by_code(code))
     {
+        // the timings are only collected when they are requested
+        let start = context.checker_timings.is_some().then(Instant::now);
         T::check(context, semantic_model);
+        if let (Some(start), Some(timings)) = (start, context.checker_timings.as_mut()) {
+            timings.push((get_checker_name::<T>(), start.elapsed()));
+        }
     }
 }
 
+// eg: `UnusedChecker`
+fn get_checker_name<T: Checker>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
 pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticModel) -> Option<()> {
     run_check::<syntax_error::SyntaxErrorChecker>(context, semantic_model);
     run_check::<analyze_error::AnalyzeErrorChecker>(context, semantic_model);
@@ -85,6 +105,9 @@ pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticMode
     run_check::<check_return_count::CheckReturnCount>(context, semantic_model);
     run_check::<unbalanced_assignments::UnbalancedAssignmentsChecker>(context, semantic_model);
     run_check::<check_param_count::CheckParamCountChecker>(context, semantic_model);
+    run_check::<string_format::StringFormatChecker>(context, semantic_model);
+    run_check::<invalid_pattern::InvalidPatternChecker>(context, semantic_model);
+    run_check::<disable_global_define::DisableGlobalDefineChecker>(context, semantic_model);
 
     run_check::<code_style::non_literal_expressions_in_assert::NonLiteralExpressionsInAssertChecker>(
         context,
@@ -97,6 +120,7 @@ pub struct DiagnosticContext<'a> {
     file_id: FileId,
     db: &'a DbIndex,
     diagnostics: Vec<Diagnostic>,
+    checker_timings: Option<CheckerTimings>,
     pub config: Arc<LuaDiagnosticConfig>,
 }
 
@@ -106,10 +130,20 @@ impl<'a> DiagnosticContext<'a> {
             file_id,
             db,
             diagnostics: Vec::new(),
+            checker_timings: None,
             config,
         }
     }
 
+    /// Records the time spent in every checker which is run
+    pub fn enable_checker_timings(&mut self) {
+        self.checker_timings = Some(Vec::new());
+    }
+
+    pub fn take_checker_timings(&mut self) -> CheckerTimings {
+        self.checker_timings.take().unwrap_or_default()
+    }
+
     pub fn get_db(&self) -> &DbIndex {
         &self.db
     }
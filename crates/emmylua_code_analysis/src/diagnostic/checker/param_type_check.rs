@@ -112,7 +112,30 @@ fn check_variadic_param_match_args(
     arg_types: &[LuaType],
     arg_ranges: &[TextRange],
 ) {
-    for (arg_type, arg_range) in arg_types.iter().zip(arg_ranges.iter()) {
+    for (idx, (arg_type, arg_range)) in arg_types.iter().zip(arg_ranges.iter()).enumerate() {
+        // `---@param ... [integer, string]` checks each vararg by position
+        let variadic_type = match variadic_type {
+            LuaType::Tuple(tuple) => match tuple.get_type(idx) {
+                Some(typ) => typ,
+                None => {
+                    let db = semantic_model.get_db();
+                    context.add_diagnostic(
+                        DiagnosticCode::ParamTypeNotMatch,
+                        *arg_range,
+                        t!(
+                            "expected at most %{count} varargs of `%{source}` but found `%{found}`",
+                            count = tuple.get_types().len(),
+                            source = humanize_type(db, variadic_type, RenderLevel::Simple),
+                            found = humanize_type(db, arg_type, RenderLevel::Simple)
+                        )
+                        .to_string(),
+                        None,
+                    );
+                    continue;
+                }
+            },
+            _ => variadic_type,
+        };
         let result = semantic_model.type_check(variadic_type, arg_type);
         if !result.is_ok() {
             add_type_check_diagnostic(
@@ -0,0 +1,162 @@
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallExpr, LuaExpr, LuaLiteralToken, LuaStringToken,
+};
+
+use crate::{
+    find_string_call_arg, get_string_literal, get_string_value_range, humanize_type,
+    parse_format_string, DiagnosticCode, FormatSpecifier, LuaStringArgKind, LuaType, RenderLevel,
+    SemanticModel,
+};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct StringFormatChecker;
+
+impl Checker for StringFormatChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::StringFormatMismatch];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            check_format_call(context, semantic_model, call_expr);
+        }
+    }
+}
+
+fn check_format_call(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    call_expr: LuaCallExpr,
+) -> Option<()> {
+    let string_arg = find_string_call_arg(semantic_model, &call_expr)?;
+    if string_arg.kind != LuaStringArgKind::FormatString {
+        return None;
+    }
+
+    let string_token = get_string_literal(&string_arg.string_expr)?;
+    let format = string_token.get_value();
+    let format_string = parse_format_string(&format);
+    for error in &format_string.errors {
+        let spec = format.get(error.range.clone()).unwrap_or("%");
+        context.add_diagnostic(
+            DiagnosticCode::StringFormatMismatch,
+            get_string_value_range(&string_token, error.range.clone())
+                .unwrap_or(string_token.get_range()),
+            t!("invalid conversion `%{spec}` in format string", spec = spec).to_string(),
+            None,
+        );
+    }
+
+    let specs = format_string.get_arg_specifiers().collect::<Vec<_>>();
+    let args = &string_arg.rest_args;
+    // the last argument may expand to multiple values
+    let is_multi_arg = matches!(args.last(), Some(arg) if is_multi_value_expr(arg));
+    for (idx, spec) in specs.iter().enumerate() {
+        match args.get(idx) {
+            Some(arg) => {
+                if is_multi_arg && idx + 1 == args.len() {
+                    break;
+                }
+                check_format_arg(context, semantic_model, &format, spec, arg);
+            }
+            None => {
+                if is_multi_arg {
+                    break;
+                }
+                add_missing_arg_diagnostic(context, &string_token, &format, spec);
+            }
+        }
+    }
+
+    if !is_multi_arg {
+        for arg in args.iter().skip(specs.len()) {
+            context.add_diagnostic(
+                DiagnosticCode::StringFormatMismatch,
+                arg.get_range(),
+                t!(
+                    "redundant argument, the format string only has %{count} specifiers",
+                    count = specs.len()
+                )
+                .to_string(),
+                None,
+            );
+        }
+    }
+
+    Some(())
+}
+
+fn check_format_arg(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    format: &str,
+    spec: &FormatSpecifier,
+    arg: &LuaExpr,
+) -> Option<()> {
+    let expected = if spec.is_integer_conversion() {
+        LuaType::Integer
+    } else if spec.is_float_conversion() {
+        LuaType::Number
+    } else {
+        // `%s` accepts any value, `%q` accepts the literals
+        return Some(());
+    };
+
+    let arg_type = semantic_model.infer_expr(arg.clone()).ok()?;
+    let is_match = match &arg_type {
+        LuaType::Any | LuaType::Unknown => true,
+        // the float is only allowed when it has an integer representation
+        LuaType::FloatConst(f) if spec.is_integer_conversion() => f.fract() == 0.0,
+        _ => semantic_model
+            .type_check(&LuaType::Number, &arg_type)
+            .is_ok(),
+    };
+    if is_match {
+        return Some(());
+    }
+
+    let db = semantic_model.get_db();
+    context.add_diagnostic(
+        DiagnosticCode::StringFormatMismatch,
+        arg.get_range(),
+        t!(
+            "expected `%{expected}` for `%{spec}` but found `%{found}`",
+            expected = humanize_type(db, &expected, RenderLevel::Simple),
+            spec = format.get(spec.range.clone()).unwrap_or("%"),
+            found = humanize_type(db, &arg_type, RenderLevel::Simple)
+        )
+        .to_string(),
+        None,
+    );
+    Some(())
+}
+
+fn add_missing_arg_diagnostic(
+    context: &mut DiagnosticContext,
+    string_token: &LuaStringToken,
+    format: &str,
+    spec: &FormatSpecifier,
+) {
+    let range = get_string_value_range(string_token, spec.range.clone())
+        .unwrap_or(string_token.get_range());
+    context.add_diagnostic(
+        DiagnosticCode::StringFormatMismatch,
+        range,
+        t!(
+            "missing argument for `%{spec}` in format string",
+            spec = format.get(spec.range.clone()).unwrap_or("%")
+        )
+        .to_string(),
+        None,
+    );
+}
+
+fn is_multi_value_expr(expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::CallExpr(_) => true,
+        LuaExpr::LiteralExpr(literal) => {
+            matches!(literal.get_literal(), Some(LuaLiteralToken::Dots(_)))
+        }
+        _ => false,
+    }
+}
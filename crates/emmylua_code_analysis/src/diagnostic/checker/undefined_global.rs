@@ -3,7 +3,10 @@ use std::collections::HashSet;
 use emmylua_parser::{LuaAstNode, LuaClosureExpr, LuaNameExpr};
 use rowan::TextRange;
 
-use crate::{DiagnosticCode, LuaSignatureId, SemanticModel};
+use crate::{
+    semantic::is_global_env_visible, DiagnosticCode, LuaSemanticDeclId, LuaSignatureId,
+    SemanticDeclLevel, SemanticModel,
+};
 
 use super::{Checker, DiagnosticContext};
 
@@ -54,14 +57,22 @@ fn check_name_expr(
         return Some(());
     }
 
-    if semantic_model
-        .get_db()
-        .get_global_index()
-        .is_exist_global_decl(&name_text)
+    let db = semantic_model.get_db();
+    // `module` without `package.seeall` and `setfenv` hide the globals of `_G`
+    if db.get_global_index().is_exist_global_decl(&name_text)
+        && is_global_env_visible(db, semantic_model.get_file_id(), name_expr.get_position())
     {
         return Some(());
     }
 
+    // field of the env table switched by `module` or `setfenv`
+    if let Some(LuaSemanticDeclId::Member(_)) = semantic_model.find_decl(
+        name_expr.syntax().clone().into(),
+        SemanticDeclLevel::NoTrace,
+    ) {
+        return Some(());
+    }
+
     if context
         .config
         .global_disable_set
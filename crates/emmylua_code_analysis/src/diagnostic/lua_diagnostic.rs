@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-pub use super::checker::DiagnosticContext;
+pub use super::checker::{CheckerTimings, DiagnosticContext};
 use super::{checker::check_file, lua_diagnostic_config::LuaDiagnosticConfig};
 use crate::{Emmyrc, FileId, LuaCompilation};
 use lsp_types::Diagnostic;
@@ -30,6 +30,29 @@ impl LuaDiagnostic {
         compilation: &LuaCompilation,
         file_id: FileId,
         cancel_token: CancellationToken,
+    ) -> Option<Vec<Diagnostic>> {
+        self.check(compilation, file_id, cancel_token, None)
+    }
+
+    /// Same as `diagnose_file`, also returns the time spent in every checker
+    pub fn diagnose_file_with_timings(
+        &self,
+        compilation: &LuaCompilation,
+        file_id: FileId,
+        cancel_token: CancellationToken,
+    ) -> Option<(Vec<Diagnostic>, CheckerTimings)> {
+        let mut timings = CheckerTimings::new();
+        let diagnostics = self.check(compilation, file_id, cancel_token, Some(&mut timings))?;
+        Some((diagnostics, timings))
+    }
+
+    // the timings are only collected when the sink is given
+    fn check(
+        &self,
+        compilation: &LuaCompilation,
+        file_id: FileId,
+        cancel_token: CancellationToken,
+        timings: Option<&mut CheckerTimings>,
     ) -> Option<Vec<Diagnostic>> {
         if !self.enable {
             return None;
@@ -40,11 +63,17 @@ impl LuaDiagnostic {
         }
 
         let db = compilation.get_db();
-        let mut semantic_model = compilation.get_semantic_model(file_id)?;
+        let semantic_model = compilation.get_semantic_model(file_id)?;
         let mut context = DiagnosticContext::new(file_id, db, self.config.clone());
+        if timings.is_some() {
+            context.enable_checker_timings();
+        }
 
-        check_file(&mut context, &mut semantic_model);
+        check_file(&mut context, &semantic_model);
 
+        if let Some(timings) = timings {
+            *timings = context.take_checker_timings();
+        }
         Some(context.get_diagnostics())
     }
 }
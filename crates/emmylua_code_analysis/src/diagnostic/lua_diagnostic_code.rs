@@ -90,6 +90,10 @@ pub enum DiagnosticCode {
     UnbalancedAssignments,
     /// unnecessary-assert
     UnnecessaryAssert,
+    /// String format mismatch
+    StringFormatMismatch,
+    /// Invalid pattern
+    InvalidPattern,
 
     #[serde(other)]
     None,
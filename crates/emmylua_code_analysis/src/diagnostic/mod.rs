@@ -2,7 +2,9 @@ mod checker;
 mod lua_diagnostic;
 mod lua_diagnostic_code;
 mod lua_diagnostic_config;
+mod quick_fix;
 mod test;
 
-pub use lua_diagnostic::LuaDiagnostic;
-pub use lua_diagnostic_code::DiagnosticCode;
+pub use lua_diagnostic::{CheckerTimings, LuaDiagnostic};
+pub use lua_diagnostic_code::{get_default_severity, is_code_default_enable, DiagnosticCode};
+pub use quick_fix::{apply_text_edits, build_quick_fix, LuaQuickFix, LuaTextEdit, QUICK_FIX_CODES};
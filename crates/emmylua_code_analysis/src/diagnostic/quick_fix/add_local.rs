@@ -0,0 +1,67 @@
+use emmylua_parser::{LuaAssignStat, LuaAstNode, LuaFuncStat, LuaNameExpr, LuaVarExpr};
+use rowan::TextRange;
+
+use crate::SemanticModel;
+
+use super::{get_token_at, LuaQuickFix, LuaTextEdit};
+
+pub fn build_add_local_fix(
+    semantic_model: &SemanticModel,
+    range: TextRange,
+) -> Option<LuaQuickFix> {
+    let token = get_token_at(semantic_model, range)?;
+    let name_expr = token.parent().and_then(LuaNameExpr::cast)?;
+    let name = name_expr.get_name_text()?;
+    let stat_node = name_expr.syntax().parent()?;
+
+    // `function f() end` can call itself after it becomes `local function f() end`
+    let allow_inner_reference = if let Some(assign_stat) = LuaAssignStat::cast(stat_node.clone()) {
+        let (vars, _) = assign_stat.get_var_and_expr_list();
+        if vars.len() != 1 {
+            return None;
+        }
+        false
+    } else if let Some(func_stat) = LuaFuncStat::cast(stat_node.clone()) {
+        if !matches!(func_stat.get_func_name()?, LuaVarExpr::NameExpr(_)) {
+            return None;
+        }
+        true
+    } else {
+        return None;
+    };
+
+    // the global must only be used after the definition in the same block
+    let file_id = semantic_model.get_file_id();
+    let stat_range = stat_node.text_range();
+    let block_range = stat_node.parent()?.text_range();
+    let references = semantic_model
+        .get_db()
+        .get_reference_index()
+        .get_global_references(&name)
+        .unwrap_or_default();
+    for reference in references {
+        if reference.file_id != file_id {
+            return None;
+        }
+
+        let reference_range = reference.value.get_range();
+        if reference_range == range {
+            continue;
+        }
+        let is_inner = stat_range.contains_range(reference_range);
+        if (is_inner && !allow_inner_reference)
+            || (!is_inner && reference_range.start() < stat_range.end())
+            || !block_range.contains_range(reference_range)
+        {
+            return None;
+        }
+    }
+
+    Some(LuaQuickFix::new(
+        t!("Declare `%{name}` as a local variable", name = name).to_string(),
+        vec![LuaTextEdit::insert(
+            stat_range.start(),
+            "local ".to_string(),
+        )],
+    ))
+}
@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaClosureExpr, LuaCommentOwner, LuaDocTagParam, LuaDocTagReturn,
+    LuaParamName, LuaStat,
+};
+use rowan::TextRange;
+
+use crate::SemanticModel;
+
+use super::{get_line_ending, get_line_indent, get_token_at, LuaQuickFix, LuaTextEdit};
+
+// adds `---@param name any` for all the undocumented parameters of the function, so the fixes of
+// the parameters in the same function are the same
+pub fn build_add_param_doc_fix(
+    semantic_model: &SemanticModel,
+    range: TextRange,
+) -> Option<LuaQuickFix> {
+    let token = get_token_at(semantic_model, range)?;
+    let closure_expr = match token.parent_ancestors().find_map(LuaParamName::cast) {
+        Some(param_name) => param_name.ancestors::<LuaClosureExpr>().next()?,
+        // the diagnostic of the global function without comment is on the statement
+        None => {
+            let stat = token
+                .parent_ancestors()
+                .filter_map(LuaStat::cast)
+                .find(|stat| stat.get_range() == range)?;
+            let closure_expr = stat.descendants::<LuaClosureExpr>().next()?;
+            closure_expr
+        }
+    };
+    let stat = closure_expr.ancestors::<LuaStat>().next()?;
+    let comment = stat.get_left_comment();
+
+    let doc_param_names: HashSet<String> = comment
+        .iter()
+        .flat_map(|comment| comment.children::<LuaDocTagParam>())
+        .filter_map(|param| {
+            param
+                .get_name_token()
+                .map(|token| token.get_name_text().to_string())
+        })
+        .collect();
+    let missing_names = closure_expr
+        .get_params_list()?
+        .get_params()
+        .filter_map(|param| param.get_name_token())
+        .filter(|token| {
+            let name = token.get_name_text();
+            name != "_" && !doc_param_names.contains(name)
+        })
+        .collect::<Vec<_>>();
+    if missing_names.is_empty() {
+        return None;
+    }
+
+    // the parameters are documented before the returns
+    let position = comment
+        .iter()
+        .flat_map(|comment| comment.children::<LuaDocTagReturn>())
+        .next()
+        .map_or(stat.get_position(), |return_tag| return_tag.get_position());
+    let document = semantic_model.get_document();
+    let text = document.get_text();
+    let (line_start, indent) = get_line_indent(text, position);
+    let line_ending = get_line_ending(text);
+    let mut new_text = String::new();
+    for name in &missing_names {
+        new_text.push_str(&format!(
+            "{}---@param {} any{}",
+            indent,
+            name.get_name_text(),
+            line_ending
+        ));
+    }
+
+    // the names are locked, so the fix conflicts with renaming the parameters
+    Some(
+        LuaQuickFix::new(
+            t!("Add missing @param annotations").to_string(),
+            vec![LuaTextEdit::insert(line_start, new_text)],
+        )
+        .with_locked_ranges(missing_names.iter().map(|name| name.get_range()).collect()),
+    )
+}
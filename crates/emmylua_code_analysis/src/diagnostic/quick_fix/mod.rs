@@ -0,0 +1,184 @@
+mod add_local;
+mod add_param_doc;
+mod remove_unused;
+
+use emmylua_parser::{LuaAstNode, LuaSyntaxToken};
+use rowan::{TextRange, TextSize};
+
+use crate::{DiagnosticCode, SemanticModel};
+
+/// The diagnostic codes which have machine applicable quick fixes
+pub const QUICK_FIX_CODES: &[DiagnosticCode] = &[
+    DiagnosticCode::Unused,
+    DiagnosticCode::DisableGlobalDefine,
+    DiagnosticCode::MissingGlobalDoc,
+    DiagnosticCode::IncompleteSignatureDoc,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LuaTextEdit {
+    pub range: TextRange,
+    pub new_text: String,
+}
+
+impl LuaTextEdit {
+    pub fn insert(position: TextSize, new_text: String) -> Self {
+        Self {
+            range: TextRange::empty(position),
+            new_text,
+        }
+    }
+
+    pub fn delete(range: TextRange) -> Self {
+        Self {
+            range,
+            new_text: String::new(),
+        }
+    }
+
+    /// Whether the two edits can not be applied together, the same edits do not conflict
+    pub fn is_conflict(&self, other: &LuaTextEdit) -> bool {
+        if self == other {
+            return false;
+        }
+
+        // two insertions at the same position have no defined order
+        if self.range.start() == other.range.start() {
+            return true;
+        }
+
+        self.range.start() < other.range.end() && other.range.start() < self.range.end()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LuaQuickFix {
+    pub title: String,
+    pub edits: Vec<LuaTextEdit>,
+    /// The ranges which the fix relies on without editing them, eg: the names of the parameters
+    /// which are documented by the fix. The fixes locking the same text conflict.
+    pub locked_ranges: Vec<TextRange>,
+}
+
+impl LuaQuickFix {
+    pub fn new(title: String, edits: Vec<LuaTextEdit>) -> Self {
+        Self {
+            title,
+            edits,
+            locked_ranges: Vec::new(),
+        }
+    }
+
+    pub fn with_locked_ranges(mut self, locked_ranges: Vec<TextRange>) -> Self {
+        self.locked_ranges = locked_ranges;
+        self
+    }
+
+    /// Whether the two fixes can not be applied together, the same fixes built for several
+    /// diagnostics do not conflict
+    pub fn is_conflict(&self, other: &LuaQuickFix) -> bool {
+        if self.edits == other.edits {
+            return false;
+        }
+
+        let is_edit_conflict = self
+            .edits
+            .iter()
+            .any(|edit| other.edits.iter().any(|other| edit.is_conflict(other)));
+        let is_lock_conflict = self.locked_ranges.iter().any(|range| {
+            other
+                .locked_ranges
+                .iter()
+                .any(|other| range.intersect(*other).is_some_and(|it| !it.is_empty()))
+        });
+        is_edit_conflict || is_lock_conflict
+    }
+}
+
+/// Builds the quick fix of the diagnostic, it is shared by the code actions of the language
+/// server and `emmylua_check --fix`
+pub fn build_quick_fix(
+    semantic_model: &SemanticModel,
+    code: DiagnosticCode,
+    range: TextRange,
+) -> Option<LuaQuickFix> {
+    match code {
+        DiagnosticCode::Unused => remove_unused::build_remove_unused_fix(semantic_model, range),
+        DiagnosticCode::DisableGlobalDefine => {
+            add_local::build_add_local_fix(semantic_model, range)
+        }
+        DiagnosticCode::MissingGlobalDoc | DiagnosticCode::IncompleteSignatureDoc => {
+            add_param_doc::build_add_param_doc_fix(semantic_model, range)
+        }
+        _ => None,
+    }
+}
+
+/// Applies the edits to the text, returns `None` when the edits conflict
+pub fn apply_text_edits(text: &str, edits: &[LuaTextEdit]) -> Option<String> {
+    let mut edits = edits.to_vec();
+    edits.sort_by_key(|edit| (edit.range.start(), edit.range.end()));
+    edits.dedup();
+    if edits.windows(2).any(|pair| pair[0].is_conflict(&pair[1])) {
+        return None;
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for edit in edits {
+        let start = usize::from(edit.range.start());
+        let end = usize::from(edit.range.end());
+        result.push_str(text.get(last..start)?);
+        result.push_str(&edit.new_text);
+        last = end;
+    }
+    result.push_str(text.get(last..)?);
+    Some(result)
+}
+
+fn get_token_at(semantic_model: &SemanticModel, range: TextRange) -> Option<LuaSyntaxToken> {
+    let root = semantic_model.get_root();
+    root.syntax().token_at_offset(range.start()).right_biased()
+}
+
+// the range of the whole lines, when the node is the only content of them
+fn extend_to_whole_lines(text: &str, range: TextRange) -> TextRange {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    let line_start = text[..start].rfind('\n').map_or(0, |pos| pos + 1);
+    if !text[line_start..start].trim().is_empty() {
+        return range;
+    }
+
+    let line_end = match text[end..].find('\n') {
+        Some(pos) => end + pos + 1,
+        None => text.len(),
+    };
+    if !text[end..line_end].trim().is_empty() {
+        return range;
+    }
+
+    TextRange::new(
+        TextSize::from(line_start as u32),
+        TextSize::from(line_end as u32),
+    )
+}
+
+// the indentation of the line which contains the position
+fn get_line_indent(text: &str, position: TextSize) -> (TextSize, String) {
+    let position = usize::from(position);
+    let line_start = text[..position].rfind('\n').map_or(0, |pos| pos + 1);
+    let indent = text[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+    (TextSize::from(line_start as u32), indent)
+}
+
+fn get_line_ending(text: &str) -> &'static str {
+    if text.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
@@ -0,0 +1,104 @@
+use emmylua_parser::{
+    LuaAstNode, LuaClosureExpr, LuaCommentOwner, LuaDocTagParam, LuaDocTagReturn, LuaExpr,
+    LuaIndexKey, LuaLocalFuncStat, LuaLocalName, LuaLocalStat, LuaParamName, LuaStat,
+};
+use rowan::TextRange;
+
+use crate::SemanticModel;
+
+use super::{extend_to_whole_lines, get_token_at, LuaQuickFix, LuaTextEdit};
+
+pub fn build_remove_unused_fix(
+    semantic_model: &SemanticModel,
+    range: TextRange,
+) -> Option<LuaQuickFix> {
+    let token = get_token_at(semantic_model, range)?;
+    let name = token.text().to_string();
+    let parent = token.parent()?;
+
+    if let Some(local_name) = LuaLocalName::cast(parent.clone()) {
+        if let Some(local_stat) = local_name.get_parent::<LuaLocalStat>() {
+            if local_stat.get_local_name_list().count() == 1
+                && local_stat.get_value_exprs().all(|expr| is_pure_expr(&expr))
+            {
+                return Some(build_remove_stat_fix(
+                    semantic_model,
+                    LuaStat::LocalStat(local_stat),
+                    &name,
+                ));
+            }
+        } else if let Some(local_func_stat) = local_name.get_parent::<LuaLocalFuncStat>() {
+            return Some(build_remove_stat_fix(
+                semantic_model,
+                LuaStat::LocalFuncStat(local_func_stat),
+                &name,
+            ));
+        }
+    } else if let Some(param_name) = LuaParamName::cast(parent) {
+        // renaming the parameter breaks the `---@param`, or the one added by the other fixes
+        if is_signature_documented(&param_name) {
+            return None;
+        }
+    }
+
+    // the name is locked, so the fix conflicts with documenting the parameter by its old name
+    Some(
+        LuaQuickFix::new(
+            t!("Prefix `%{name}` with an underscore", name = name).to_string(),
+            vec![LuaTextEdit::insert(range.start(), "_".to_string())],
+        )
+        .with_locked_ranges(vec![token.text_range()]),
+    )
+}
+
+// removes the statement with its comment
+fn build_remove_stat_fix(semantic_model: &SemanticModel, stat: LuaStat, name: &str) -> LuaQuickFix {
+    let mut range = stat.get_range();
+    if let Some(comment) = stat.get_left_comment() {
+        range = range.cover(comment.get_range());
+    }
+    let document = semantic_model.get_document();
+    let range = extend_to_whole_lines(document.get_text(), range);
+
+    LuaQuickFix::new(
+        t!("Remove unused local `%{name}`", name = name).to_string(),
+        vec![LuaTextEdit::delete(range)],
+    )
+}
+
+// the expression can be removed without changing the behavior
+fn is_pure_expr(expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::LiteralExpr(_) | LuaExpr::ClosureExpr(_) | LuaExpr::NameExpr(_) => true,
+        LuaExpr::ParenExpr(paren_expr) => paren_expr
+            .get_expr()
+            .is_none_or(|inner| is_pure_expr(&inner)),
+        LuaExpr::TableExpr(table_expr) => table_expr.get_fields().all(|field| {
+            let is_pure_key = match field.get_field_key() {
+                Some(LuaIndexKey::Expr(key_expr)) => is_pure_expr(&key_expr),
+                _ => true,
+            };
+            is_pure_key
+                && field
+                    .get_value_expr()
+                    .is_none_or(|value| is_pure_expr(&value))
+        }),
+        _ => false,
+    }
+}
+
+fn is_signature_documented(param_name: &LuaParamName) -> bool {
+    let Some(closure_expr) = param_name.ancestors::<LuaClosureExpr>().next() else {
+        return false;
+    };
+    let Some(comment) = closure_expr
+        .ancestors::<LuaStat>()
+        .next()
+        .and_then(|stat| stat.get_left_comment())
+    else {
+        return false;
+    };
+
+    comment.children::<LuaDocTagParam>().next().is_some()
+        || comment.children::<LuaDocTagReturn>().next().is_some()
+}
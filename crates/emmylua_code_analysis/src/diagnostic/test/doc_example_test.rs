@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod test {
+    use lsp_types::NumberOrString;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{extract_doc_examples_lua, DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_extract_doc_examples_lua() {
+        let code = "---@example\n--- ```lua\n--- add(1, 2)\n--- ```\n---@usage add(3, 4)\nlocal function add(a, b) end\n";
+        assert_eq!(
+            extract_doc_examples_lua(code, None),
+            vec![
+                "---@diagnostic disable: unused\n\n    add(1, 2)\n\n\n\n".to_string(),
+                "---@diagnostic disable: unused\n\n\n\n          add(3, 4)\n\n".to_string(),
+            ]
+        );
+        // only the lua code blocks are checked
+        assert_eq!(
+            extract_doc_examples_lua("---@example\n--- ```sh\n--- lua main.lua\n--- ```\n", None),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_doc_example_diagnostic() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UndefinedField);
+        ws.enable_check(DiagnosticCode::ParamTypeNotMatch);
+
+        let lib = r#"---@class lib
+local M = {}
+
+---@param a number
+---@param b number
+---@return number
+---@example
+--- ```lua
+--- local lib = require("lib")
+--- local c = lib.add(1, 2)
+--- lib.sub(1, 2)
+--- lib.add("1", 2)
+--- ```
+function M.add(a, b)
+    return a + b
+end
+
+return M
+"#;
+        ws.def_file("lib.lua", lib);
+        let examples = extract_doc_examples_lua(lib, None);
+        assert_eq!(examples.len(), 1);
+        let file_id = ws.def_file("lib.example.lua", &examples[0]);
+        let diagnostics = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap();
+        let mut results = diagnostics
+            .iter()
+            .filter_map(|diagnostic| match &diagnostic.code {
+                Some(NumberOrString::String(code)) => Some((
+                    code.clone(),
+                    diagnostic.range.start.line,
+                    diagnostic.range.start.character,
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        results.sort();
+        assert_eq!(
+            results,
+            vec![
+                ("param-type-not-match".to_string(), 11, 12),
+                ("undefined-field".to_string(), 10, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_doc_example_module_binding() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UndefinedGlobal);
+        ws.enable_check(DiagnosticCode::ParamTypeNotMatch);
+
+        let lib = r#"local M = {}
+
+---@param a number
+---@example M.add(1)
+---@usage
+--- local M = require("lib")
+--- M.add(3)
+function M.add(a) end
+
+---@usage M.add("1")
+function M.sub() end
+
+return M
+"#;
+        ws.def_file("lib.lua", lib);
+        let examples = extract_doc_examples_lua(lib, Some("lib"));
+        assert_eq!(examples.len(), 3);
+        // the export is bound on the last empty line before the code
+        assert_eq!(
+            examples[0].split('\n').nth(2),
+            Some(r#"local M = require("lib")"#)
+        );
+        // the example which declares the name itself is left as it is
+        assert_eq!(examples[1].matches(r#"require("lib")"#).count(), 1);
+
+        let mut results = Vec::new();
+        for (idx, example) in examples.iter().enumerate() {
+            let file_id = ws.def_file(&format!("lib.example-{}.lua", idx + 1), example);
+            let diagnostics = ws
+                .analysis
+                .diagnose_file(file_id, CancellationToken::new())
+                .unwrap();
+            results.extend(
+                diagnostics
+                    .iter()
+                    .filter_map(|diagnostic| match &diagnostic.code {
+                        Some(NumberOrString::String(code)) => {
+                            Some((code.clone(), diagnostic.range.start.line))
+                        }
+                        _ => None,
+                    }),
+            );
+        }
+        assert_eq!(results, vec![("param-type-not-match".to_string(), 9)]);
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod test {
+    use lsp_types::NumberOrString;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{DiagnosticCode, Emmyrc, EmmyrcLuaVersion, VirtualWorkspace};
+
+    #[test]
+    fn test_valid_pattern() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::InvalidPattern,
+            r#"
+            local s = "key = value"
+            local k, v = string.match(s, "^(%w+)%s*=%s*(.-)$")
+            local a = s:find("[%]%-]+")
+            local b = s:gsub("(%a)%1", "%1")
+            local c = s:match("%b()")
+            local d = s:match("%f[%w]%w+")
+            local e = s:find("(", 1, true)
+            for word in s:gmatch("[^%s]+") do end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_zero_class_pattern() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let mut config = Emmyrc::default();
+        config.runtime.version = EmmyrcLuaVersion::Lua51;
+        ws.analysis.update_config(config.into());
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::InvalidPattern,
+            r#"
+            local s = "a\0b"
+            local a = s:find("%z")
+            local b = s:gsub("[%z%s]+", "")
+            local c = s:match("%Z+")
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        let cases = [
+            r#"local a = string.find("s", "abc%")"#,
+            r#"local a = string.match("s", "%b(")"#,
+            r#"local a = string.match("s", "%fa")"#,
+            r#"local a = string.gmatch("s", "[abc")"#,
+            r#"local a = string.gsub("s", "(abc", "")"#,
+            r#"local a = string.match("s", "abc)")"#,
+            r#"local a = string.match("s", "(a)%2")"#,
+            r#"local a = ("s"):match("%y")"#,
+        ];
+        for case in cases {
+            assert!(
+                !ws.check_code_for(DiagnosticCode::InvalidPattern, case),
+                "{}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_pattern_message() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        let file_id = ws.def(
+            r#"
+            local a = string.match("s", "(a)%2")
+            "#,
+        );
+        let diagnostics = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap();
+        let code = Some(NumberOrString::String(
+            DiagnosticCode::InvalidPattern.get_name().to_string(),
+        ));
+        let diagnostic = diagnostics.iter().find(|d| d.code == code).unwrap();
+        assert_eq!(diagnostic.message, "invalid capture index `%2`");
+        assert_eq!(diagnostic.range.start.character, 44);
+        assert_eq!(diagnostic.range.end.character, 46);
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod test {
+    use lsp_types::NumberOrString;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{extract_markdown_lua_blocks, DiagnosticCode, FileId, VirtualWorkspace};
+
+    fn def_markdown(ws: &mut VirtualWorkspace, file_name: &str, markdown: &str) -> Vec<FileId> {
+        let files = extract_markdown_lua_blocks(markdown)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, block)| {
+                let uri = ws.virtual_url_generator.new_uri(&format!(
+                    "{}/block-{}.lua",
+                    file_name,
+                    idx + 1
+                ));
+                (uri, Some(block))
+            })
+            .collect();
+        let mut file_ids = ws.analysis.update_isolated_files_by_uri(files);
+        file_ids.sort();
+        file_ids
+    }
+
+    fn get_diagnostic_positions(
+        ws: &VirtualWorkspace,
+        file_id: FileId,
+        code: DiagnosticCode,
+    ) -> Vec<(u32, u32)> {
+        let code = Some(NumberOrString::String(code.get_name().to_string()));
+        ws.analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default()
+            .iter()
+            .filter(|diagnostic| diagnostic.code == code)
+            .map(|diagnostic| {
+                (
+                    diagnostic.range.start.line,
+                    diagnostic.range.start.character,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_markdown_lua_blocks() {
+        let markdown = "# Title\n\n```lua\nlocal a = 1\n```\n\n```js\nlet b = 2\n```\n\n  ~~~Lua title\n  return a\n";
+        assert_eq!(
+            extract_markdown_lua_blocks(markdown),
+            vec![
+                "\n\n\nlocal a = 1".to_string(),
+                "\n\n\n\n\n\n\n\n\n\n\n  return a\n".to_string()
+            ]
+        );
+        assert!(extract_markdown_lua_blocks("```js\nlet a = 1\n```\n").is_empty());
+        // the lua fence inside another block is not a code block
+        assert!(extract_markdown_lua_blocks("````md\n```lua\nlocal a\n```\n````\n").is_empty());
+    }
+
+    #[test]
+    fn test_markdown_diagnostic_position() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UndefinedGlobal);
+
+        let markdown = "# Example\n\n```lua\nlocal value = 1\nprint(value)\n```\n\nText\n\n```lua\n    print(value)\n```\n";
+        let file_ids = def_markdown(&mut ws, "README.md", markdown);
+        assert_eq!(file_ids.len(), 2);
+        assert!(
+            get_diagnostic_positions(&ws, file_ids[0], DiagnosticCode::UndefinedGlobal).is_empty()
+        );
+        // the local of the first block is not visible in the second one
+        assert_eq!(
+            get_diagnostic_positions(&ws, file_ids[1], DiagnosticCode::UndefinedGlobal),
+            vec![(10, 10)]
+        );
+    }
+
+    #[test]
+    fn test_markdown_globals_are_isolated() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UndefinedGlobal);
+
+        let markdown = "```lua\nconfig = { debug = true }\n_G.level = 1\nprint(config.debug)\n```\n\n```lua\nprint(config)\n```\n";
+        let file_ids = def_markdown(&mut ws, "README.md", markdown);
+        // the global of the block is visible in the block, and the block still sees `_G`
+        assert!(
+            get_diagnostic_positions(&ws, file_ids[0], DiagnosticCode::UndefinedGlobal).is_empty()
+        );
+        // but it is not visible in the other blocks and in the real files
+        assert_eq!(
+            get_diagnostic_positions(&ws, file_ids[1], DiagnosticCode::UndefinedGlobal),
+            vec![(7, 6)]
+        );
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UndefinedGlobal,
+            r#"
+            print(config, level)
+            "#
+        ));
+
+        let global_index = ws.analysis.compilation.get_db().get_global_index();
+        assert!(!global_index.is_exist_global_decl("config"));
+        assert!(!global_index.is_exist_global_decl("level"));
+    }
+
+    #[test]
+    fn test_markdown_block_is_not_module() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let file_ids = def_markdown(&mut ws, "lib.md", "```lua\nreturn { value = 1 }\n```\n");
+        let module_index = ws.analysis.compilation.get_db().get_module_index();
+        assert!(module_index.get_module(file_ids[0]).is_none());
+        assert!(module_index.find_module("lib.md.block-1").is_none());
+    }
+}
@@ -4,17 +4,22 @@ mod await_in_sync_test;
 mod check_return_count_test;
 mod code_style;
 mod disable_line_test;
+mod doc_example_test;
 mod duplicate_doc_field_test;
 mod duplicate_require_test;
 mod incomplete_signature_doc_test;
 mod inject_field_test;
+mod invalid_pattern_test;
+mod markdown_test;
 mod missing_fields_test;
 mod missing_parameter_test;
 mod need_check_nil_test;
 mod param_type_check_test;
+mod quick_fix_test;
 mod redefined_local_test;
 mod redundant_parameter_test;
 mod return_type_mismatch_test;
+mod string_format_test;
 mod syntax_error_test;
 mod unbalanced_assignments_test;
 mod undefined_doc_param_test;
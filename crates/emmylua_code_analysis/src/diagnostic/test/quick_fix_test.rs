@@ -0,0 +1,244 @@
+#[cfg(test)]
+mod test {
+    use lsp_types::NumberOrString;
+    use rowan::{TextRange, TextSize};
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{
+        apply_text_edits, build_quick_fix, DiagnosticCode, LuaQuickFix, LuaTextEdit,
+        VirtualWorkspace,
+    };
+
+    fn apply_quick_fixes(ws: &mut VirtualWorkspace, code: DiagnosticCode, text: &str) -> String {
+        ws.enable_check(code);
+        let file_id = ws.def(text);
+        let diagnostics = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default();
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let document = semantic_model.get_document();
+        let code_string = Some(NumberOrString::String(code.get_name().to_string()));
+
+        let mut edits: Vec<LuaTextEdit> = Vec::new();
+        for diagnostic in diagnostics {
+            if diagnostic.code != code_string {
+                continue;
+            }
+            let range = document.to_rowan_range(diagnostic.range).unwrap();
+            if let Some(fix) = build_quick_fix(&semantic_model, code, range) {
+                edits.extend(fix.edits);
+            }
+        }
+
+        apply_text_edits(text, &edits).unwrap()
+    }
+
+    #[test]
+    fn test_remove_unused() {
+        let mut ws = VirtualWorkspace::new();
+
+        let result = apply_quick_fixes(
+            &mut ws,
+            DiagnosticCode::Unused,
+            r#"
+            ---@type string
+            local a = "a"
+            local b, c = 1, 2
+            local d = io.read()
+            local function e() end
+            print(b)
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            local b, _c = 1, 2
+            local _d = io.read()
+            print(b)
+            "#
+        );
+    }
+
+    #[test]
+    fn test_unused_documented_param() {
+        let mut ws = VirtualWorkspace::new();
+
+        let result = apply_quick_fixes(
+            &mut ws,
+            DiagnosticCode::Unused,
+            r#"
+            ---@param a integer
+            function f(a, b) end
+            function g(a) end
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            ---@param a integer
+            function f(a, b) end
+            function g(_a) end
+            "#
+        );
+    }
+
+    #[test]
+    fn test_disable_global_define() {
+        let mut ws = VirtualWorkspace::new();
+        ws.enable_check(DiagnosticCode::DisableGlobalDefine);
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::DisableGlobalDefine,
+            r#"
+            count = 1
+            "#
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::DisableGlobalDefine,
+            r#"
+            local t = {}
+            _G.value = 1
+            t.value = 1
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_add_local() {
+        let mut ws = VirtualWorkspace::new();
+
+        let result = apply_quick_fixes(
+            &mut ws,
+            DiagnosticCode::DisableGlobalDefine,
+            r#"
+            function run()
+                total = 0
+                total = total + 1
+                return total
+            end
+
+            function helper()
+                return helper()
+            end
+
+            print(late)
+            late = 1
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            local function run()
+                local total = 0
+                total = total + 1
+                return total
+            end
+
+            local function helper()
+                return helper()
+            end
+
+            print(late)
+            late = 1
+            "#
+        );
+    }
+
+    #[test]
+    fn test_add_param_doc() {
+        let mut ws = VirtualWorkspace::new();
+
+        let result = apply_quick_fixes(
+            &mut ws,
+            DiagnosticCode::IncompleteSignatureDoc,
+            r#"
+            local M = {}
+
+            ---@param a integer
+            ---@return integer
+            local function f(a, b, c)
+                return a
+            end
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            local M = {}
+
+            ---@param a integer
+            ---@param b any
+            ---@param c any
+            ---@return integer
+            local function f(a, b, c)
+                return a
+            end
+            "#
+        );
+    }
+
+    fn build_quick_fixes(
+        ws: &mut VirtualWorkspace,
+        codes: &[DiagnosticCode],
+        text: &str,
+    ) -> Vec<LuaQuickFix> {
+        for code in codes {
+            ws.enable_check(*code);
+        }
+        let file_id = ws.def(text);
+        let diagnostics = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default();
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let document = semantic_model.get_document();
+
+        let mut fixes = Vec::new();
+        for code in codes {
+            let code_string = Some(NumberOrString::String(code.get_name().to_string()));
+            for diagnostic in &diagnostics {
+                if diagnostic.code != code_string {
+                    continue;
+                }
+                let range = document.to_rowan_range(diagnostic.range).unwrap();
+                fixes.extend(build_quick_fix(&semantic_model, *code, range));
+            }
+        }
+        fixes
+    }
+
+    #[test]
+    fn test_rename_param_conflicts_with_add_param_doc() {
+        let mut ws = VirtualWorkspace::new();
+
+        let fixes = build_quick_fixes(
+            &mut ws,
+            &[DiagnosticCode::Unused, DiagnosticCode::MissingGlobalDoc],
+            r#"
+            function g(a)
+            end
+            "#,
+        );
+        assert_eq!(fixes.len(), 2);
+        assert!(fixes[0].is_conflict(&fixes[1]));
+    }
+
+    #[test]
+    fn test_apply_overlapping_edits() {
+        let edits = vec![
+            LuaTextEdit::delete(TextRange::new(TextSize::from(2), TextSize::from(6))),
+            LuaTextEdit::insert(TextSize::from(4), "x".to_string()),
+        ];
+        assert_eq!(apply_text_edits("local a = 1", &edits), None);
+
+        let edits = vec![
+            LuaTextEdit::insert(TextSize::from(6), "_".to_string()),
+            LuaTextEdit::delete(TextRange::new(TextSize::from(0), TextSize::from(6))),
+        ];
+        assert_eq!(
+            apply_text_edits("local a = 1", &edits),
+            Some("_a = 1".to_string())
+        );
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_format_args() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            ---@type integer
+            local count
+            ---@type number
+            local ratio
+            local a = string.format("%d items, %5.2f%%, %s, %q", count, ratio, {}, "name")
+            local b = ("%x-%X"):format(255, 2.0)
+            local c = string.format("%s %s", ...)
+            local d = string.format("%d: %s", count, tostring(count))
+            local e = string.format("100%%")
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_format_arg_count() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            local a = string.format("%s and %s", "one")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            local a = string.format("%s", "one", "two")
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_format_arg_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            local a = string.format("%d", "count")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            local a = string.format("%d", 1.5)
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            ---@type string
+            local name
+            local a = ("%f"):format(name)
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_format_invalid_conversion() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            local a = string.format("%y", 1)
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::StringFormatMismatch,
+            r#"
+            local a = string.format("%123d", 1)
+            "#
+        ));
+    }
+}
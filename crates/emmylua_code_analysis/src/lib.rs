@@ -121,6 +121,19 @@ impl EmmyLuaAnalysis {
     }
 
     pub fn update_files_by_uri(&mut self, files: Vec<(Uri, Option<String>)>) -> Vec<FileId> {
+        self.update_files(files, false)
+    }
+
+    /// Updates the isolated documents, eg: the code blocks of the markdown files. Their globals are
+    /// not visible to the other files and they can not be required
+    pub fn update_isolated_files_by_uri(
+        &mut self,
+        files: Vec<(Uri, Option<String>)>,
+    ) -> Vec<FileId> {
+        self.update_files(files, true)
+    }
+
+    fn update_files(&mut self, files: Vec<(Uri, Option<String>)>, isolated: bool) -> Vec<FileId> {
         let mut removed_files = HashSet::new();
         let mut updated_files = HashSet::new();
         {
@@ -132,6 +145,10 @@ impl EmmyLuaAnalysis {
                     .get_db_mut()
                     .get_vfs_mut()
                     .set_file_content(&uri, text);
+                self.compilation
+                    .get_db_mut()
+                    .get_vfs_mut()
+                    .set_isolated(file_id, isolated && is_new_text);
                 removed_files.insert(file_id);
                 if is_new_text {
                     updated_files.insert(file_id);
@@ -156,6 +173,20 @@ impl EmmyLuaAnalysis {
         self.update_files_by_uri(files)
     }
 
+    pub fn update_isolated_files_by_path(
+        &mut self,
+        files: Vec<(PathBuf, Option<String>)>,
+    ) -> Vec<FileId> {
+        let files = files
+            .into_iter()
+            .filter_map(|(path, text)| {
+                let uri = file_path_to_uri(&path)?;
+                Some((uri, text))
+            })
+            .collect();
+        self.update_isolated_files_by_uri(files)
+    }
+
     pub fn update_config(&mut self, config: Arc<Emmyrc>) {
         self.emmyrc = config.clone();
         self.compilation.update_config(config.clone());
@@ -175,16 +206,28 @@ impl EmmyLuaAnalysis {
             .diagnose_file(&self.compilation, file_id, cancel_token)
     }
 
+    pub fn diagnose_file_with_timings(
+        &self,
+        file_id: FileId,
+        cancel_token: CancellationToken,
+    ) -> Option<(Vec<lsp_types::Diagnostic>, CheckerTimings)> {
+        self.diagnostic
+            .diagnose_file_with_timings(&self.compilation, file_id, cancel_token)
+    }
+
     pub fn reindex(&mut self) {
         let module = self.compilation.get_db().get_module_index();
         let std_file_ids = module.get_std_file_ids();
         let main_file_ids = module.get_main_workspace_file_ids();
         let lib_file_ids = module.get_lib_file_ids();
+        // the isolated documents are not in the module index
+        let isolated_file_ids = self.compilation.get_db().get_vfs().get_isolated_file_ids();
         self.compilation.clear_index();
 
         self.compilation.update_index(std_file_ids);
         self.compilation.update_index(lib_file_ids);
         self.compilation.update_index(main_file_ids);
+        self.compilation.update_index(isolated_file_ids);
     }
 }
 
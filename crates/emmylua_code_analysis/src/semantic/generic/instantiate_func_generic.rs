@@ -1,11 +1,13 @@
 use std::ops::Deref;
 
-use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaExpr};
+use emmylua_parser::{
+    LuaAstNode, LuaCallArgList, LuaCallExpr, LuaClosureExpr, LuaExpr, LuaStat, PathTrait,
+};
 
 use crate::{
     db_index::{DbIndex, LuaType},
     semantic::{infer::InferFailReason, infer_expr, LuaInferCache},
-    LuaFunctionType,
+    GenericTplId, LuaDeclId, LuaFunctionType, LuaGenericType, LuaSignatureId,
 };
 
 use super::{
@@ -43,6 +45,7 @@ pub fn instantiate_func_generic(
     }
 
     let mut substitutor = TypeSubstitutor::new();
+    infer_coroutine_yield_type(db, cache, func, &call_expr, &mut substitutor);
     tpl_pattern_match_args(
         db,
         cache,
@@ -97,3 +100,102 @@ fn infer_self_type(
 
     Ok(())
 }
+
+// `coroutine.yield` inside the body function of `coroutine.create` or `coroutine.wrap`, the yield
+// values and the resume values are bound to the `thread<Yield, Resume, Return>` of the coroutine
+fn infer_coroutine_yield_type(
+    db: &DbIndex,
+    cache: &mut LuaInferCache,
+    func: &LuaFunctionType,
+    call_expr: &LuaCallExpr,
+    substitutor: &mut TypeSubstitutor,
+) -> Option<()> {
+    if call_expr.get_access_path()? != "coroutine.yield" {
+        return None;
+    }
+
+    let closure = call_expr.ancestors::<LuaClosureExpr>().next()?;
+    let body_call = closure
+        .get_parent::<LuaCallArgList>()?
+        .get_parent::<LuaCallExpr>()?;
+    let (yield_type, resume_type) = match body_call.get_access_path()?.as_str() {
+        "coroutine.create" => match infer_coroutine_thread_type(db, cache, &body_call) {
+            Some(thread) => {
+                let params = thread.get_params();
+                (params.first().cloned(), params.get(1).cloned())
+            }
+            None => (None, None),
+        },
+        "coroutine.wrap" => (None, None),
+        _ => return None,
+    };
+
+    // the values of the first resume are passed as the params of the body function, the resume
+    // values of an unannotated coroutine are unknown
+    let resume_type = resume_type
+        .or_else(|| {
+            let signature_id = LuaSignatureId::from_closure(cache.get_file_id(), &closure);
+            db.get_signature_index()
+                .get(&signature_id)?
+                .get_type_params()
+                .first()
+                .and_then(|(_, typ)| typ.clone())
+        })
+        .unwrap_or(LuaType::Any);
+
+    if let (Some(yield_type), Some((_, Some(param_type)))) = (yield_type, func.get_params().first())
+    {
+        if let Some(tpl_id) = get_tpl_id(param_type) {
+            substitutor.insert_type(tpl_id, yield_type);
+        }
+    }
+
+    if let Some(ret_type) = func.get_ret().first() {
+        if let Some(tpl_id) = get_tpl_id(ret_type) {
+            substitutor.insert_type(tpl_id, resume_type);
+        }
+    }
+
+    Some(())
+}
+
+fn infer_coroutine_thread_type(
+    db: &DbIndex,
+    cache: &mut LuaInferCache,
+    body_call: &LuaCallExpr,
+) -> Option<LuaGenericType> {
+    let typ = match body_call.get_parent::<LuaStat>()? {
+        LuaStat::LocalStat(local_stat) => {
+            let idx = local_stat
+                .get_value_exprs()
+                .position(|expr| expr.syntax() == body_call.syntax())?;
+            let local_name = local_stat.get_local_name_list().nth(idx)?;
+            let decl_id = LuaDeclId::new(cache.get_file_id(), local_name.get_position());
+            db.get_type_index()
+                .get_type_cache(&decl_id.into())?
+                .as_type()
+                .clone()
+        }
+        LuaStat::AssignStat(assign_stat) => {
+            let (vars, exprs) = assign_stat.get_var_and_expr_list();
+            let idx = exprs
+                .iter()
+                .position(|expr| expr.syntax() == body_call.syntax())?;
+            infer_expr(db, cache, vars.get(idx)?.clone().into()).ok()?
+        }
+        _ => return None,
+    };
+
+    match typ {
+        LuaType::Generic(generic) if generic.is_thread() => Some(generic.deref().clone()),
+        _ => None,
+    }
+}
+
+fn get_tpl_id(typ: &LuaType) -> Option<GenericTplId> {
+    match typ {
+        LuaType::TplRef(tpl) => Some(tpl.get_tpl_id()),
+        LuaType::Variadic(inner) => get_tpl_id(inner),
+        _ => None,
+    }
+}
@@ -53,6 +53,7 @@ fn instantiate_array(db: &DbIndex, base: &LuaType, substitutor: &TypeSubstitutor
 fn instantiate_tuple(db: &DbIndex, tuple: &LuaTupleType, substitutor: &TypeSubstitutor) -> LuaType {
     let tuple_types = tuple.get_types();
     let mut new_types = Vec::new();
+    let mut has_vararg_rest = false;
     for t in tuple_types {
         if let LuaType::Variadic(inner) = t {
             if let LuaType::TplRef(tpl) = inner.deref() {
@@ -60,7 +61,19 @@ fn instantiate_tuple(db: &DbIndex, tuple: &LuaTupleType, substitutor: &TypeSubst
                     match value {
                         SubstitutorValue::MultiTypes(types) => {
                             for typ in types {
-                                new_types.push(typ.clone());
+                                match typ {
+                                    // the varargs `...` passed as the rest arguments
+                                    LuaType::MuliReturn(multi) => match multi.deref() {
+                                        LuaMultiReturn::Base(base) => {
+                                            new_types.push(LuaType::Variadic(base.clone().into()));
+                                            has_vararg_rest = true;
+                                        }
+                                        LuaMultiReturn::Multi(multi) => {
+                                            new_types.extend(multi.iter().cloned());
+                                        }
+                                    },
+                                    _ => new_types.push(typ.clone()),
+                                }
                             }
                         }
                         SubstitutorValue::Params(params) => {
@@ -80,6 +93,13 @@ fn instantiate_tuple(db: &DbIndex, tuple: &LuaTupleType, substitutor: &TypeSubst
         let t = instantiate_type_generic(db, t, substitutor);
         new_types.push(t);
     }
+
+    // `[T...]` of the varargs `...`, eg: `table.pack(...)`, is an array of the vararg type
+    if has_vararg_rest {
+        if let [LuaType::Variadic(base)] = new_types.as_slice() {
+            return LuaType::Array(base.clone());
+        }
+    }
     LuaType::Tuple(LuaTupleType::new(new_types).into())
 }
 
@@ -119,6 +139,9 @@ pub fn instantiate_doc_function(
                                 ));
                             }
                         }
+                    } else {
+                        // the unbound tpl is kept, it can be bound by the later inference
+                        new_params.push(origin_param.clone());
                     }
                 }
             }
@@ -154,6 +177,8 @@ pub fn instantiate_doc_function(
                                 ));
                             }
                         }
+                    } else {
+                        new_returns.push(ret_type.clone());
                     }
                 }
             }
@@ -231,7 +256,10 @@ fn instantiate_generic(
     let generic_params = generic.get_params();
     let mut new_params = Vec::new();
     for param in generic_params {
-        let new_param = instantiate_type_generic(db, param, substitutor);
+        let new_param = match instantiate_thread_param(generic, param, substitutor) {
+            Some(new_param) => new_param,
+            None => instantiate_type_generic(db, param, substitutor),
+        };
         new_params.push(new_param);
     }
 
@@ -257,6 +285,40 @@ fn instantiate_generic(
     LuaType::Generic(LuaGenericType::new(type_decl_id, new_params).into())
 }
 
+// the params of `thread<Yield, Resume, Return>` are the types of every value passed across the
+// yields, so the tpl bound to several values becomes their union instead of the first value
+fn instantiate_thread_param(
+    generic: &LuaGenericType,
+    param: &LuaType,
+    substitutor: &TypeSubstitutor,
+) -> Option<LuaType> {
+    if !generic.is_thread() {
+        return None;
+    }
+    let LuaType::TplRef(tpl) = param else {
+        return None;
+    };
+
+    let types = match substitutor.get(tpl.get_tpl_id())? {
+        SubstitutorValue::MultiTypes(types) => types.clone(),
+        SubstitutorValue::Params(params) => params
+            .iter()
+            .map(|(_, typ)| typ.clone().unwrap_or(LuaType::Any))
+            .collect(),
+        _ => return None,
+    };
+    if types.len() < 2 {
+        return None;
+    }
+
+    Some(
+        types
+            .iter()
+            .skip(1)
+            .fold(types[0].clone(), |acc, typ| TypeOps::Union.apply(&acc, typ)),
+    )
+}
+
 fn instantiate_table_generic(
     db: &DbIndex,
     table_params: &Vec<LuaType>,
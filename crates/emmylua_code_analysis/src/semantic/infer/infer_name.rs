@@ -1,12 +1,14 @@
-use emmylua_parser::{LuaAstNode, LuaNameExpr};
+use emmylua_parser::{LuaAstNode, LuaExpr, LuaNameExpr};
+use rowan::TextSize;
 use smol_str::SmolStr;
 
 use crate::{
     db_index::{DbIndex, LuaDeclOrMemberId},
-    LuaDecl, LuaDeclExtra, LuaFlowId, LuaInferCache, LuaMemberId, LuaType, TypeOps, VarRefId,
+    FileId, InFiled, LuaChunkEnv, LuaDecl, LuaDeclExtra, LuaFlowId, LuaInferCache, LuaMemberId,
+    LuaMemberKey, LuaMemberOwner, LuaType, TypeOps, VarRefId,
 };
 
-use super::{InferFailReason, InferResult};
+use super::{infer_expr, InferFailReason, InferResult};
 
 pub fn infer_name_expr(
     db: &DbIndex,
@@ -49,10 +51,75 @@ pub fn infer_name_expr(
         }
         Ok(decl_type)
     } else {
+        if let Some(env_type) = infer_chunk_env_type(db, cache, &name_expr) {
+            match infer_chunk_env_member_type(db, &env_type, name) {
+                Err(InferFailReason::FieldDotFound) => {
+                    let file_id = cache.get_file_id();
+                    if !is_global_env_visible(db, file_id, name_expr.get_position()) {
+                        return Err(InferFailReason::None);
+                    }
+                }
+                result => return result,
+            }
+        }
+
         infer_global_type(db, name)
     }
 }
 
+/// Infer the env table switched by `module` or `setfenv` at the position of the name expr,
+/// returns `None` if the name refers to `_G`
+pub fn infer_chunk_env_type(
+    db: &DbIndex,
+    cache: &mut LuaInferCache,
+    name_expr: &LuaNameExpr,
+) -> Option<LuaType> {
+    let file_id = cache.get_file_id();
+    let env = db
+        .get_decl_index()
+        .get_decl_tree(&file_id)?
+        .get_chunk_env(name_expr.get_position())?;
+    match env {
+        LuaChunkEnv::Module { range, .. } | LuaChunkEnv::Isolated { range } => {
+            Some(LuaType::TableConst(InFiled::new(file_id, *range)))
+        }
+        LuaChunkEnv::Table { syntax_id, .. } => {
+            let root = name_expr.get_root();
+            let env_expr = LuaExpr::cast(syntax_id.to_node_from_root(&root)?)?;
+            infer_expr(db, cache, env_expr).ok()
+        }
+    }
+}
+
+/// Whether the names which are not fields of the env table switched by `module` or `setfenv` are
+/// found in `_G` at the position
+pub fn is_global_env_visible(db: &DbIndex, file_id: FileId, position: TextSize) -> bool {
+    db.get_decl_index()
+        .get_decl_tree(&file_id)
+        .and_then(|decl_tree| decl_tree.get_chunk_env(position))
+        .is_none_or(|env| env.is_seeall())
+}
+
+/// `FieldDotFound` means the name is not a field of the env table, the caller falls back to the
+/// global type when the env sees `_G`
+fn infer_chunk_env_member_type(db: &DbIndex, env_type: &LuaType, name: &str) -> InferResult {
+    let owner = get_chunk_env_owner(env_type).ok_or(InferFailReason::FieldDotFound)?;
+    let key = LuaMemberKey::Name(name.into());
+    match db.get_member_index().get_member_item(&owner, &key) {
+        Some(member_item) => member_item.resolve_type(db),
+        None => Err(InferFailReason::FieldDotFound),
+    }
+}
+
+pub fn get_chunk_env_owner(env_type: &LuaType) -> Option<LuaMemberOwner> {
+    match env_type {
+        LuaType::TableConst(in_file_range) => Some(LuaMemberOwner::Element(in_file_range.clone())),
+        LuaType::Def(id) | LuaType::Ref(id) => Some(LuaMemberOwner::Type(id.clone())),
+        LuaType::Instance(instance) => Some(LuaMemberOwner::Element(instance.get_range().clone())),
+        _ => None,
+    }
+}
+
 fn get_decl_type(db: &DbIndex, decl: &LuaDecl) -> InferResult {
     if decl.is_global() {
         let name = decl.get_name();
@@ -19,8 +19,11 @@ use infer_call::infer_call_expr;
 pub use infer_call_func::infer_call_expr_func;
 pub use infer_fail_reason::InferFailReason;
 use infer_index::infer_index_expr;
-pub use infer_name::find_self_decl_or_member_id;
-use infer_name::{infer_name_expr, infer_param};
+use infer_name::infer_name_expr;
+pub(crate) use infer_name::infer_param;
+pub use infer_name::{
+    find_self_decl_or_member_id, get_chunk_env_owner, infer_chunk_env_type, is_global_env_visible,
+};
 use infer_table::infer_table_expr;
 pub use infer_table::{infer_table_field_value_should_be, infer_table_should_be};
 use infer_unary::infer_unary_expr;
@@ -130,7 +133,13 @@ fn infer_literal_expr(db: &DbIndex, config: &LuaInferCache, expr: LuaLiteralExpr
                 Some(decl) if decl.is_global() => LuaType::Any,
                 Some(decl) if decl.is_param() => {
                     let base = infer_param(db, decl).unwrap_or(LuaType::Unknown);
-                    LuaType::MuliReturn(LuaMultiReturn::Base(base).into())
+                    match base {
+                        // `---@param ... [integer, string]` types each vararg by position
+                        LuaType::Tuple(tuple) => LuaType::MuliReturn(
+                            LuaMultiReturn::Multi(tuple.get_types().to_vec()).into(),
+                        ),
+                        _ => LuaType::MuliReturn(LuaMultiReturn::Base(base).into()),
+                    }
                 }
                 _ => LuaType::Any, // 默认返回 Any
             };
@@ -5,7 +5,9 @@ mod member;
 mod overload_resolve;
 mod reference;
 mod semantic_info;
+mod string_syntax;
 mod type_check;
+mod type_coverage;
 mod visibility;
 
 use std::cell::RefCell;
@@ -26,7 +28,7 @@ use semantic_info::{
     infer_node_semantic_decl, infer_node_semantic_info, infer_token_semantic_decl,
     infer_token_semantic_info,
 };
-pub(crate) use type_check::check_type_compact;
+pub use type_check::check_type_compact;
 use type_check::is_sub_type_of;
 use visibility::check_visibility;
 
@@ -38,10 +40,17 @@ use crate::{
 use crate::{LuaFunctionType, LuaMemberKey, LuaTypeOwner};
 pub use generic::{instantiate_type_generic, TypeSubstitutor};
 pub use infer::InferFailReason;
-pub(crate) use infer::{infer_call_expr_func, infer_expr};
+pub(crate) use infer::{infer_call_expr_func, infer_expr, is_global_env_visible};
 use overload_resolve::resolve_signature;
 pub use semantic_info::SemanticDeclLevel;
+pub use string_syntax::{
+    classify_string_literal, find_string_call_arg, get_string_literal, get_string_value_range,
+    parse_format_string, parse_lua_pattern, FormatSpecifier, FormatString, FormatStringError,
+    LuaPattern, LuaPatternError, LuaPatternErrorKind, LuaPatternItem, LuaPatternItemKind,
+    LuaStringArgKind, LuaStringCallArg,
+};
 pub use type_check::{TypeCheckFailReason, TypeCheckResult};
+pub use type_coverage::{collect_type_coverage, LuaCoverageCount, LuaTypeCoverage};
 
 #[derive(Debug)]
 pub struct SemanticModel<'a> {
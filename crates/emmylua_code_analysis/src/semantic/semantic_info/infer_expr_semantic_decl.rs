@@ -4,7 +4,10 @@ use emmylua_parser::{
 };
 
 use crate::{
-    semantic::{infer::find_self_decl_or_member_id, member::get_buildin_type_map_type_id},
+    semantic::{
+        infer::{find_self_decl_or_member_id, get_chunk_env_owner, infer_chunk_env_type},
+        member::get_buildin_type_map_type_id,
+    },
     DbIndex, LuaDeclId, LuaDeclOrMemberId, LuaInferCache, LuaInstanceType, LuaMemberId,
     LuaMemberKey, LuaMemberOwner, LuaSemanticDeclId, LuaType, LuaTypeCache, LuaTypeDeclId,
     LuaUnionType,
@@ -65,6 +68,10 @@ fn infer_name_expr_semantic_decl(
         return infer_self_semantic_decl(db, cache, name_expr);
     }
 
+    if let Some(semantic_decl) = infer_chunk_env_semantic_decl(db, cache, &name, &name_expr) {
+        return Some(semantic_decl);
+    }
+
     let decl_id = get_name_decl_id(db, cache, &name, name_expr.clone())?;
     let decl = db.get_decl_index().get_decl(&decl_id)?;
     if semantic_guard.reached_limit() {
@@ -130,6 +137,25 @@ fn get_name_decl_id(
     db.get_global_index().resolve_global_decl_id(db, name)
 }
 
+fn infer_chunk_env_semantic_decl(
+    db: &DbIndex,
+    cache: &mut LuaInferCache,
+    name: &str,
+    name_expr: &LuaNameExpr,
+) -> Option<LuaSemanticDeclId> {
+    let file_id = cache.get_file_id();
+    let local_ref = db.get_reference_index().get_local_reference(&file_id)?;
+    if local_ref.get_decl_id(&name_expr.get_range()).is_some() {
+        return None;
+    }
+
+    let env_type = infer_chunk_env_type(db, cache, name_expr)?;
+    let owner = get_chunk_env_owner(&env_type)?;
+    db.get_member_index()
+        .get_member_item(&owner, &LuaMemberKey::Name(name.into()))?
+        .resolve_semantic_decl(db)
+}
+
 fn infer_self_semantic_decl(
     db: &DbIndex,
     cache: &LuaInferCache,
@@ -0,0 +1,130 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatSpecifier {
+    pub range: Range<usize>,
+    /// the conversion character, `%%` is `'%'`
+    pub conversion: char,
+}
+
+impl FormatSpecifier {
+    pub fn consumes_arg(&self) -> bool {
+        self.conversion != '%'
+    }
+
+    pub fn is_integer_conversion(&self) -> bool {
+        matches!(self.conversion, 'd' | 'i' | 'u' | 'c' | 'o' | 'x' | 'X')
+    }
+
+    pub fn is_float_conversion(&self) -> bool {
+        matches!(
+            self.conversion,
+            'a' | 'A' | 'e' | 'E' | 'f' | 'F' | 'g' | 'G'
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatStringError {
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FormatString {
+    pub specifiers: Vec<FormatSpecifier>,
+    pub errors: Vec<FormatStringError>,
+}
+
+impl FormatString {
+    pub fn get_arg_specifiers(&self) -> impl Iterator<Item = &FormatSpecifier> {
+        self.specifiers.iter().filter(|spec| spec.consumes_arg())
+    }
+}
+
+/// Parses the format string of `string.format`, the ranges are the byte offsets in the format
+/// string. A specifier is `%[flags][width][.precision]conversion`, the width and the precision
+/// have at most two digits.
+pub fn parse_format_string(format: &str) -> FormatString {
+    let bytes = format.as_bytes();
+    let len = bytes.len();
+    let mut result = FormatString::default();
+
+    let mut i = 0;
+    while i < len {
+        if bytes[i] != b'%' {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        if i < len && bytes[i] == b'%' {
+            result.specifiers.push(FormatSpecifier {
+                range: start..i + 1,
+                conversion: '%',
+            });
+            i += 1;
+            continue;
+        }
+
+        while i < len && matches!(bytes[i], b'-' | b'+' | b' ' | b'#' | b'0') {
+            i += 1;
+        }
+        let mut valid = skip_digits(bytes, &mut i);
+        if i < len && bytes[i] == b'.' {
+            i += 1;
+            valid &= skip_digits(bytes, &mut i);
+        }
+
+        if i >= len {
+            result.errors.push(FormatStringError { range: start..len });
+            break;
+        }
+
+        let conversion = bytes[i];
+        i += 1;
+        if !valid || !is_conversion(conversion) {
+            result.errors.push(FormatStringError { range: start..i });
+            continue;
+        }
+
+        result.specifiers.push(FormatSpecifier {
+            range: start..i,
+            conversion: conversion as char,
+        });
+    }
+
+    result
+}
+
+// returns false when there are more than two digits
+fn skip_digits(bytes: &[u8], i: &mut usize) -> bool {
+    let start = *i;
+    while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+        *i += 1;
+    }
+
+    *i - start <= 2
+}
+
+fn is_conversion(c: u8) -> bool {
+    matches!(
+        c,
+        b'c' | b'd'
+            | b'i'
+            | b'u'
+            | b'o'
+            | b'x'
+            | b'X'
+            | b'a'
+            | b'A'
+            | b'e'
+            | b'E'
+            | b'f'
+            | b'F'
+            | b'g'
+            | b'G'
+            | b'q'
+            | b's'
+    )
+}
@@ -0,0 +1,255 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuaPatternItemKind {
+    /// `^` at the start or `$` at the end of the pattern
+    Anchor,
+    /// `.`, `%a`, `%d`, ...
+    CharClass,
+    /// `%.`, `%%`, the escape of a non alphanumeric character
+    Escape,
+    /// `[a-z%d_]`
+    Set,
+    /// `*`, `+`, `-`, `?`
+    Quantifier,
+    /// `(`, `)` and the position capture `()`
+    Capture,
+    /// `%1` - `%9`
+    BackReference,
+    /// `%bxy`
+    Balance,
+    /// `%f[set]`
+    Frontier,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LuaPatternItem {
+    pub range: Range<usize>,
+    pub kind: LuaPatternItemKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LuaPatternErrorKind {
+    /// `%` at the end of the pattern
+    EndsWithPercent,
+    /// `%b` without the two delimiters
+    MissingBalanceArgs,
+    /// `%f` not followed by a set
+    MissingFrontierSet,
+    /// `[` without the closing `]`
+    UnclosedSet,
+    /// `(` without the closing `)`
+    UnfinishedCapture,
+    /// `)` without the opening `(`
+    InvalidCaptureClose,
+    /// `%n` refers to a capture that does not exist or is not closed
+    InvalidCaptureIndex(char),
+    /// `%` followed by a letter which is not a character class
+    InvalidEscape(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LuaPatternError {
+    pub range: Range<usize>,
+    pub kind: LuaPatternErrorKind,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LuaPattern {
+    pub items: Vec<LuaPatternItem>,
+    pub errors: Vec<LuaPatternError>,
+}
+
+/// Parses a Lua pattern as `lstrlib` does, the ranges are the byte offsets in the pattern
+pub fn parse_lua_pattern(pattern: &str) -> LuaPattern {
+    let bytes = pattern.as_bytes();
+    let len = bytes.len();
+    let mut result = LuaPattern::default();
+    // each capture and whether it is closed
+    let mut captures: Vec<bool> = Vec::new();
+    // the index and the position of the open captures
+    let mut open_captures: Vec<(usize, usize)> = Vec::new();
+
+    let mut i = 0;
+    while i < len {
+        let single_end = match bytes[i] {
+            b'(' => {
+                if i + 1 < len && bytes[i + 1] == b')' {
+                    result.add_item(i..i + 2, LuaPatternItemKind::Capture);
+                    captures.push(true);
+                    i += 2;
+                } else {
+                    result.add_item(i..i + 1, LuaPatternItemKind::Capture);
+                    open_captures.push((captures.len(), i));
+                    captures.push(false);
+                    i += 1;
+                }
+                continue;
+            }
+            b')' => {
+                if let Some((idx, _)) = open_captures.pop() {
+                    captures[idx] = true;
+                    result.add_item(i..i + 1, LuaPatternItemKind::Capture);
+                } else {
+                    result.add_error(i..i + 1, LuaPatternErrorKind::InvalidCaptureClose);
+                }
+                i += 1;
+                continue;
+            }
+            b'^' if i == 0 => {
+                result.add_item(i..i + 1, LuaPatternItemKind::Anchor);
+                i += 1;
+                continue;
+            }
+            b'$' if i + 1 == len => {
+                result.add_item(i..i + 1, LuaPatternItemKind::Anchor);
+                i += 1;
+                continue;
+            }
+            b'%' => {
+                if i + 1 >= len {
+                    result.add_error(i..len, LuaPatternErrorKind::EndsWithPercent);
+                    break;
+                }
+
+                let next = bytes[i + 1];
+                match next {
+                    b'b' => {
+                        if i + 4 > len {
+                            result.add_error(i..len, LuaPatternErrorKind::MissingBalanceArgs);
+                            break;
+                        }
+                        result.add_item(i..i + 4, LuaPatternItemKind::Balance);
+                        i += 4;
+                        continue;
+                    }
+                    b'f' => {
+                        if i + 2 >= len || bytes[i + 2] != b'[' {
+                            result.add_error(i..i + 2, LuaPatternErrorKind::MissingFrontierSet);
+                            i += 2;
+                            continue;
+                        }
+                        match find_set_end(bytes, i + 2) {
+                            Some(end) => {
+                                result.add_item(i..end, LuaPatternItemKind::Frontier);
+                                i = end;
+                                continue;
+                            }
+                            None => {
+                                result.add_error(i + 2..len, LuaPatternErrorKind::UnclosedSet);
+                                break;
+                            }
+                        }
+                    }
+                    b'0'..=b'9' => {
+                        let idx = (next - b'0') as usize;
+                        if idx == 0 || !captures.get(idx - 1).copied().unwrap_or(false) {
+                            result.add_error(
+                                i..i + 2,
+                                LuaPatternErrorKind::InvalidCaptureIndex(next as char),
+                            );
+                        } else {
+                            result.add_item(i..i + 2, LuaPatternItemKind::BackReference);
+                        }
+                        i += 2;
+                        continue;
+                    }
+                    _ if next.is_ascii_alphabetic() => {
+                        if is_class_letter(next) {
+                            result.add_item(i..i + 2, LuaPatternItemKind::CharClass);
+                        } else {
+                            result.add_error(
+                                i..i + 2,
+                                LuaPatternErrorKind::InvalidEscape(next as char),
+                            );
+                        }
+                        i + 2
+                    }
+                    _ => {
+                        result.add_item(i..i + 2, LuaPatternItemKind::Escape);
+                        i + 2
+                    }
+                }
+            }
+            b'[' => match find_set_end(bytes, i) {
+                Some(end) => {
+                    result.add_item(i..end, LuaPatternItemKind::Set);
+                    end
+                }
+                None => {
+                    result.add_error(i..len, LuaPatternErrorKind::UnclosedSet);
+                    break;
+                }
+            },
+            b'.' => {
+                result.add_item(i..i + 1, LuaPatternItemKind::CharClass);
+                i + 1
+            }
+            _ => i + 1,
+        };
+
+        if single_end < len && matches!(bytes[single_end], b'*' | b'+' | b'-' | b'?') {
+            result.add_item(single_end..single_end + 1, LuaPatternItemKind::Quantifier);
+            i = single_end + 1;
+        } else {
+            i = single_end;
+        }
+    }
+
+    for (_, position) in open_captures {
+        result.add_error(
+            position..position + 1,
+            LuaPatternErrorKind::UnfinishedCapture,
+        );
+    }
+
+    result
+}
+
+impl LuaPattern {
+    fn add_item(&mut self, range: Range<usize>, kind: LuaPatternItemKind) {
+        self.items.push(LuaPatternItem { range, kind });
+    }
+
+    fn add_error(&mut self, range: Range<usize>, kind: LuaPatternErrorKind) {
+        self.errors.push(LuaPatternError { range, kind });
+    }
+}
+
+// the same as `classEnd` in lstrlib, `start` is the position of `[`
+fn find_set_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let len = bytes.len();
+    let mut p = start + 1;
+    if p < len && bytes[p] == b'^' {
+        p += 1;
+    }
+
+    // the first character of the set can be `]`
+    loop {
+        if p >= len {
+            return None;
+        }
+        let c = bytes[p];
+        p += 1;
+        if c == b'%' {
+            if p >= len {
+                return None;
+            }
+            p += 1;
+        }
+        if p >= len {
+            return None;
+        }
+        if bytes[p] == b']' {
+            return Some(p + 1);
+        }
+    }
+}
+
+// `%z` is the class of `\0` in Lua 5.1 and LuaJIT, the later versions still accept it
+fn is_class_letter(c: u8) -> bool {
+    matches!(
+        c.to_ascii_lowercase(),
+        b'a' | b'c' | b'd' | b'g' | b'l' | b'p' | b's' | b'u' | b'w' | b'x' | b'z'
+    )
+}
@@ -0,0 +1,177 @@
+mod format_string;
+mod lua_pattern;
+
+use std::ops::Range;
+
+pub use format_string::{parse_format_string, FormatSpecifier, FormatString, FormatStringError};
+pub use lua_pattern::{
+    parse_lua_pattern, LuaPattern, LuaPatternError, LuaPatternErrorKind, LuaPatternItem,
+    LuaPatternItemKind,
+};
+
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallArgList, LuaCallExpr, LuaExpr, LuaIndexExpr, LuaLiteralExpr,
+    LuaLiteralToken, LuaParenExpr, LuaStringToken,
+};
+use rowan::{TextRange, TextSize};
+
+use super::SemanticModel;
+use crate::LuaType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuaStringArgKind {
+    /// the param typed as `std.FormatString`, eg: the format string of `string.format`
+    FormatString,
+    /// the param typed as `std.Pattern`, eg: the pattern of `string.find` and `string.gsub`
+    Pattern,
+}
+
+impl LuaStringArgKind {
+    fn from_param_type(typ: &LuaType) -> Option<Self> {
+        match typ {
+            LuaType::Ref(type_decl_id) => match type_decl_id.get_name() {
+                "std.FormatString" => Some(Self::FormatString),
+                "std.Pattern" => Some(Self::Pattern),
+                _ => None,
+            },
+            LuaType::Union(union) => union.get_types().iter().find_map(Self::from_param_type),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LuaStringCallArg {
+    pub kind: LuaStringArgKind,
+    /// the expression of the format string or the pattern, for `("%d"):format(1)` it is the
+    /// receiver of the call
+    pub string_expr: LuaExpr,
+    /// the arguments after the format string or the pattern
+    pub rest_args: Vec<LuaExpr>,
+}
+
+/// Finds the argument of a call which is passed to a param typed as `std.FormatString` or
+/// `std.Pattern`, both `string.format(fmt, ...)` and `fmt:format(...)` are supported
+pub fn find_string_call_arg(
+    semantic_model: &SemanticModel,
+    call_expr: &LuaCallExpr,
+) -> Option<LuaStringCallArg> {
+    let func = semantic_model.infer_call_expr_func(call_expr.clone(), None)?;
+    let mut params = func.get_params().to_vec();
+    let mut args = call_expr.get_args_list()?.get_args().collect::<Vec<_>>();
+    match (call_expr.is_colon_call(), func.is_colon_define()) {
+        (true, false) => {
+            // the receiver of the colon call is the first argument
+            let LuaExpr::IndexExpr(index_expr) = call_expr.get_prefix_expr()? else {
+                return None;
+            };
+            args.insert(0, index_expr.get_prefix_expr()?);
+        }
+        (false, true) => params.insert(0, ("self".into(), Some(LuaType::SelfInfer))),
+        _ => {}
+    }
+
+    let (idx, kind) = params.iter().enumerate().find_map(|(idx, (_, typ))| {
+        LuaStringArgKind::from_param_type(typ.as_ref()?).map(|kind| (idx, kind))
+    })?;
+
+    // `string.find(s, pattern, init, true)` does a plain search
+    if kind == LuaStringArgKind::Pattern {
+        let plain_idx = params.iter().position(|(name, _)| name == "plain");
+        if is_true_literal(plain_idx.and_then(|plain_idx| args.get(plain_idx))) {
+            return None;
+        }
+    }
+
+    let string_expr = args.get(idx)?.clone();
+    let rest_args = args.split_off(idx + 1);
+    Some(LuaStringCallArg {
+        kind,
+        string_expr,
+        rest_args,
+    })
+}
+
+/// Classifies a string literal by the call it is passed to, returns `None` when the literal is
+/// neither a format string nor a pattern
+pub fn classify_string_literal(
+    semantic_model: &SemanticModel,
+    string_token: &LuaStringToken,
+) -> Option<LuaStringArgKind> {
+    let literal_expr = string_token.get_parent::<LuaLiteralExpr>()?;
+    // skip the parentheses around the literal
+    let mut node = literal_expr.syntax().clone();
+    let call_expr = loop {
+        let parent = node.parent()?;
+        if LuaCallArgList::can_cast(parent.kind().into()) {
+            break parent.parent().and_then(LuaCallExpr::cast)?;
+        } else if let Some(index_expr) = LuaIndexExpr::cast(parent.clone()) {
+            // the receiver of `("%d"):format(1)`
+            break index_expr.get_parent::<LuaCallExpr>()?;
+        } else if LuaParenExpr::can_cast(parent.kind().into()) {
+            node = parent;
+        } else {
+            return None;
+        }
+    };
+
+    let string_arg = find_string_call_arg(semantic_model, &call_expr)?;
+    if get_string_literal(&string_arg.string_expr)? != *string_token {
+        return None;
+    }
+
+    Some(string_arg.kind)
+}
+
+/// Returns the string literal token of the expression, the parentheses are skipped
+pub fn get_string_literal(expr: &LuaExpr) -> Option<LuaStringToken> {
+    match expr {
+        LuaExpr::LiteralExpr(literal) => match literal.get_literal()? {
+            LuaLiteralToken::String(string_token) => Some(string_token),
+            _ => None,
+        },
+        LuaExpr::ParenExpr(paren) => get_string_literal(&paren.get_expr()?),
+        _ => None,
+    }
+}
+
+/// Maps the byte range in the value of a string literal to the range in the source. Returns
+/// `None` when the literal contains escape sequences, so the offsets can not be mapped directly.
+pub fn get_string_value_range(
+    string_token: &LuaStringToken,
+    range: Range<usize>,
+) -> Option<TextRange> {
+    let text = string_token.get_text();
+    // `[==[` of the long string
+    let (prefix_len, suffix_len) = match text.strip_prefix('[') {
+        Some(rest) => {
+            let len = rest.find('[')? + 2;
+            (len, len)
+        }
+        None => (1, 1),
+    };
+    if text.len() < prefix_len + suffix_len {
+        return None;
+    }
+
+    let inner = &text[prefix_len..text.len() - suffix_len];
+    if inner != string_token.get_value() || range.end > inner.len() {
+        return None;
+    }
+
+    let start = string_token.get_position() + TextSize::from(prefix_len as u32);
+    Some(TextRange::new(
+        start + TextSize::from(range.start as u32),
+        start + TextSize::from(range.end as u32),
+    ))
+}
+
+fn is_true_literal(expr: Option<&LuaExpr>) -> bool {
+    if let Some(LuaExpr::LiteralExpr(literal)) = expr {
+        if let Some(LuaLiteralToken::Bool(bool)) = literal.get_literal() {
+            return bool.is_true();
+        }
+    }
+
+    false
+}
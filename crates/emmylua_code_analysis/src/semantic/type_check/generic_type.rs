@@ -16,6 +16,11 @@ pub fn check_generic_type_compact(
         return Ok(());
     }
 
+    // `thread<Yield, Resume, Return>` accepts the opaque thread
+    if source_generic.is_thread() && compact_type.is_thread() {
+        return Ok(());
+    }
+
     let source_base_id = source_generic.get_base_type_id();
     let type_decl = db
         .get_type_index()
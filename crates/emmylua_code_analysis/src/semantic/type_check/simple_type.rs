@@ -53,11 +53,11 @@ pub fn check_simple_type_compact(
                 return Ok(());
             }
         }
-        LuaType::Thread => {
-            if let LuaType::Thread = compact_type {
-                return Ok(());
-            }
-        }
+        LuaType::Thread => match compact_type {
+            LuaType::Thread => return Ok(()),
+            LuaType::Generic(generic) if generic.is_thread() => return Ok(()),
+            _ => {}
+        },
         LuaType::Boolean | LuaType::BooleanConst(_) => {
             if compact_type.is_boolean() {
                 return Ok(());
@@ -0,0 +1,116 @@
+use emmylua_parser::{LuaAstNode, LuaClosureExpr, LuaExpr};
+
+use crate::{LuaDeclExtra, LuaSignatureId, LuaType};
+
+use super::{infer::infer_param, SemanticModel};
+
+/// The number of the items which infer to a concrete type
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LuaCoverageCount {
+    pub typed: usize,
+    pub total: usize,
+}
+
+impl LuaCoverageCount {
+    fn add(&mut self, typ: &LuaType) {
+        self.total += 1;
+        if is_typed(typ) {
+            self.typed += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: &LuaCoverageCount) {
+        self.typed += other.typed;
+        self.total += other.total;
+    }
+
+    /// The percentage of the typed items, 100 when there is no item
+    pub fn get_percent(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+
+        self.typed as f64 * 100.0 / self.total as f64
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LuaTypeCoverage {
+    pub exprs: LuaCoverageCount,
+    pub locals: LuaCoverageCount,
+    pub params: LuaCoverageCount,
+    pub returns: LuaCoverageCount,
+}
+
+impl LuaTypeCoverage {
+    pub fn merge(&mut self, other: &LuaTypeCoverage) {
+        self.exprs.merge(&other.exprs);
+        self.locals.merge(&other.locals);
+        self.params.merge(&other.params);
+        self.returns.merge(&other.returns);
+    }
+
+    pub fn get_total(&self) -> LuaCoverageCount {
+        let mut total = self.exprs;
+        total.merge(&self.locals);
+        total.merge(&self.params);
+        total.merge(&self.returns);
+        total
+    }
+}
+
+/// Counts the name, index and call expressions, locals, parameters and returns of the file which
+/// infer to a concrete type rather than `unknown` or `any`
+pub fn collect_type_coverage(semantic_model: &SemanticModel) -> LuaTypeCoverage {
+    let mut coverage = LuaTypeCoverage::default();
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    let root = semantic_model.get_root();
+
+    for expr in root.descendants::<LuaExpr>() {
+        // the literals, the operators and the closures are always typed, they would hide the
+        // untyped names in the percentage
+        if !matches!(
+            expr,
+            LuaExpr::NameExpr(_) | LuaExpr::IndexExpr(_) | LuaExpr::CallExpr(_)
+        ) {
+            continue;
+        }
+
+        let typ = semantic_model.infer_expr(expr).unwrap_or(LuaType::Unknown);
+        coverage.exprs.add(&typ);
+    }
+
+    if let Some(decl_tree) = db.get_decl_index().get_decl_tree(&file_id) {
+        for decl in decl_tree.get_decls().values() {
+            match &decl.extra {
+                LuaDeclExtra::Local { .. } => {
+                    coverage
+                        .locals
+                        .add(&semantic_model.get_type(decl.get_id().into()));
+                }
+                LuaDeclExtra::Param { .. } => {
+                    let typ = infer_param(db, decl).unwrap_or(LuaType::Unknown);
+                    coverage.params.add(&typ);
+                }
+                LuaDeclExtra::Global { .. } => {}
+            }
+        }
+    }
+
+    for closure in root.descendants::<LuaClosureExpr>() {
+        let signature_id = LuaSignatureId::from_closure(file_id, &closure);
+        let Some(signature) = db.get_signature_index().get(&signature_id) else {
+            continue;
+        };
+        for typ in signature.get_return_types() {
+            coverage.returns.add(&typ);
+        }
+    }
+
+    coverage
+}
+
+fn is_typed(typ: &LuaType) -> bool {
+    !typ.is_unknown() && !typ.is_any()
+}
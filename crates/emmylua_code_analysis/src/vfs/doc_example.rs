@@ -0,0 +1,140 @@
+use emmylua_parser::{
+    LuaAstNode, LuaChunk, LuaDocDescriptionOwner, LuaDocTagExample, LuaExpr, LuaLocalName,
+    LuaParser, LuaStat, LuaTokenKind, ParserConfig,
+};
+
+use super::markdown::{is_closing_fence, is_lua_info, parse_opening_fence};
+
+#[derive(Debug, Clone, Copy)]
+struct ExampleLine<'a> {
+    line: usize,
+    // the column is counted in chars
+    col: usize,
+    text: &'a str,
+}
+
+/// Extracts the code of the `@example` and the `@usage` tags of a lua document, every example
+/// becomes a lua document of its own.
+///
+/// Only the code is kept, so the diagnostics keep the line and column numbers of the original
+/// document. The example with fenced code blocks uses the lua blocks and the blocks without a
+/// language, otherwise the whole example is the code.
+///
+/// When the document is the module `module_name`, its export is bound to the local name of the
+/// export on an empty line before the code, eg: `local M = require("lib")`, so the examples can
+/// use the module like its functions do.
+pub fn extract_doc_examples_lua(text: &str, module_name: Option<&str>) -> Vec<String> {
+    let tree = LuaParser::parse(text, ParserConfig::default());
+    let line_count = text.split('\n').count();
+    let prelude = module_name.and_then(|module_name| {
+        let export_name = get_export_name(&tree.get_chunk_node())?;
+        Some((
+            format!("local {} = require(\"{}\")", export_name, module_name),
+            export_name,
+        ))
+    });
+    let mut results = Vec::new();
+    for example in tree.get_chunk_node().descendants::<LuaDocTagExample>() {
+        let Some(description) = example.get_description() else {
+            continue;
+        };
+
+        let lines = description
+            .syntax()
+            .children_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|token| token.kind() == LuaTokenKind::TkDocDetail.into())
+            .map(|token| {
+                let offset: usize = token.text_range().start().into();
+                let line_start = text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+                ExampleLine {
+                    line: text[..offset].matches('\n').count(),
+                    col: text[line_start..offset].chars().count(),
+                    text: &text[token.text_range()],
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let code = extract_example_code(&lines);
+        if code.is_empty() {
+            continue;
+        }
+
+        let mut doc_lines = vec![String::new(); line_count];
+        for line in &code {
+            doc_lines[line.line] = format!("{}{}", " ".repeat(line.col), line.text);
+        }
+        // the example which declares the name itself does not need the module
+        if let Some((prelude, export_name)) = &prelude {
+            let prelude_line = (0..code[0].line)
+                .rev()
+                .find(|&idx| doc_lines[idx].is_empty());
+            if let Some(prelude_line) = prelude_line {
+                if !is_local_declared(&doc_lines.join("\n"), export_name) {
+                    doc_lines[prelude_line] = prelude.clone();
+                }
+            }
+        }
+        // the locals of the examples are only shown, they are not used
+        if let Some(empty_line) = doc_lines.iter_mut().find(|line| line.is_empty()) {
+            *empty_line = "---@diagnostic disable: unused".to_string();
+        }
+        results.push(doc_lines.join("\n"));
+    }
+
+    results
+}
+
+// the name of the local which is returned by the chunk, eg: `return M`
+fn get_export_name(chunk: &LuaChunk) -> Option<String> {
+    let LuaStat::ReturnStat(return_stat) = chunk.get_block()?.get_stats().last()? else {
+        return None;
+    };
+    let LuaExpr::NameExpr(name_expr) = return_stat.get_expr_list().next()? else {
+        return None;
+    };
+    name_expr.get_name_text()
+}
+
+fn is_local_declared(code: &str, name: &str) -> bool {
+    LuaParser::parse(code, ParserConfig::default())
+        .get_chunk_node()
+        .descendants::<LuaLocalName>()
+        .any(|local_name| {
+            local_name
+                .get_name_token()
+                .is_some_and(|token| token.get_name_text() == name)
+        })
+}
+
+fn extract_example_code<'a>(lines: &[ExampleLine<'a>]) -> Vec<ExampleLine<'a>> {
+    if !lines
+        .iter()
+        .any(|line| parse_opening_fence(line.text).is_some())
+    {
+        return lines.to_vec();
+    }
+
+    let mut code = Vec::new();
+    // the fence character, the fence length and whether the block is lua
+    let mut current_fence: Option<(char, usize, bool)> = None;
+    for line in lines {
+        match current_fence {
+            Some((fence_char, fence_len, is_lua)) => {
+                if is_closing_fence(line.text, fence_char, fence_len) {
+                    current_fence = None;
+                } else if is_lua {
+                    code.push(*line);
+                }
+            }
+            None => {
+                if let Some((fence_char, fence_len, info)) = parse_opening_fence(line.text) {
+                    let is_lua = info.is_empty() || is_lua_info(info);
+                    current_fence = Some((fence_char, fence_len, is_lua));
+                }
+            }
+        }
+    }
+
+    code
+}
@@ -0,0 +1,207 @@
+/// Rewrites the LDoc tags of the comments into the EmmyLua tags, eg: `-- @tparam string name`
+/// becomes `---@param name string`. The other lines are kept as they are.
+///
+/// The tags which only LDoc has are converted in every comment, the tags which EmmyLua has too,
+/// eg: `@param`, are only converted in the lines starting with two dashes.
+pub fn convert_ldoc_comments(text: &str) -> String {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim_end_matches(['\r', '\n']);
+            match convert_ldoc_line(content) {
+                Some(new_content) => format!("{}{}", new_content, &line[content.len()..]),
+                None => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct LDocTagLine<'a> {
+    indent: &'a str,
+    // `-- @tag` is an ldoc line, `--- @tag` may be an emmylua tag
+    is_ldoc_line: bool,
+    tag: &'a str,
+    // eg: `opt` of `@tparam[opt]`
+    modifier: &'a str,
+    content: &'a str,
+}
+
+fn parse_ldoc_tag_line(line: &str) -> Option<LDocTagLine<'_>> {
+    let text = line.trim_start();
+    let indent = &line[..line.len() - text.len()];
+    let dash_count = text.chars().take_while(|ch| *ch == '-').count();
+    if !(2..=3).contains(&dash_count) {
+        return None;
+    }
+
+    let after_dashes = &text[dash_count..];
+    let tag_start = after_dashes.trim_start();
+    // `---@tag` is an emmylua tag already
+    if dash_count == 3 && tag_start.len() == after_dashes.len() {
+        return None;
+    }
+
+    let tag_text = tag_start.strip_prefix('@')?;
+    let tag_len = tag_text
+        .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+        .unwrap_or(tag_text.len());
+    let tag = &tag_text[..tag_len];
+    let mut rest = &tag_text[tag_len..];
+    let mut modifier = "";
+    if let Some(modifier_text) = rest.strip_prefix('[') {
+        let end = modifier_text.find(']')?;
+        modifier = &modifier_text[..end];
+        rest = &modifier_text[end + 1..];
+    }
+
+    Some(LDocTagLine {
+        indent,
+        is_ldoc_line: dash_count == 2,
+        tag,
+        modifier,
+        content: rest.trim(),
+    })
+}
+
+fn convert_ldoc_line(line: &str) -> Option<String> {
+    let tag_line = parse_ldoc_tag_line(line)?;
+    let is_ldoc_line = tag_line.is_ldoc_line;
+    let is_opt = tag_line.modifier.starts_with("opt");
+    let tag = match tag_line.tag {
+        "tparam" | "tfield" => {
+            let (typ, rest) = split_ldoc_type(tag_line.content)?;
+            let (name, description) = split_first_word(rest);
+            if name.is_empty() {
+                return None;
+            }
+            let (typ, is_optional) = convert_ldoc_type(typ);
+            let tag_name = if tag_line.tag == "tparam" {
+                "param"
+            } else {
+                "field"
+            };
+            let optional = if is_opt || is_optional { "?" } else { "" };
+            format!("@{} {}{} {} {}", tag_name, name, optional, typ, description)
+        }
+        "param" | "field" if is_ldoc_line => {
+            let (name, description) = split_first_word(tag_line.content);
+            if name.is_empty() {
+                return None;
+            }
+            let optional = if is_opt { "?" } else { "" };
+            format!("@{} {}{} any {}", tag_line.tag, name, optional, description)
+        }
+        "treturn" => {
+            let (typ, description) = split_ldoc_type(tag_line.content)?;
+            let typ = match convert_ldoc_type(typ) {
+                (typ, true) if typ.contains('|') => format!("({})?", typ),
+                (typ, true) => format!("{}?", typ),
+                (typ, false) => typ,
+            };
+            format!("@return {}{}", typ, format_return_description(description))
+        }
+        "return" if is_ldoc_line => {
+            format!("@return any{}", format_return_description(tag_line.content))
+        }
+        // `---@module "name"` is an emmylua tag
+        "module" if !tag_line.content.starts_with(['"', '\'']) => {
+            format!("@class {}", tag_line.content)
+        }
+        "classmod" => format!("@class {}", tag_line.content),
+        "usage" | "see" if is_ldoc_line => format!("@{} {}", tag_line.tag, tag_line.content),
+        _ => return None,
+    };
+
+    Some(format!("{}---{}", tag_line.indent, tag.trim_end()))
+}
+
+fn format_return_description(description: &str) -> String {
+    if description.is_empty() {
+        String::new()
+    } else {
+        format!(" # {}", description)
+    }
+}
+
+fn split_first_word(text: &str) -> (&str, &str) {
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (text, ""),
+    }
+}
+
+// the type ends at the first whitespace outside of the braces, eg: `{string, ...}`
+fn split_ldoc_type(text: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+    for (idx, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ch if ch.is_whitespace() && depth == 0 => {
+                return Some((&text[..idx], text[idx..].trim_start()));
+            }
+            _ => {}
+        }
+    }
+
+    if text.is_empty() {
+        None
+    } else {
+        Some((text, ""))
+    }
+}
+
+// `?string|number` is the optional union
+fn convert_ldoc_type(typ: &str) -> (String, bool) {
+    let (typ, is_optional) = match typ.strip_prefix('?') {
+        Some(typ) => (typ, true),
+        None => (typ, false),
+    };
+
+    let mut types = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (idx, ch) in typ.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            '|' if depth == 0 => {
+                types.push(convert_ldoc_type_name(&typ[start..idx]));
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    types.push(convert_ldoc_type_name(&typ[start..]));
+
+    (types.join("|"), is_optional)
+}
+
+fn convert_ldoc_type_name(name: &str) -> String {
+    let name = name.trim();
+    match name {
+        "int" => "integer".to_string(),
+        "bool" => "boolean".to_string(),
+        "func" => "function".to_string(),
+        "tab" => "table".to_string(),
+        _ => match name
+            .strip_prefix('{')
+            .and_then(|name| name.strip_suffix('}'))
+        {
+            // `{string,...}` is the array of strings
+            Some(inner) => match inner.trim_end().strip_suffix("...") {
+                Some(element) => {
+                    let element = element.trim_end().trim_end_matches(',').trim();
+                    let (element, _) = convert_ldoc_type(element);
+                    if element.contains('|') {
+                        format!("({})[]", element)
+                    } else {
+                        format!("{}[]", element)
+                    }
+                }
+                None => "table".to_string(),
+            },
+            None => name.to_string(),
+        },
+    }
+}
@@ -0,0 +1,83 @@
+/// Extracts the fenced lua code blocks of a markdown document, every block becomes a lua document
+/// of its own.
+///
+/// The other lines of the markdown document are blanked, so the diagnostics keep the line and
+/// column numbers of the markdown document.
+pub fn extract_markdown_lua_blocks(text: &str) -> Vec<String> {
+    let lines = text.split('\n').collect::<Vec<_>>();
+    let mut blocks = Vec::new();
+    // the fence character, the fence length and the first line of the lua block
+    let mut current_fence: Option<(char, usize, Option<usize>)> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        match current_fence {
+            Some((fence_char, fence_len, lua_start)) => {
+                if is_closing_fence(line, fence_char, fence_len) {
+                    current_fence = None;
+                    if let Some(lua_start) = lua_start {
+                        blocks.push(make_block(&lines, lua_start, idx));
+                    }
+                }
+            }
+            None => {
+                if let Some((fence_char, fence_len, info)) = parse_opening_fence(line) {
+                    let lua_start = is_lua_info(info).then_some(idx + 1);
+                    current_fence = Some((fence_char, fence_len, lua_start));
+                }
+            }
+        }
+    }
+
+    // the unclosed block ends with the document
+    if let Some((_, _, Some(lua_start))) = current_fence {
+        blocks.push(make_block(&lines, lua_start, lines.len()));
+    }
+    blocks
+}
+
+fn make_block(lines: &[&str], start: usize, end: usize) -> String {
+    let mut block = "\n".repeat(start);
+    block.push_str(&lines[start..end].join("\n"));
+    block
+}
+
+// eg: "```lua" or "~~~~ lua title"
+pub(super) fn parse_opening_fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+
+    let fence_char = trimmed.chars().next()?;
+    if fence_char != '`' && fence_char != '~' {
+        return None;
+    }
+
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+
+    let info = trimmed[fence_len..].trim();
+    // the info string of a backtick fence can not contain backticks
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+
+    Some((fence_char, fence_len, info))
+}
+
+pub(super) fn is_closing_fence(line: &str, fence_char: char, fence_len: usize) -> bool {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return false;
+    }
+
+    let len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    len >= fence_len && trimmed[len..].trim().is_empty()
+}
+
+pub(super) fn is_lua_info(info: &str) -> bool {
+    info.split(|c: char| c.is_whitespace() || c == '{' || c == ',')
+        .next()
+        .is_some_and(|lang| lang.eq_ignore_ascii_case("lua"))
+}
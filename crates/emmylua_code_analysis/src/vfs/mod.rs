@@ -1,17 +1,23 @@
+mod doc_example;
 mod document;
 mod file_id;
 mod file_uri_handler;
+mod ldoc_convert;
 mod loader;
+mod markdown;
 mod virtual_url;
 
+pub use doc_example::extract_doc_examples_lua;
 pub use document::LuaDocument;
 use emmylua_parser::{LineIndex, LuaParser, LuaSyntaxTree};
 pub use file_id::{FileId, InFiled};
 pub use file_uri_handler::{file_path_to_uri, uri_to_file_path};
+pub use ldoc_convert::convert_ldoc_comments;
 pub use loader::{load_workspace_files, read_file_with_encoding, LuaFileInfo};
 use lsp_types::Uri;
+pub use markdown::extract_markdown_lua_blocks;
 use rowan::{NodeCache, TextRange};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 pub use virtual_url::VirtualUrlGenerator;
@@ -25,6 +31,7 @@ pub struct Vfs {
     file_data: Vec<Option<String>>,
     line_index_map: HashMap<FileId, LineIndex>,
     tree_map: HashMap<FileId, LuaSyntaxTree>,
+    isolated_file_ids: HashSet<FileId>,
     emmyrc: Option<Arc<Emmyrc>>,
     node_cache: NodeCache,
 }
@@ -37,6 +44,7 @@ impl Vfs {
             file_data: Vec::new(),
             line_index_map: HashMap::new(),
             tree_map: HashMap::new(),
+            isolated_file_ids: HashSet::new(),
             emmyrc: None,
             node_cache: NodeCache::default(),
         }
@@ -86,11 +94,30 @@ impl Vfs {
         } else {
             self.line_index_map.remove(&fid);
             self.tree_map.remove(&fid);
+            self.isolated_file_ids.remove(&fid);
         }
         self.file_data[fid.id as usize] = data;
         fid
     }
 
+    /// An isolated document does not define globals for the other files and it is not a module
+    /// which can be required
+    pub fn set_isolated(&mut self, id: FileId, isolated: bool) {
+        if isolated {
+            self.isolated_file_ids.insert(id);
+        } else {
+            self.isolated_file_ids.remove(&id);
+        }
+    }
+
+    pub fn is_isolated(&self, id: &FileId) -> bool {
+        self.isolated_file_ids.contains(id)
+    }
+
+    pub fn get_isolated_file_ids(&self) -> Vec<FileId> {
+        self.isolated_file_ids.iter().copied().collect()
+    }
+
     pub fn update_config(&mut self, emmyrc: Arc<Emmyrc>) {
         self.emmyrc = Some(emmyrc);
     }
@@ -2,7 +2,10 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, Ident};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Expr, Fields, Ident, Lit,
+    Meta,
+};
 
 // Convert enum variant names to kebab-case
 fn to_kebab_case(ident: &Ident) -> String {
@@ -19,6 +22,24 @@ fn to_kebab_case(ident: &Ident) -> String {
     result
 }
 
+// Join the doc comments of the enum variant
+fn get_doc_string(attrs: &[Attribute]) -> String {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if let Meta::NameValue(name_value) = &attr.meta {
+            if !name_value.path.is_ident("doc") {
+                continue;
+            }
+            if let Expr::Lit(expr_lit) = &name_value.value {
+                if let Lit::Str(lit_str) = &expr_lit.lit {
+                    lines.push(lit_str.value().trim().to_string());
+                }
+            }
+        }
+    }
+    lines.join(" ")
+}
+
 #[proc_macro_derive(LuaDiagnosticMacro)]
 pub fn lua_diagnostic_macro(input: TokenStream) -> TokenStream {
     // Parse the input TokenStream into DeriveInput
@@ -35,9 +56,10 @@ pub fn lua_diagnostic_macro(input: TokenStream) -> TokenStream {
         }
     };
 
-    // Generate get_name / FromStr / Display / all based on variants
+    // Generate get_name / get_description / FromStr / Display / all based on variants
     let mut variant_idents = Vec::new();
     let mut variant_strings = Vec::new();
+    let mut variant_docs = Vec::new();
 
     for variant in variants.iter() {
         let variant_ident = &variant.ident;
@@ -46,6 +68,7 @@ pub fn lua_diagnostic_macro(input: TokenStream) -> TokenStream {
             let kebab_case_string = to_kebab_case(variant_ident);
             variant_idents.push(variant_ident);
             variant_strings.push(kebab_case_string);
+            variant_docs.push(get_doc_string(&variant.attrs));
         } else {
             // Only unit variants are supported
             return syn::Error::new(variant.ident.span(), "Only unit variants supported")
@@ -64,6 +87,17 @@ pub fn lua_diagnostic_macro(input: TokenStream) -> TokenStream {
             }
         });
 
+    // Build match arms for get_description()
+    let get_description_arms =
+        variant_idents
+            .iter()
+            .zip(variant_docs.iter())
+            .map(|(ident, doc)| {
+                quote! {
+                    #name::#ident => #doc
+                }
+            });
+
     // Build match arms for FromStr
     let from_str_arms = variant_idents
         .iter()
@@ -89,6 +123,14 @@ pub fn lua_diagnostic_macro(input: TokenStream) -> TokenStream {
                 }
             }
 
+            // Return the doc comment of the variant
+            pub fn get_description(&self) -> &str {
+                match self {
+                    #(#get_description_arms),*,
+                    _ => ""
+                }
+            }
+
             // Return all variants
             pub fn all() -> Vec<#name> {
                 vec![
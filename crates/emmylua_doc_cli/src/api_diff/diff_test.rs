@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use emmylua_code_analysis::EmmyLuaAnalysis;
+
+    use crate::api_diff::{ApiDiffReport, ChangeLevel};
+
+    // the files are only in the vfs, the root is never read
+    fn load_version(name: &str, code: &str) -> (EmmyLuaAnalysis, PathBuf) {
+        let root = std::env::temp_dir().join("emmylua_api_diff").join(name);
+        let mut analysis = EmmyLuaAnalysis::new();
+        analysis.init_std_lib(None);
+        analysis.add_main_workspace(root.clone());
+        analysis.update_file_by_path(&root.join("lib.lua"), Some(code.to_string()));
+        (analysis, root)
+    }
+
+    fn get_changes(old_code: &str, new_code: &str) -> Vec<(ChangeLevel, String)> {
+        let (old_analysis, old_root) = load_version("old", old_code);
+        let (new_analysis, new_root) = load_version("new", new_code);
+        let report = ApiDiffReport::collect(&old_analysis, &old_root, &new_analysis, &new_root);
+        report
+            .changes
+            .iter()
+            .map(|change| (change.level, change.message.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_narrowed_types() {
+        let changes = get_changes(
+            r#"
+            local M = {}
+            ---@param x number|string
+            ---@return number|string
+            function M.f(x) end
+            return M
+            "#,
+            r#"
+            local M = {}
+            ---@param x number
+            ---@return number
+            function M.f(x) end
+            return M
+            "#,
+        );
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    ChangeLevel::Major,
+                    "the param `x` changed from `number|string` to `number`".to_string()
+                ),
+                (
+                    ChangeLevel::Minor,
+                    "the return 1 changed from `number|string` to `number`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_widened_types() {
+        let changes = get_changes(
+            r#"
+            local M = {}
+            ---@param x integer
+            ---@return string
+            function M.f(x) end
+            return M
+            "#,
+            r#"
+            local M = {}
+            ---@param x integer|string
+            ---@return string?
+            function M.f(x) end
+            return M
+            "#,
+        );
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    ChangeLevel::Minor,
+                    "the param `x` changed from `integer` to `integer|string`".to_string()
+                ),
+                (
+                    ChangeLevel::Major,
+                    "the return 1 changed from `string` to `string?`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_incompatible_types() {
+        let changes = get_changes(
+            r#"
+            local M = {}
+            ---@param x string
+            ---@return boolean
+            function M.f(x) end
+            return M
+            "#,
+            r#"
+            local M = {}
+            ---@param x boolean
+            ---@return string
+            function M.f(x) end
+            return M
+            "#,
+        );
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    ChangeLevel::Major,
+                    "the param `x` changed from `string` to `boolean`".to_string()
+                ),
+                (
+                    ChangeLevel::Major,
+                    "the return 1 changed from `boolean` to `string`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classes_are_resolved_by_name() {
+        let changes = get_changes(
+            r#"
+            ---@class Point
+            ---@field x number
+
+            ---@class Old
+
+            local M = {}
+            ---@param p Point?
+            ---@return Old
+            function M.f(p) end
+            return M
+            "#,
+            r#"
+            ---@class Point
+            ---@field x number
+
+            local M = {}
+            ---@param p Point
+            ---@return Point
+            function M.f(p) end
+            return M
+            "#,
+        );
+        // the class of the old version is found in the new one, the class which is gone is not
+        assert!(changes.contains(&(
+            ChangeLevel::Major,
+            "the param `p` changed from `Point?` to `Point`".to_string()
+        )));
+        assert!(changes.contains(&(
+            ChangeLevel::Major,
+            "the return 1 changed from `Old` to `Point`".to_string()
+        )));
+    }
+}
@@ -0,0 +1,198 @@
+use emmylua_code_analysis::{
+    DbIndex, FileId, LuaDeclId, LuaMember, LuaMemberOwner, LuaSemanticDeclId, LuaType,
+    LuaTypeDeclId, ModuleInfo,
+};
+
+use crate::api_model::{get_member_key_name, get_table_member_owner};
+
+/// The path of an api item, it is resolved again in the db of each version
+#[derive(Debug, Clone)]
+pub enum ApiPath {
+    Type(String),
+    TypeMember(String, String),
+    Module(String),
+    ModuleMember(String, String),
+    Global(String),
+    GlobalMember(String, String),
+}
+
+/// The type of the item, the alias is resolved to its origin
+pub fn find_lua_type(db: &DbIndex, path: &ApiPath) -> Option<LuaType> {
+    match path {
+        ApiPath::Type(name) => db
+            .get_type_index()
+            .get_type_decl(&LuaTypeDeclId::new(name))?
+            .get_alias_origin(db, None),
+        ApiPath::TypeMember(name, member_name) => find_member_type(
+            db,
+            LuaMemberOwner::Type(LuaTypeDeclId::new(name)),
+            member_name,
+        ),
+        ApiPath::Module(name) => find_module_export(db, name),
+        ApiPath::ModuleMember(name, member_name) => {
+            let owner = get_table_member_owner(&find_module_export(db, name)?)?;
+            find_member_type(db, owner, member_name)
+        }
+        ApiPath::Global(name) => find_global_type(db, name),
+        ApiPath::GlobalMember(name, member_name) => {
+            let owner = get_table_member_owner(&find_global_type(db, name)?)?;
+            find_member_type(db, owner, member_name)
+        }
+    }
+}
+
+/// The owner of the property of the item and the file of the owner
+pub fn find_semantic_decl(db: &DbIndex, path: &ApiPath) -> Option<(FileId, LuaSemanticDeclId)> {
+    match path {
+        ApiPath::Type(name) => {
+            let type_id = LuaTypeDeclId::new(name);
+            let file_id = db
+                .get_type_index()
+                .get_type_decl(&type_id)?
+                .get_locations()
+                .first()?
+                .file_id;
+            Some((file_id, LuaSemanticDeclId::TypeDecl(type_id)))
+        }
+        ApiPath::TypeMember(name, member_name) => find_member(
+            db,
+            LuaMemberOwner::Type(LuaTypeDeclId::new(name)),
+            member_name,
+        )
+        .map(get_member_semantic_decl),
+        ApiPath::Module(name) => {
+            let module = find_module(db, name)?;
+            Some((module.file_id, module.property_owner_id.clone()?))
+        }
+        ApiPath::ModuleMember(name, member_name) => {
+            let owner = get_table_member_owner(&find_module_export(db, name)?)?;
+            find_member(db, owner, member_name).map(get_member_semantic_decl)
+        }
+        ApiPath::Global(name) => {
+            let decl_id = find_global_decl_id(db, name)?;
+            Some((decl_id.file_id, LuaSemanticDeclId::LuaDecl(decl_id)))
+        }
+        ApiPath::GlobalMember(name, member_name) => {
+            let owner = get_table_member_owner(&find_global_type(db, name)?)?;
+            find_member(db, owner, member_name).map(get_member_semantic_decl)
+        }
+    }
+}
+
+fn get_member_semantic_decl(member: &LuaMember) -> (FileId, LuaSemanticDeclId) {
+    (
+        member.get_file_id(),
+        LuaSemanticDeclId::Member(member.get_id()),
+    )
+}
+
+fn find_member<'a>(
+    db: &'a DbIndex,
+    owner: LuaMemberOwner,
+    member_name: &str,
+) -> Option<&'a LuaMember> {
+    db.get_member_index()
+        .get_members(&owner)?
+        .into_iter()
+        .find(|member| get_member_key_name(member.get_key()) == member_name)
+}
+
+fn find_member_type(db: &DbIndex, owner: LuaMemberOwner, member_name: &str) -> Option<LuaType> {
+    let member = find_member(db, owner, member_name)?;
+    db.get_type_index()
+        .get_type_cache(&member.get_id().into())
+        .map(|cache| cache.as_type().clone())
+}
+
+fn find_module<'a>(db: &'a DbIndex, name: &str) -> Option<&'a ModuleInfo> {
+    db.get_module_index()
+        .get_module_infos()
+        .into_iter()
+        .find(|module| module.workspace_id.is_main() && module.full_module_name == name)
+}
+
+fn find_module_export(db: &DbIndex, name: &str) -> Option<LuaType> {
+    find_module(db, name)?.export_type.clone()
+}
+
+fn find_global_decl_id(db: &DbIndex, name: &str) -> Option<LuaDeclId> {
+    db.get_global_index()
+        .get_global_decl_ids(name)?
+        .iter()
+        .find(|decl_id| {
+            db.get_module_index()
+                .get_module(decl_id.file_id)
+                .is_some_and(|module| module.workspace_id.is_main())
+        })
+        .copied()
+}
+
+fn find_global_type(db: &DbIndex, name: &str) -> Option<LuaType> {
+    let decl_id = find_global_decl_id(db, name)?;
+    db.get_type_index()
+        .get_type_cache(&decl_id.into())
+        .map(|cache| cache.as_type().clone())
+}
+
+/// The params and the returns of a function which are compared between the versions
+#[derive(Debug)]
+pub struct FuncShape {
+    pub is_method: bool,
+    pub params: Vec<ParamShape>,
+    pub returns: Vec<LuaType>,
+}
+
+#[derive(Debug)]
+pub struct ParamShape {
+    pub name: String,
+    pub typ: Option<LuaType>,
+    pub optional: bool,
+}
+
+pub fn get_func_shape(db: &DbIndex, typ: &LuaType) -> Option<FuncShape> {
+    match typ {
+        LuaType::Signature(signature_id) => {
+            let signature = db.get_signature_index().get(signature_id)?;
+            let params = signature
+                .get_type_params()
+                .into_iter()
+                .enumerate()
+                .map(|(idx, (name, typ))| {
+                    let nullable = signature
+                        .get_param_info_by_id(idx)
+                        .is_some_and(|info| info.nullable);
+                    ParamShape {
+                        optional: nullable || is_optional_param(&name, typ.as_ref()),
+                        name,
+                        typ,
+                    }
+                })
+                .collect();
+            Some(FuncShape {
+                is_method: signature.is_colon_define,
+                params,
+                returns: signature.get_return_types(),
+            })
+        }
+        LuaType::DocFunction(func) => Some(FuncShape {
+            is_method: func.is_colon_define(),
+            params: func
+                .get_params()
+                .iter()
+                .filter(|(name, _)| name != "self")
+                .map(|(name, typ)| ParamShape {
+                    name: name.clone(),
+                    typ: typ.clone(),
+                    optional: is_optional_param(name, typ.as_ref()),
+                })
+                .collect(),
+            returns: func.get_ret().to_vec(),
+        }),
+        _ => None,
+    }
+}
+
+// the params without the type accept anything, so they can be omitted too
+fn is_optional_param(name: &str, typ: Option<&LuaType>) -> bool {
+    name == "..." || typ.is_none_or(|typ| typ.is_optional())
+}
@@ -0,0 +1,791 @@
+mod diff_test;
+mod lookup;
+mod resolve;
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+};
+
+use emmylua_code_analysis::{check_type_compact, DbIndex, EmmyLuaAnalysis, LuaType};
+use lookup::{find_lua_type, get_func_shape, FuncShape};
+pub use lookup::{find_semantic_decl, ApiPath};
+use resolve::resolve_old_type;
+use serde::Serialize;
+
+use crate::{
+    api_model::{
+        collect_api_model, to_api_type, ApiLocation, ApiMember, ApiModel, ApiProperty, ApiType,
+        ApiVisibility,
+    },
+    stub_generator::render_type,
+};
+
+/// The semver level of a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeLevel {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl ChangeLevel {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ChangeLevel::Patch => "patch",
+            ChangeLevel::Minor => "minor",
+            ChangeLevel::Major => "major",
+        }
+    }
+}
+
+/// What happened to the item, the versioned docs group the changes by it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiChangeKind {
+    Added,
+    Deprecated,
+    Changed,
+    Removed,
+}
+
+impl ApiChangeKind {
+    fn get_title(&self) -> &'static str {
+        match self {
+            ApiChangeKind::Added => "Added",
+            ApiChangeKind::Deprecated => "Deprecated",
+            ApiChangeKind::Changed => "Changed",
+            ApiChangeKind::Removed => "Removed",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ApiChange {
+    level: ChangeLevel,
+    kind: ApiChangeKind,
+    location: Option<ApiLocation>,
+    name: String,
+    message: String,
+}
+
+/// The changes of one kind for the changes page of the versioned docs
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiChangeGroup {
+    pub title: String,
+    pub changes: Vec<ApiChangeEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiChangeEntry {
+    pub name: String,
+    pub message: String,
+    pub breaking: bool,
+}
+
+/// The changes of the public api between the old and the new version of a workspace
+#[derive(Debug)]
+pub struct ApiDiffReport {
+    changes: Vec<ApiChange>,
+    // the items which are new in the new version, the renamed items too
+    added: Vec<(String, ApiPath)>,
+}
+
+impl ApiDiffReport {
+    pub fn collect(
+        old_analysis: &EmmyLuaAnalysis,
+        old_input: &Path,
+        new_analysis: &EmmyLuaAnalysis,
+        new_input: &Path,
+    ) -> Self {
+        let old = ApiVersion::new(old_analysis.compilation.get_db(), old_input);
+        let new = ApiVersion::new(new_analysis.compilation.get_db(), new_input);
+        let mut differ = ApiDiffer {
+            old: &old,
+            new: &new,
+            changes: Vec::new(),
+            added: Vec::new(),
+        };
+        differ.diff();
+
+        Self {
+            changes: differ.changes,
+            added: differ.added,
+        }
+    }
+
+    pub fn get_added(&self) -> &[(String, ApiPath)] {
+        &self.added
+    }
+
+    /// The names of the items which are no longer the api, the renamed items too
+    pub fn get_removed_names(&self) -> Vec<&str> {
+        self.changes
+            .iter()
+            .filter(|change| change.kind == ApiChangeKind::Removed)
+            .map(|change| change.name.as_str())
+            .collect()
+    }
+
+    pub fn get_change_groups(&self) -> Vec<ApiChangeGroup> {
+        let mut groups = Vec::new();
+        for kind in [
+            ApiChangeKind::Added,
+            ApiChangeKind::Deprecated,
+            ApiChangeKind::Changed,
+            ApiChangeKind::Removed,
+        ] {
+            let changes = self
+                .changes
+                .iter()
+                .filter(|change| change.kind == kind)
+                .map(|change| ApiChangeEntry {
+                    name: change.name.clone(),
+                    // the message which only repeats the title is not shown
+                    message: if change.message == kind.get_title().to_lowercase() {
+                        String::new()
+                    } else {
+                        change.message.clone()
+                    },
+                    breaking: change.level == ChangeLevel::Major,
+                })
+                .collect::<Vec<_>>();
+            if !changes.is_empty() {
+                groups.push(ApiChangeGroup {
+                    title: kind.get_title().to_string(),
+                    changes,
+                });
+            }
+        }
+
+        groups
+    }
+
+    /// The changes without the api effect are patches
+    pub fn get_level(&self) -> ChangeLevel {
+        self.changes
+            .iter()
+            .map(|change| change.level)
+            .max()
+            .unwrap_or(ChangeLevel::Patch)
+    }
+
+    pub fn print(&self) {
+        let mut lines = Vec::new();
+        for (title, level) in [
+            ("Breaking changes:", ChangeLevel::Major),
+            ("Compatible changes:", ChangeLevel::Minor),
+        ] {
+            let changes = self
+                .changes
+                .iter()
+                .filter(|change| change.level == level)
+                .collect::<Vec<_>>();
+            if changes.is_empty() {
+                continue;
+            }
+
+            lines.push(title.to_string());
+            for change in changes {
+                let location = match &change.location {
+                    Some(location) => {
+                        format!("{}:{}:{}: ", location.file, location.line, location.col)
+                    }
+                    None => String::new(),
+                };
+                lines.push(format!("  {}{}: {}", location, change.name, change.message));
+            }
+            lines.push(String::new());
+        }
+
+        if self.changes.is_empty() {
+            lines.push("No api changes".to_string());
+        }
+        lines.push(format!("Semver: {}", self.get_level().as_str()));
+        println!("{}", lines.join("\n"));
+    }
+}
+
+#[derive(Debug)]
+struct ApiEntry {
+    kind: &'static str,
+    // the parent of the member, the renamed items are searched in the same parent
+    owner: String,
+    path: ApiPath,
+    property: ApiProperty,
+    location: Option<ApiLocation>,
+    // the items with the same signature are treated as renamed
+    signature: String,
+    supers: Vec<String>,
+}
+
+impl ApiEntry {
+    fn is_api(&self) -> bool {
+        matches!(
+            self.property.visibility,
+            ApiVisibility::Public | ApiVisibility::Protected
+        )
+    }
+}
+
+struct ApiVersion<'a> {
+    db: &'a DbIndex,
+    entries: BTreeMap<String, ApiEntry>,
+}
+
+impl<'a> ApiVersion<'a> {
+    fn new(db: &'a DbIndex, input: &Path) -> Self {
+        let model = collect_api_model(db, input);
+        Self {
+            db,
+            entries: collect_entries(&model),
+        }
+    }
+
+    fn get_type(&self, entry: &ApiEntry) -> Option<LuaType> {
+        find_lua_type(self.db, &entry.path)
+    }
+}
+
+fn collect_entries(model: &ApiModel) -> BTreeMap<String, ApiEntry> {
+    let mut entries = BTreeMap::new();
+    for class in &model.classes {
+        let supers = class.supers.iter().map(render_type).collect();
+        add_type_entry(
+            &mut entries,
+            "class",
+            &class.full_name,
+            &class.property,
+            class.locations.first(),
+            get_members_signature(&class.members),
+            supers,
+        );
+        add_member_entries(&mut entries, &class.full_name, &class.members, |name| {
+            ApiPath::TypeMember(class.full_name.clone(), name.to_string())
+        });
+    }
+
+    for e in &model.enums {
+        add_type_entry(
+            &mut entries,
+            "enum",
+            &e.full_name,
+            &e.property,
+            e.locations.first(),
+            get_members_signature(&e.members),
+            Vec::new(),
+        );
+        add_member_entries(&mut entries, &e.full_name, &e.members, |name| {
+            ApiPath::TypeMember(e.full_name.clone(), name.to_string())
+        });
+    }
+
+    for alias in &model.aliases {
+        add_type_entry(
+            &mut entries,
+            "alias",
+            &alias.full_name,
+            &alias.property,
+            alias.locations.first(),
+            alias.origin.as_ref().map(render_type).unwrap_or_default(),
+            Vec::new(),
+        );
+    }
+
+    // only the exports are the api of the module
+    for module in &model.modules {
+        let Some(export) = &module.export else {
+            continue;
+        };
+
+        let module_name = format!("require(\"{}\")", module.name);
+        entries.insert(
+            module_name.clone(),
+            ApiEntry {
+                kind: "module",
+                owner: String::new(),
+                path: ApiPath::Module(module.name.clone()),
+                property: module.property.clone(),
+                location: Some(ApiLocation {
+                    file: module.file.clone(),
+                    line: 1,
+                    col: 1,
+                }),
+                signature: render_type(export),
+                supers: Vec::new(),
+            },
+        );
+        add_member_entries(&mut entries, &module_name, &module.members, |name| {
+            ApiPath::ModuleMember(module.name.clone(), name.to_string())
+        });
+    }
+
+    for global in &model.globals {
+        // the global table of a type is compared as the type
+        if let ApiType::Ref { name } = &global.typ {
+            if entries.contains_key(name) {
+                continue;
+            }
+        }
+
+        entries.insert(
+            global.name.clone(),
+            ApiEntry {
+                kind: "global",
+                owner: String::new(),
+                path: ApiPath::Global(global.name.clone()),
+                property: global.property.clone(),
+                location: Some(global.location.clone()),
+                signature: render_type(&global.typ),
+                supers: Vec::new(),
+            },
+        );
+        add_member_entries(&mut entries, &global.name, &global.members, |name| {
+            ApiPath::GlobalMember(global.name.clone(), name.to_string())
+        });
+    }
+
+    entries
+}
+
+fn add_type_entry(
+    entries: &mut BTreeMap<String, ApiEntry>,
+    kind: &'static str,
+    full_name: &str,
+    property: &ApiProperty,
+    location: Option<&ApiLocation>,
+    signature: String,
+    supers: Vec<String>,
+) {
+    entries.insert(
+        full_name.to_string(),
+        ApiEntry {
+            kind,
+            owner: String::new(),
+            path: ApiPath::Type(full_name.to_string()),
+            property: property.clone(),
+            location: location.cloned(),
+            signature,
+            supers,
+        },
+    );
+}
+
+fn add_member_entries(
+    entries: &mut BTreeMap<String, ApiEntry>,
+    owner: &str,
+    members: &[ApiMember],
+    to_path: impl Fn(&str) -> ApiPath,
+) {
+    for member in members {
+        let kind = match &member.typ {
+            ApiType::Function(_) => "function",
+            _ => "field",
+        };
+        entries.insert(
+            format!("{}.{}", owner, member.name),
+            ApiEntry {
+                kind,
+                owner: owner.to_string(),
+                path: to_path(&member.name),
+                property: member.property.clone(),
+                location: Some(member.location.clone()),
+                signature: render_type(&member.typ),
+                supers: Vec::new(),
+            },
+        );
+    }
+}
+
+// the renamed class keeps its members
+fn get_members_signature(members: &[ApiMember]) -> String {
+    members
+        .iter()
+        .map(|member| format!("{}: {}", member.name, render_type(&member.typ)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypeChange {
+    Same,
+    Narrowed,
+    Widened,
+    // both versions accept each other, eg: `any`
+    Compatible,
+    Incompatible,
+}
+
+struct ApiDiffer<'a> {
+    old: &'a ApiVersion<'a>,
+    new: &'a ApiVersion<'a>,
+    changes: Vec<ApiChange>,
+    added: Vec<(String, ApiPath)>,
+}
+
+impl ApiDiffer<'_> {
+    fn diff(&mut self) {
+        let added = self
+            .new
+            .entries
+            .iter()
+            .filter(|(name, entry)| {
+                entry.is_api()
+                    && !self
+                        .old
+                        .entries
+                        .get(*name)
+                        .is_some_and(|old_entry| old_entry.is_api())
+            })
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        let mut renamed = HashSet::new();
+
+        for (name, old_entry) in &self.old.entries {
+            if !old_entry.is_api() {
+                continue;
+            }
+
+            match self.new.entries.get(name) {
+                Some(new_entry) if new_entry.is_api() => {
+                    self.diff_entry(name, old_entry, new_entry)
+                }
+                Some(new_entry) => self.add_kind_change(
+                    ApiChangeKind::Removed,
+                    ChangeLevel::Major,
+                    new_entry.location.as_ref(),
+                    name,
+                    "no longer public".to_string(),
+                ),
+                None => {
+                    let new_name = added.iter().find(|new_name| {
+                        let new_entry = &self.new.entries[**new_name];
+                        !renamed.contains(*new_name)
+                            && !old_entry.signature.is_empty()
+                            && new_entry.kind == old_entry.kind
+                            && new_entry.owner == old_entry.owner
+                            && new_entry.signature == old_entry.signature
+                    });
+                    match new_name {
+                        Some(new_name) => {
+                            renamed.insert(*new_name);
+                            self.add_kind_change(
+                                ApiChangeKind::Removed,
+                                ChangeLevel::Major,
+                                self.new.entries[*new_name].location.as_ref(),
+                                name,
+                                format!("renamed to {}", new_name),
+                            );
+                        }
+                        // the location is in the old version
+                        None => self.add_kind_change(
+                            ApiChangeKind::Removed,
+                            ChangeLevel::Major,
+                            old_entry.location.as_ref(),
+                            name,
+                            "removed".to_string(),
+                        ),
+                    }
+                }
+            }
+        }
+
+        for name in added {
+            let entry = &self.new.entries[name];
+            self.added.push((name.to_string(), entry.path.clone()));
+            if renamed.contains(name) {
+                continue;
+            }
+
+            self.add_kind_change(
+                ApiChangeKind::Added,
+                ChangeLevel::Minor,
+                entry.location.as_ref(),
+                name,
+                "added".to_string(),
+            );
+        }
+    }
+
+    fn diff_entry(&mut self, name: &str, old_entry: &ApiEntry, new_entry: &ApiEntry) {
+        let location = new_entry.location.as_ref();
+        if old_entry.property.deprecated.is_none() && new_entry.property.deprecated.is_some() {
+            self.add_kind_change(
+                ApiChangeKind::Deprecated,
+                ChangeLevel::Minor,
+                location,
+                name,
+                "deprecated".to_string(),
+            );
+        }
+
+        for super_name in &old_entry.supers {
+            if !new_entry.supers.contains(super_name) {
+                self.add_change(
+                    ChangeLevel::Major,
+                    location,
+                    name,
+                    format!("no longer extends {}", super_name),
+                );
+            }
+        }
+
+        if old_entry.kind != new_entry.kind {
+            self.add_change(
+                ChangeLevel::Major,
+                location,
+                name,
+                format!("changed from {} to {}", old_entry.kind, new_entry.kind),
+            );
+            return;
+        }
+
+        match old_entry.kind {
+            "class" | "enum" => {}
+            // the alias is used by the params and the returns
+            "alias" => {
+                let old_type = self.old.get_type(old_entry).unwrap_or(LuaType::Unknown);
+                let new_type = self.new.get_type(new_entry).unwrap_or(LuaType::Unknown);
+                match self.compare_types(&old_type, &new_type) {
+                    TypeChange::Same => {}
+                    TypeChange::Compatible => self.add_change(
+                        ChangeLevel::Minor,
+                        location,
+                        name,
+                        self.get_type_change_message("type", &old_type, &new_type),
+                    ),
+                    _ => self.add_change(
+                        ChangeLevel::Major,
+                        location,
+                        name,
+                        self.get_type_change_message("type", &old_type, &new_type),
+                    ),
+                }
+            }
+            _ => {
+                let old_type = self.old.get_type(old_entry).unwrap_or(LuaType::Unknown);
+                let new_type = self.new.get_type(new_entry).unwrap_or(LuaType::Unknown);
+                let old_func = get_func_shape(self.old.db, &old_type);
+                let new_func = get_func_shape(self.new.db, &new_type);
+                match (old_func, new_func) {
+                    (Some(old_func), Some(new_func)) => {
+                        self.diff_function(name, location, &old_func, &new_func)
+                    }
+                    (None, None) => {
+                        self.diff_output_type(name, location, "type", &old_type, &new_type)
+                    }
+                    (Some(_), None) => self.add_change(
+                        ChangeLevel::Major,
+                        location,
+                        name,
+                        "is no longer a function".to_string(),
+                    ),
+                    (None, Some(_)) => self.add_change(
+                        ChangeLevel::Major,
+                        location,
+                        name,
+                        "became a function".to_string(),
+                    ),
+                }
+            }
+        }
+    }
+
+    fn diff_function(
+        &mut self,
+        name: &str,
+        location: Option<&ApiLocation>,
+        old_func: &FuncShape,
+        new_func: &FuncShape,
+    ) {
+        if old_func.is_method != new_func.is_method {
+            let message = if new_func.is_method {
+                "is now called with `:`"
+            } else {
+                "is now called with `.`"
+            };
+            self.add_change(ChangeLevel::Major, location, name, message.to_string());
+        }
+
+        for (idx, old_param) in old_func.params.iter().enumerate() {
+            let Some(new_param) = new_func.params.get(idx) else {
+                self.add_change(
+                    ChangeLevel::Major,
+                    location,
+                    name,
+                    format!("the param `{}` was removed", old_param.name),
+                );
+                continue;
+            };
+
+            let what = format!("the param `{}`", new_param.name);
+            if old_param.optional && !new_param.optional {
+                self.add_change(
+                    ChangeLevel::Major,
+                    location,
+                    name,
+                    format!("{} is required now", what),
+                );
+            } else if !old_param.optional && new_param.optional {
+                self.add_change(
+                    ChangeLevel::Minor,
+                    location,
+                    name,
+                    format!("{} is optional now", what),
+                );
+            }
+
+            let old_type = old_param.typ.clone().unwrap_or(LuaType::Any);
+            let new_type = new_param.typ.clone().unwrap_or(LuaType::Any);
+            // the new param must accept the arguments of the old callers
+            let level = match self.compare_types(&old_type, &new_type) {
+                TypeChange::Same => continue,
+                TypeChange::Widened | TypeChange::Compatible => ChangeLevel::Minor,
+                TypeChange::Narrowed | TypeChange::Incompatible => ChangeLevel::Major,
+            };
+            self.add_change(
+                level,
+                location,
+                name,
+                self.get_type_change_message(&what, &old_type, &new_type),
+            );
+        }
+
+        for new_param in new_func.params.iter().skip(old_func.params.len()) {
+            if new_param.optional {
+                self.add_change(
+                    ChangeLevel::Minor,
+                    location,
+                    name,
+                    format!("the optional param `{}` was added", new_param.name),
+                );
+            } else {
+                self.add_change(
+                    ChangeLevel::Major,
+                    location,
+                    name,
+                    format!("the required param `{}` was added", new_param.name),
+                );
+            }
+        }
+
+        for (idx, old_return) in old_func.returns.iter().enumerate() {
+            let what = format!("the return {}", idx + 1);
+            match new_func.returns.get(idx) {
+                Some(new_return) => {
+                    self.diff_output_type(name, location, &what, old_return, new_return)
+                }
+                None => self.add_change(
+                    ChangeLevel::Major,
+                    location,
+                    name,
+                    format!("{} was removed", what),
+                ),
+            }
+        }
+
+        if new_func.returns.len() > old_func.returns.len() {
+            self.add_change(
+                ChangeLevel::Minor,
+                location,
+                name,
+                format!(
+                    "returns {} values instead of {}",
+                    new_func.returns.len(),
+                    old_func.returns.len()
+                ),
+            );
+        }
+    }
+
+    // the callers of the old version must accept the new returns and the new fields
+    fn diff_output_type(
+        &mut self,
+        name: &str,
+        location: Option<&ApiLocation>,
+        what: &str,
+        old_type: &LuaType,
+        new_type: &LuaType,
+    ) {
+        let level = match self.compare_types(old_type, new_type) {
+            TypeChange::Same => return,
+            TypeChange::Narrowed | TypeChange::Compatible => ChangeLevel::Minor,
+            TypeChange::Widened | TypeChange::Incompatible => ChangeLevel::Major,
+        };
+        self.add_change(
+            level,
+            location,
+            name,
+            self.get_type_change_message(what, old_type, new_type),
+        );
+    }
+
+    // the old type is resolved in the new db by the names of its classes, the old type which
+    // refers to a class that is gone can not be compatible with the new one
+    fn compare_types(&self, old_type: &LuaType, new_type: &LuaType) -> TypeChange {
+        if self.render_old_type(old_type) == self.render_new_type(new_type) {
+            return TypeChange::Same;
+        }
+
+        let db = self.new.db;
+        let Some(old_type) = resolve_old_type(self.old.db, db, old_type) else {
+            return TypeChange::Incompatible;
+        };
+        let narrowed = check_type_compact(db, &old_type, new_type).is_ok();
+        let widened = check_type_compact(db, new_type, &old_type).is_ok();
+        match (narrowed, widened) {
+            (true, true) => TypeChange::Compatible,
+            (true, false) => TypeChange::Narrowed,
+            (false, true) => TypeChange::Widened,
+            (false, false) => TypeChange::Incompatible,
+        }
+    }
+
+    fn get_type_change_message(
+        &self,
+        what: &str,
+        old_type: &LuaType,
+        new_type: &LuaType,
+    ) -> String {
+        format!(
+            "{} changed from `{}` to `{}`",
+            what,
+            self.render_old_type(old_type),
+            self.render_new_type(new_type)
+        )
+    }
+
+    fn render_old_type(&self, typ: &LuaType) -> String {
+        render_type(&to_api_type(self.old.db, typ))
+    }
+
+    fn render_new_type(&self, typ: &LuaType) -> String {
+        render_type(&to_api_type(self.new.db, typ))
+    }
+
+    fn add_change(
+        &mut self,
+        level: ChangeLevel,
+        location: Option<&ApiLocation>,
+        name: &str,
+        message: String,
+    ) {
+        self.add_kind_change(ApiChangeKind::Changed, level, location, name, message);
+    }
+
+    fn add_kind_change(
+        &mut self,
+        kind: ApiChangeKind,
+        level: ChangeLevel,
+        location: Option<&ApiLocation>,
+        name: &str,
+        message: String,
+    ) {
+        self.changes.push(ApiChange {
+            level,
+            kind,
+            location: location.cloned(),
+            name: name.to_string(),
+            message,
+        });
+    }
+}
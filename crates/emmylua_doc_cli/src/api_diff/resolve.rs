@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use emmylua_code_analysis::{
+    DbIndex, LuaAliasCallType, LuaFunctionType, LuaGenericType, LuaIntersectionType,
+    LuaMultiLineUnion, LuaMultiReturn, LuaObjectType, LuaTupleType, LuaType, LuaUnionType,
+};
+
+/// Resolves the type of the old version in the db of the new version, so the two versions can be
+/// checked by `check_type_compact`.
+///
+/// The classes and the aliases are found by their names, the signatures become doc functions and
+/// the table literals become `table`. Returns `None` when the type refers to a class or an alias
+/// which is gone in the new version.
+pub fn resolve_old_type(old_db: &DbIndex, new_db: &DbIndex, typ: &LuaType) -> Option<LuaType> {
+    let resolve = |typ: &LuaType| resolve_old_type(old_db, new_db, typ);
+    let resolve_types = |types: &[LuaType]| types.iter().map(resolve).collect::<Option<Vec<_>>>();
+    let resolved = match typ {
+        LuaType::Ref(id) | LuaType::Def(id) => {
+            new_db.get_type_index().get_type_decl(id)?;
+            typ.clone()
+        }
+        LuaType::TableConst(_) => LuaType::Table,
+        LuaType::Instance(instance) => resolve(instance.get_base())?,
+        LuaType::Signature(signature_id) => {
+            let signature = old_db.get_signature_index().get(signature_id)?;
+            resolve(&LuaType::DocFunction(signature.to_doc_func_type()))?
+        }
+        LuaType::Array(base) => LuaType::Array(Arc::new(resolve(base)?)),
+        LuaType::Variadic(base) => LuaType::Variadic(Arc::new(resolve(base)?)),
+        LuaType::Tuple(tuple) => LuaTupleType::new(resolve_types(tuple.get_types())?).into(),
+        LuaType::Union(union) => LuaUnionType::new(resolve_types(union.get_types())?).into(),
+        LuaType::Intersection(intersection) => {
+            LuaIntersectionType::new(resolve_types(intersection.get_types())?).into()
+        }
+        LuaType::TableGeneric(params) => LuaType::TableGeneric(Arc::new(resolve_types(params)?)),
+        LuaType::Generic(generic) => {
+            new_db
+                .get_type_index()
+                .get_type_decl(&generic.get_base_type_id())?;
+            LuaGenericType::new(
+                generic.get_base_type_id(),
+                resolve_types(generic.get_params())?,
+            )
+            .into()
+        }
+        LuaType::DocFunction(func) => {
+            let params = func
+                .get_params()
+                .iter()
+                .map(|(name, typ)| match typ {
+                    Some(typ) => Some((name.clone(), Some(resolve(typ)?))),
+                    None => Some((name.clone(), None)),
+                })
+                .collect::<Option<Vec<_>>>()?;
+            LuaFunctionType::new(
+                func.is_async(),
+                func.is_colon_define(),
+                params,
+                resolve_types(func.get_ret())?,
+            )
+            .into()
+        }
+        LuaType::Object(object) => {
+            let fields = object
+                .get_fields()
+                .iter()
+                .map(|(key, typ)| Some((key.clone(), resolve(typ)?)))
+                .collect::<Option<_>>()?;
+            let index_access = object
+                .get_index_access()
+                .iter()
+                .map(|(key, typ)| Some((resolve(key)?, resolve(typ)?)))
+                .collect::<Option<_>>()?;
+            LuaObjectType::new_with_fields(fields, index_access).into()
+        }
+        LuaType::MuliReturn(multi) => {
+            let multi = match multi.as_ref() {
+                LuaMultiReturn::Multi(types) => LuaMultiReturn::Multi(resolve_types(types)?),
+                LuaMultiReturn::Base(base) => LuaMultiReturn::Base(resolve(base)?),
+            };
+            LuaType::MuliReturn(Arc::new(multi))
+        }
+        LuaType::MultiLineUnion(multi) => {
+            let unions = multi
+                .get_unions()
+                .iter()
+                .map(|(typ, description)| Some((resolve(typ)?, description.clone())))
+                .collect::<Option<_>>()?;
+            LuaType::MultiLineUnion(Arc::new(LuaMultiLineUnion::new(unions)))
+        }
+        LuaType::Call(call) => LuaType::Call(Arc::new(LuaAliasCallType::new(
+            call.get_call_kind(),
+            resolve_types(call.get_operands())?,
+        ))),
+        _ => typ.clone(),
+    };
+
+    Some(resolved)
+}
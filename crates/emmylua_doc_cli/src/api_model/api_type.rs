@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+
+use emmylua_code_analysis::{
+    DbIndex, LuaAliasCallKind, LuaFunctionType, LuaMemberKey, LuaMultiReturn, LuaSignatureId,
+    LuaType, SignatureReturnStatus,
+};
+use emmylua_parser::{
+    LuaAstNode, LuaClosureExpr, LuaCommentOwner, LuaDocTagParam, LuaStat, LuaTableField,
+};
+use serde_json::json;
+
+use super::model::{
+    ApiFunction, ApiGenericParam, ApiIndexAccess, ApiObjectField, ApiParam, ApiReturn, ApiType,
+};
+
+pub fn to_api_type(db: &DbIndex, typ: &LuaType) -> ApiType {
+    match typ {
+        LuaType::Unknown => primitive("unknown"),
+        LuaType::Any => primitive("any"),
+        LuaType::Nil => primitive("nil"),
+        LuaType::Table | LuaType::TableConst(_) => primitive("table"),
+        LuaType::Userdata => primitive("userdata"),
+        LuaType::Function => primitive("function"),
+        LuaType::Thread => primitive("thread"),
+        LuaType::Boolean => primitive("boolean"),
+        LuaType::String => primitive("string"),
+        LuaType::Integer => primitive("integer"),
+        LuaType::Number => primitive("number"),
+        LuaType::Io => primitive("io"),
+        LuaType::SelfInfer => primitive("self"),
+        LuaType::Global => primitive("global"),
+        LuaType::BooleanConst(b) | LuaType::DocBooleanConst(b) => {
+            ApiType::Literal { value: json!(b) }
+        }
+        LuaType::StringConst(s) | LuaType::DocStringConst(s) => ApiType::Literal {
+            value: json!(s.as_str()),
+        },
+        LuaType::IntegerConst(i) | LuaType::DocIntegerConst(i) => {
+            ApiType::Literal { value: json!(i) }
+        }
+        LuaType::FloatConst(f) => ApiType::Literal { value: json!(f) },
+        LuaType::Ref(id) | LuaType::Def(id) => ApiType::Ref {
+            name: id.get_name().to_string(),
+        },
+        LuaType::Array(base) => ApiType::Array {
+            element: Box::new(to_api_type(db, base)),
+        },
+        LuaType::Tuple(tuple) => ApiType::Tuple {
+            types: to_api_types(db, tuple.get_types()),
+        },
+        LuaType::DocFunction(func) => ApiType::Function(to_api_doc_function(db, func)),
+        LuaType::Signature(signature_id) => match to_api_signature(db, signature_id) {
+            Some(func) => ApiType::Function(func),
+            None => primitive("function"),
+        },
+        LuaType::Object(object) => {
+            let mut fields = object
+                .get_fields()
+                .iter()
+                .map(|(key, typ)| ApiObjectField {
+                    name: get_member_key_name(key),
+                    typ: to_api_type(db, typ),
+                })
+                .collect::<Vec<_>>();
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+            ApiType::Object {
+                fields,
+                index_access: object
+                    .get_index_access()
+                    .iter()
+                    .map(|(key, value)| ApiIndexAccess {
+                        key: to_api_type(db, key),
+                        value: to_api_type(db, value),
+                    })
+                    .collect(),
+            }
+        }
+        LuaType::Union(union) => ApiType::Union {
+            types: to_api_types(db, union.get_types()),
+        },
+        LuaType::MultiLineUnion(union) => ApiType::Union {
+            types: union
+                .get_unions()
+                .iter()
+                .map(|(typ, _)| to_api_type(db, typ))
+                .collect(),
+        },
+        LuaType::Intersection(intersection) => ApiType::Intersection {
+            types: to_api_types(db, intersection.get_types()),
+        },
+        LuaType::Generic(generic) => ApiType::Generic {
+            name: generic.get_base_type_id().get_name().to_string(),
+            params: to_api_types(db, generic.get_params()),
+        },
+        LuaType::TableGeneric(params) => ApiType::TableGeneric {
+            params: to_api_types(db, params),
+        },
+        LuaType::TplRef(tpl) => ApiType::TemplateRef {
+            name: tpl.get_name().to_string(),
+        },
+        LuaType::StrTplRef(str_tpl) => ApiType::StringTemplateRef {
+            prefix: str_tpl.get_prefix().to_string(),
+            name: str_tpl.get_name().to_string(),
+            suffix: str_tpl.get_suffix().to_string(),
+        },
+        LuaType::MuliReturn(multi) => match multi.as_ref() {
+            LuaMultiReturn::Multi(types) => ApiType::MultiReturn {
+                types: to_api_types(db, types),
+            },
+            LuaMultiReturn::Base(base) => ApiType::Variadic {
+                typ: Box::new(to_api_type(db, base)),
+            },
+        },
+        LuaType::Variadic(base) => ApiType::Variadic {
+            typ: Box::new(to_api_type(db, base)),
+        },
+        LuaType::Instance(instance) => to_api_type(db, instance.get_base()),
+        LuaType::Namespace(name) => ApiType::Namespace {
+            name: name.to_string(),
+        },
+        LuaType::Call(call) => ApiType::Call {
+            call: get_call_kind_name(call.get_call_kind()).to_string(),
+            operands: to_api_types(db, call.get_operands()),
+        },
+    }
+}
+
+pub fn to_api_types(db: &DbIndex, types: &[LuaType]) -> Vec<ApiType> {
+    types.iter().map(|typ| to_api_type(db, typ)).collect()
+}
+
+pub fn to_api_generic_params(
+    db: &DbIndex,
+    params: &[(String, Option<LuaType>)],
+) -> Vec<ApiGenericParam> {
+    params
+        .iter()
+        .map(|(name, constraint)| ApiGenericParam {
+            name: name.clone(),
+            constraint: constraint.as_ref().map(|typ| to_api_type(db, typ)),
+        })
+        .collect()
+}
+
+fn to_api_doc_function(db: &DbIndex, func: &LuaFunctionType) -> ApiFunction {
+    ApiFunction {
+        params: func
+            .get_params()
+            .iter()
+            .map(|(name, typ)| ApiParam {
+                name: name.clone(),
+                typ: typ.as_ref().map(|typ| to_api_type(db, typ)),
+                optional: typ.as_ref().is_some_and(|typ| typ.is_optional()),
+                description: None,
+                has_tag: typ.is_some(),
+            })
+            .collect(),
+        returns: func
+            .get_ret()
+            .iter()
+            .map(|typ| ApiReturn {
+                name: None,
+                typ: to_api_type(db, typ),
+                description: None,
+                has_tag: true,
+            })
+            .collect(),
+        is_async: func.is_async(),
+        is_method: func.is_colon_define(),
+        ..Default::default()
+    }
+}
+
+// the signature has the docs of the params and the returns
+fn to_api_signature(db: &DbIndex, signature_id: &LuaSignatureId) -> Option<ApiFunction> {
+    let signature = db.get_signature_index().get(signature_id)?;
+    let doc_param_names = get_doc_param_names(db, signature_id);
+    let params = signature
+        .get_type_params()
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (name, typ))| {
+            let param_info = signature.get_param_info_by_id(idx);
+            ApiParam {
+                has_tag: doc_param_names.contains(&name),
+                name,
+                typ: typ.as_ref().map(|typ| to_api_type(db, typ)),
+                optional: param_info.is_some_and(|info| info.nullable),
+                description: param_info.and_then(|info| info.description.clone()),
+            }
+        })
+        .collect();
+
+    let returns = signature
+        .return_docs
+        .iter()
+        .map(|ret| ApiReturn {
+            name: ret.name.clone(),
+            typ: to_api_type(db, &ret.type_ref),
+            description: ret.description.clone(),
+            has_tag: signature.resolve_return == SignatureReturnStatus::DocResolve,
+        })
+        .collect();
+
+    Some(ApiFunction {
+        generics: to_api_generic_params(db, &signature.generic_params),
+        params,
+        returns,
+        overloads: signature
+            .overloads
+            .iter()
+            .map(|overload| to_api_doc_function(db, overload))
+            .collect(),
+        is_async: signature.is_async,
+        is_method: signature.is_colon_define,
+        is_nodiscard: signature.is_nodiscard,
+    })
+}
+
+// the types of the params without the `@param` tag are inferred, eg: from the call or the type of
+// the field which the closure is assigned to
+fn get_doc_param_names(db: &DbIndex, signature_id: &LuaSignatureId) -> HashSet<String> {
+    let Some(tree) = db.get_vfs().get_syntax_tree(&signature_id.get_file_id()) else {
+        return HashSet::new();
+    };
+    let closure = tree
+        .get_red_root()
+        .token_at_offset(signature_id.get_position())
+        .right_biased()
+        .and_then(|token| token.parent_ancestors().find_map(LuaClosureExpr::cast));
+    let comment = closure.and_then(|closure| {
+        closure.syntax().ancestors().find_map(|node| {
+            if let Some(stat) = LuaStat::cast(node.clone()) {
+                Some(stat.get_left_comment())
+            } else {
+                LuaTableField::cast(node).map(|field| field.get_left_comment())
+            }
+        })?
+    });
+
+    comment
+        .map(|comment| {
+            comment
+                .children::<LuaDocTagParam>()
+                .filter_map(|tag| {
+                    if tag.is_vararg() {
+                        return Some("...".to_string());
+                    }
+                    tag.get_name_token()
+                        .map(|token| token.get_name_text().to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn get_member_key_name(key: &LuaMemberKey) -> String {
+    match key {
+        LuaMemberKey::Name(name) => name.to_string(),
+        LuaMemberKey::Integer(i) => format!("[{}]", i),
+        LuaMemberKey::SyntaxId(_) | LuaMemberKey::None => String::new(),
+    }
+}
+
+fn get_call_kind_name(call_kind: LuaAliasCallKind) -> &'static str {
+    match call_kind {
+        LuaAliasCallKind::KeyOf => "keyof",
+        LuaAliasCallKind::Index => "index",
+        LuaAliasCallKind::Extends => "extends",
+        LuaAliasCallKind::Add => "add",
+        LuaAliasCallKind::Sub => "sub",
+        LuaAliasCallKind::Select => "select",
+        LuaAliasCallKind::Unpack => "unpack",
+    }
+}
+
+fn primitive(name: &str) -> ApiType {
+    ApiType::Primitive {
+        name: name.to_string(),
+    }
+}
@@ -0,0 +1,355 @@
+use std::path::Path;
+
+use emmylua_code_analysis::{
+    DbIndex, FileId, LuaDeclId, LuaMemberOwner, LuaSemanticDeclId, LuaType, LuaTypeCache,
+    LuaTypeDecl, ModuleInfo,
+};
+use emmylua_parser::{LuaVersionCondition, LuaVersionNumber, VisibilityKind};
+use rowan::TextSize;
+
+use super::{
+    api_type::{get_member_key_name, to_api_generic_params, to_api_type},
+    model::{
+        ApiAlias, ApiClass, ApiDeprecated, ApiEnum, ApiGlobal, ApiLocation, ApiMember, ApiModel,
+        ApiModule, ApiProperty, ApiVersionCondition, ApiVersionOp, ApiVisibility,
+        API_MODEL_VERSION,
+    },
+};
+
+/// Collects the api of the main workspace, the std and the library types are not included
+pub fn collect_api_model(db: &DbIndex, input: &Path) -> ApiModel {
+    let mut model = ApiModel {
+        version: API_MODEL_VERSION,
+        classes: Vec::new(),
+        enums: Vec::new(),
+        aliases: Vec::new(),
+        modules: Vec::new(),
+        globals: Vec::new(),
+    };
+
+    for type_decl in db.get_type_index().get_all_types() {
+        if !is_main_type(db, type_decl) {
+            continue;
+        }
+
+        if type_decl.is_class() {
+            model.classes.push(collect_class(db, type_decl, input));
+        } else if type_decl.is_enum() {
+            model.enums.push(collect_enum(db, type_decl, input));
+        } else {
+            model.aliases.push(collect_alias(db, type_decl, input));
+        }
+    }
+
+    for module in db.get_module_index().get_module_infos() {
+        if !module.workspace_id.is_main() {
+            continue;
+        }
+
+        if let Some(api_module) = collect_module(db, module, input) {
+            model.modules.push(api_module);
+        }
+    }
+
+    for decl_id in db.get_global_index().get_all_global_decl_ids() {
+        if let Some(global) = collect_global(db, &decl_id, input) {
+            model.globals.push(global);
+        }
+    }
+
+    model.classes.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+    model.enums.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+    model.aliases.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+    model.modules.sort_by(|a, b| a.name.cmp(&b.name));
+    model.globals.sort_by(|a, b| a.name.cmp(&b.name));
+    model
+}
+
+fn is_main_type(db: &DbIndex, type_decl: &LuaTypeDecl) -> bool {
+    type_decl.get_locations().iter().any(|location| {
+        db.get_module_index()
+            .get_module(location.file_id)
+            .is_some_and(|module| module.workspace_id.is_main())
+    })
+}
+
+fn collect_class(db: &DbIndex, type_decl: &LuaTypeDecl, input: &Path) -> ApiClass {
+    let type_id = type_decl.get_id();
+    let type_index = db.get_type_index();
+    ApiClass {
+        name: type_decl.get_name().to_string(),
+        full_name: type_decl.get_full_name().to_string(),
+        namespace: type_decl.get_namespace().map(|s| s.to_string()),
+        generics: type_index
+            .get_generic_params(&type_id)
+            .map(|params| to_api_generic_params(db, params))
+            .unwrap_or_default(),
+        supers: type_index
+            .get_super_types(&type_id)
+            .unwrap_or_default()
+            .iter()
+            .map(|typ| to_api_type(db, typ))
+            .collect(),
+        members: collect_members(db, LuaMemberOwner::Type(type_id.clone()), input),
+        property: collect_property(db, LuaSemanticDeclId::TypeDecl(type_id)),
+        locations: collect_type_locations(db, type_decl, input),
+    }
+}
+
+fn collect_enum(db: &DbIndex, type_decl: &LuaTypeDecl, input: &Path) -> ApiEnum {
+    let type_id = type_decl.get_id();
+    ApiEnum {
+        name: type_decl.get_name().to_string(),
+        full_name: type_decl.get_full_name().to_string(),
+        namespace: type_decl.get_namespace().map(|s| s.to_string()),
+        base: type_decl.get_enum_base().map(|typ| to_api_type(db, typ)),
+        is_key: type_decl.is_enum_key(),
+        members: collect_members(db, LuaMemberOwner::Type(type_id.clone()), input),
+        property: collect_property(db, LuaSemanticDeclId::TypeDecl(type_id)),
+        locations: collect_type_locations(db, type_decl, input),
+    }
+}
+
+fn collect_alias(db: &DbIndex, type_decl: &LuaTypeDecl, input: &Path) -> ApiAlias {
+    let type_id = type_decl.get_id();
+    ApiAlias {
+        name: type_decl.get_name().to_string(),
+        full_name: type_decl.get_full_name().to_string(),
+        namespace: type_decl.get_namespace().map(|s| s.to_string()),
+        generics: db
+            .get_type_index()
+            .get_generic_params(&type_id)
+            .map(|params| to_api_generic_params(db, params))
+            .unwrap_or_default(),
+        origin: type_decl
+            .get_alias_origin(db, None)
+            .map(|typ| to_api_type(db, &typ)),
+        property: collect_property(db, LuaSemanticDeclId::TypeDecl(type_id)),
+        locations: collect_type_locations(db, type_decl, input),
+    }
+}
+
+fn collect_module(db: &DbIndex, module: &ModuleInfo, input: &Path) -> Option<ApiModule> {
+    let file_path = db.get_vfs().get_file_path(&module.file_id)?;
+    let export_type = module.export_type.as_ref();
+    let mut property = match &module.property_owner_id {
+        Some(property_id) => collect_property(db, property_id.clone()),
+        None => ApiProperty::default(),
+    };
+    if property.versions.is_empty() {
+        property.versions = to_api_version_conds(module.version_conds.as_deref());
+    }
+
+    Some(ApiModule {
+        name: module.full_module_name.clone(),
+        file: get_relative_path(input, file_path),
+        export: export_type.map(|typ| to_api_type(db, typ)),
+        members: export_type
+            .and_then(get_table_member_owner)
+            .map(|owner| collect_members(db, owner, input))
+            .unwrap_or_default(),
+        property,
+    })
+}
+
+fn collect_global(db: &DbIndex, decl_id: &LuaDeclId, input: &Path) -> Option<ApiGlobal> {
+    let module = db.get_module_index().get_module(decl_id.file_id)?;
+    if !module.workspace_id.is_main() {
+        return None;
+    }
+
+    let decl = db.get_decl_index().get_decl(decl_id)?;
+    let decl_type = db
+        .get_type_index()
+        .get_type_cache(&(*decl_id).into())
+        .map(|cache| cache.as_type().clone())
+        .unwrap_or(LuaType::Unknown);
+
+    Some(ApiGlobal {
+        name: decl.get_name().to_string(),
+        typ: to_api_type(db, &decl_type),
+        members: get_table_member_owner(&decl_type)
+            .map(|owner| collect_members(db, owner, input))
+            .unwrap_or_default(),
+        property: collect_value_property(db, LuaSemanticDeclId::LuaDecl(*decl_id), &decl_type),
+        location: get_location(db, decl_id.file_id, decl.get_position(), input)?,
+    })
+}
+
+// the members of a table export are owned by the table element
+pub fn get_table_member_owner(typ: &LuaType) -> Option<LuaMemberOwner> {
+    match typ {
+        LuaType::TableConst(t) => Some(LuaMemberOwner::Element(t.clone())),
+        LuaType::Instance(i) => Some(LuaMemberOwner::Element(i.get_range().clone())),
+        _ => None,
+    }
+}
+
+fn collect_members(db: &DbIndex, owner: LuaMemberOwner, input: &Path) -> Vec<ApiMember> {
+    let Some(members) = db.get_member_index().get_sorted_members(&owner) else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::new();
+    for member in members {
+        let name = get_member_key_name(member.get_key());
+        if name.is_empty() {
+            continue;
+        }
+
+        let member_type = db
+            .get_type_index()
+            .get_type_cache(&member.get_id().into())
+            .unwrap_or(&LuaTypeCache::InferType(LuaType::Unknown))
+            .as_type();
+        let Some(location) =
+            get_location(db, member.get_file_id(), member.get_range().start(), input)
+        else {
+            continue;
+        };
+
+        result.push(ApiMember {
+            name,
+            typ: to_api_type(db, member_type),
+            is_doc_field: member.is_field(),
+            property: collect_value_property(
+                db,
+                LuaSemanticDeclId::Member(member.get_id()),
+                member_type,
+            ),
+            location,
+        });
+    }
+
+    result
+}
+
+// the tags before a function statement are owned by the signature
+fn collect_value_property(
+    db: &DbIndex,
+    semantic_decl: LuaSemanticDeclId,
+    typ: &LuaType,
+) -> ApiProperty {
+    let mut property = collect_property(db, semantic_decl);
+    let LuaType::Signature(signature_id) = typ else {
+        return property;
+    };
+
+    let signature_property = collect_property(db, LuaSemanticDeclId::Signature(*signature_id));
+    if property.description.is_none() {
+        property.description = signature_property.description;
+    }
+    if property.visibility == ApiVisibility::Public {
+        property.visibility = signature_property.visibility;
+    }
+    if property.deprecated.is_none() {
+        property.deprecated = signature_property.deprecated;
+    }
+    if property.versions.is_empty() {
+        property.versions = signature_property.versions;
+    }
+    if property.since.is_none() {
+        property.since = signature_property.since;
+    }
+    if property.see.is_none() {
+        property.see = signature_property.see;
+    }
+    if property.source.is_none() {
+        property.source = signature_property.source;
+    }
+    if property.other.is_none() {
+        property.other = signature_property.other;
+    }
+    property
+}
+
+fn collect_property(db: &DbIndex, semantic_decl: LuaSemanticDeclId) -> ApiProperty {
+    let Some(property) = db.get_property_index().get_property(&semantic_decl) else {
+        return ApiProperty::default();
+    };
+
+    ApiProperty {
+        // the description of the class is empty when there is only the tags
+        description: property
+            .description
+            .as_deref()
+            .filter(|description| !description.is_empty())
+            .cloned(),
+        visibility: to_api_visibility(property.visibility),
+        deprecated: property.is_deprecated.then(|| ApiDeprecated {
+            message: property.deprecated_message.as_deref().cloned(),
+        }),
+        versions: to_api_version_conds(property.version_conds.as_deref()),
+        since: property.since.clone(),
+        see: property.see_content.as_deref().cloned(),
+        source: property.source.as_deref().cloned(),
+        other: property.other_content.as_deref().cloned(),
+        examples: property.examples.clone().unwrap_or_default(),
+    }
+}
+
+fn to_api_visibility(visibility: Option<VisibilityKind>) -> ApiVisibility {
+    match visibility {
+        Some(VisibilityKind::Protected) => ApiVisibility::Protected,
+        Some(VisibilityKind::Private) => ApiVisibility::Private,
+        Some(VisibilityKind::Internal) => ApiVisibility::Internal,
+        Some(VisibilityKind::Package) => ApiVisibility::Package,
+        _ => ApiVisibility::Public,
+    }
+}
+
+fn to_api_version_conds(
+    version_conds: Option<&Vec<LuaVersionCondition>>,
+) -> Vec<ApiVersionCondition> {
+    let Some(version_conds) = version_conds else {
+        return Vec::new();
+    };
+
+    version_conds
+        .iter()
+        .map(|cond| {
+            let (op, version) = match cond {
+                LuaVersionCondition::Eq(v) => (ApiVersionOp::Eq, v),
+                LuaVersionCondition::Gte(v) => (ApiVersionOp::Gte, v),
+                LuaVersionCondition::Lte(v) => (ApiVersionOp::Lte, v),
+            };
+            let version = if *version == LuaVersionNumber::LUA_JIT {
+                "JIT".to_string()
+            } else {
+                format!("{}.{}", version.major, version.minor)
+            };
+            ApiVersionCondition { op, version }
+        })
+        .collect()
+}
+
+fn collect_type_locations(db: &DbIndex, type_decl: &LuaTypeDecl, input: &Path) -> Vec<ApiLocation> {
+    type_decl
+        .get_locations()
+        .iter()
+        .filter_map(|location| get_location(db, location.file_id, location.range.start(), input))
+        .collect()
+}
+
+fn get_location(
+    db: &DbIndex,
+    file_id: FileId,
+    offset: TextSize,
+    input: &Path,
+) -> Option<ApiLocation> {
+    let document = db.get_vfs().get_document(&file_id)?;
+    let (line, col) = document.get_line_col(offset)?;
+    Some(ApiLocation {
+        file: get_relative_path(input, document.get_file_path()),
+        line: line + 1,
+        col: col + 1,
+    })
+}
+
+pub fn get_relative_path(input: &Path, file_path: &Path) -> String {
+    file_path
+        .strip_prefix(input)
+        .unwrap_or(file_path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
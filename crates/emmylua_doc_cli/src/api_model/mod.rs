@@ -0,0 +1,10 @@
+mod api_type;
+mod collect;
+mod model;
+
+pub use api_type::{get_member_key_name, to_api_type};
+pub use collect::{collect_api_model, get_relative_path, get_table_member_owner};
+pub use model::{
+    ApiAlias, ApiClass, ApiEnum, ApiFunction, ApiGenericParam, ApiGlobal, ApiLocation, ApiMember,
+    ApiModel, ApiModule, ApiProperty, ApiType, ApiVersionOp, ApiVisibility,
+};
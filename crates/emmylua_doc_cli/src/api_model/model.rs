@@ -0,0 +1,313 @@
+use serde::{Deserialize, Serialize};
+
+/// The version of the json api model, it is increased when the format changes incompatibly
+pub const API_MODEL_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiModel {
+    pub version: u32,
+    pub classes: Vec<ApiClass>,
+    pub enums: Vec<ApiEnum>,
+    pub aliases: Vec<ApiAlias>,
+    pub modules: Vec<ApiModule>,
+    pub globals: Vec<ApiGlobal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiClass {
+    pub name: String,
+    pub full_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generics: Vec<ApiGenericParam>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supers: Vec<ApiType>,
+    pub members: Vec<ApiMember>,
+    #[serde(flatten)]
+    pub property: ApiProperty,
+    pub locations: Vec<ApiLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiEnum {
+    pub name: String,
+    pub full_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<ApiType>,
+    /// The enum uses the keys of the fields rather than the values
+    pub is_key: bool,
+    pub members: Vec<ApiMember>,
+    #[serde(flatten)]
+    pub property: ApiProperty,
+    pub locations: Vec<ApiLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAlias {
+    pub name: String,
+    pub full_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generics: Vec<ApiGenericParam>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<ApiType>,
+    #[serde(flatten)]
+    pub property: ApiProperty,
+    pub locations: Vec<ApiLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiModule {
+    pub name: String,
+    pub file: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export: Option<ApiType>,
+    /// The members of the exported table, the members of an exported class are in the class
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<ApiMember>,
+    #[serde(flatten)]
+    pub property: ApiProperty,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiGlobal {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: ApiType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<ApiMember>,
+    #[serde(flatten)]
+    pub property: ApiProperty,
+    pub location: ApiLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMember {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: ApiType,
+    /// The member is declared by a `---@field` tag
+    pub is_doc_field: bool,
+    #[serde(flatten)]
+    pub property: ApiProperty,
+    pub location: ApiLocation,
+}
+
+/// The doc properties shared by the types, members, modules and globals
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiProperty {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub visibility: ApiVisibility,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<ApiDeprecated>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<ApiVersionCondition>,
+    /// The version of the package in which the item appeared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub see: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other: Option<String>,
+    /// The markdown of the examples, the code is in the fenced code blocks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiVisibility {
+    #[default]
+    Public,
+    Protected,
+    Private,
+    Internal,
+    Package,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDeprecated {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The `@version` condition, eg: `>= 5.3` is `{ "op": "gte", "version": "5.3" }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiVersionCondition {
+    pub op: ApiVersionOp,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiVersionOp {
+    Eq,
+    Gte,
+    Lte,
+}
+
+/// The file is relative to the input directory, the line and the col start from 1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiLocation {
+    pub file: String,
+    pub line: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiGenericParam {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<ApiType>,
+}
+
+/// The structured lua type, it is tagged by the `kind` field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ApiType {
+    /// The builtin types, eg: `any`, `nil`, `string`, `integer`, `table`, `function`
+    Primitive {
+        name: String,
+    },
+    /// The literal types, eg: `"a"`, `1`, `true`
+    Literal {
+        value: serde_json::Value,
+    },
+    /// The reference of a class, an enum or an alias
+    Ref {
+        name: String,
+    },
+    Array {
+        element: Box<ApiType>,
+    },
+    Tuple {
+        types: Vec<ApiType>,
+    },
+    Function(ApiFunction),
+    Object {
+        fields: Vec<ApiObjectField>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        index_access: Vec<ApiIndexAccess>,
+    },
+    Union {
+        types: Vec<ApiType>,
+    },
+    Intersection {
+        types: Vec<ApiType>,
+    },
+    /// The instantiated generic class or alias, eg: `List<string>`
+    Generic {
+        name: String,
+        params: Vec<ApiType>,
+    },
+    /// eg: `table<string, integer>`
+    TableGeneric {
+        params: Vec<ApiType>,
+    },
+    /// The reference of a generic parameter
+    TemplateRef {
+        name: String,
+    },
+    /// eg: `` prefix.`T` ``
+    StringTemplateRef {
+        prefix: String,
+        name: String,
+        suffix: String,
+    },
+    Variadic {
+        #[serde(rename = "type")]
+        typ: Box<ApiType>,
+    },
+    MultiReturn {
+        types: Vec<ApiType>,
+    },
+    Namespace {
+        name: String,
+    },
+    /// The type operators, eg: `keyof T`
+    Call {
+        call: String,
+        operands: Vec<ApiType>,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiFunction {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generics: Vec<ApiGenericParam>,
+    pub params: Vec<ApiParam>,
+    pub returns: Vec<ApiReturn>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overloads: Vec<ApiFunction>,
+    pub is_async: bool,
+    /// The function is defined with `:`, the `self` param is not in the params
+    pub is_method: bool,
+    pub is_nodiscard: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiParam {
+    pub name: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<ApiType>,
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The param has a `@param` tag, otherwise its type is inferred
+    #[serde(skip)]
+    pub has_tag: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiReturn {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub typ: ApiType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The return has a `@return` tag, otherwise its type is inferred
+    #[serde(skip)]
+    pub has_tag: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiObjectField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: ApiType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiIndexAccess {
+    pub key: ApiType,
+    pub value: ApiType,
+}
@@ -33,4 +33,89 @@ pub struct CmdArgs {
         help = "The path of the mixin md file"
     )]
     pub mixin: Option<std::path::PathBuf>,
+
+    #[structopt(
+        parse(from_os_str),
+        long = "config",
+        help = "The path of the json config of the markdown and the html docs, eg: the include and exclude globs"
+    )]
+    pub config: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long = "format",
+        default_value = "markdown",
+        possible_values = &Format::variants(),
+        case_insensitive = true,
+        help = "The output format (markdown, json, html, stubs, teal, coverage or diff)"
+    )]
+    pub format: Format,
+
+    #[structopt(
+        long = "source-url",
+        help = "The base url of the source links in the html output, eg: https://github.com/user/repo/blob/main"
+    )]
+    pub source_url: Option<String>,
+
+    #[structopt(
+        long = "min-coverage",
+        help = "Fail when the documentation coverage in percent is lower than the threshold, used with `--format coverage`"
+    )]
+    pub min_coverage: Option<f64>,
+
+    #[structopt(
+        parse(from_os_str),
+        long = "compare",
+        help = "The path of the old version of the lua project, used with `--format diff`",
+        required_if("format", "diff")
+    )]
+    pub compare: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long = "versions",
+        use_delimiter = true,
+        help = "The older versions of the lua project from the oldest, eg: 1.0=../sdk-1.0,1.1=../sdk-1.1, the markdown and the html docs of every version are generated"
+    )]
+    pub versions: Vec<String>,
+
+    #[structopt(
+        long = "version-label",
+        default_value = "current",
+        help = "The version label of the input, used with `--versions`"
+    )]
+    pub version_label: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Json,
+    Html,
+    Stubs,
+    Teal,
+    Coverage,
+    Diff,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            "html" => Ok(Format::Html),
+            "stubs" => Ok(Format::Stubs),
+            "teal" => Ok(Format::Teal),
+            "coverage" => Ok(Format::Coverage),
+            "diff" => Ok(Format::Diff),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
+impl Format {
+    pub fn variants() -> [&'static str; 7] {
+        [
+            "markdown", "json", "html", "stubs", "teal", "coverage", "diff",
+        ]
+    }
 }
@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use emmylua_parser::VisibilityKind;
+use serde::Deserialize;
+use wax::{Glob, Pattern};
+
+use crate::api_model::{ApiMember, ApiModel, ApiVisibility};
+
+/// The config of the generated docs, it is loaded from the file given by `--config`
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DocConfig {
+    /// The globs of the files relative to the input, all files are included when it is empty
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// The globs of the dotted names of the types and the modules, eg: `sdk.internal.**`
+    pub include_namespaces: Vec<String>,
+    pub exclude_namespaces: Vec<String>,
+    /// Shows the `@private`, `@protected` and `@package` members
+    pub show_private: bool,
+    pub group_by: DocGroupBy,
+    /// The module which is shown as the entry of the package
+    pub entry_module: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DocGroupBy {
+    #[default]
+    None,
+    Namespace,
+    Directory,
+}
+
+pub fn load_doc_config(path: Option<&Path>) -> Option<DocConfig> {
+    let Some(path) = path else {
+        return Some(DocConfig::default());
+    };
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Failed to read config file {:?}: {}", path, e);
+            return None;
+        }
+    };
+    match serde_json::from_str(&content) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("Failed to parse config file {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Decides which files, names and members are written to the docs
+#[derive(Debug)]
+pub struct DocFilter {
+    include: Vec<Glob<'static>>,
+    exclude: Vec<Glob<'static>>,
+    include_namespaces: Vec<Glob<'static>>,
+    exclude_namespaces: Vec<Glob<'static>>,
+    show_private: bool,
+}
+
+impl DocFilter {
+    pub fn new(config: &DocConfig) -> Self {
+        Self {
+            include: compile_globs(&config.include),
+            exclude: compile_globs(&config.exclude),
+            include_namespaces: compile_namespace_globs(&config.include_namespaces),
+            exclude_namespaces: compile_namespace_globs(&config.exclude_namespaces),
+            show_private: config.show_private,
+        }
+    }
+
+    /// The path is relative to the input directory
+    pub fn is_file_included(&self, relative_path: &str) -> bool {
+        is_included(&self.include, &self.exclude, Path::new(relative_path))
+    }
+
+    pub fn is_name_included(&self, full_name: &str) -> bool {
+        let path = full_name.replace('.', "/");
+        is_included(
+            &self.include_namespaces,
+            &self.exclude_namespaces,
+            Path::new(&path),
+        )
+    }
+
+    pub fn is_visibility_included(&self, visibility: Option<VisibilityKind>) -> bool {
+        self.show_private
+            || matches!(
+                visibility,
+                None | Some(VisibilityKind::None | VisibilityKind::Public)
+            )
+    }
+
+    fn is_api_visibility_included(&self, visibility: &ApiVisibility) -> bool {
+        self.show_private || *visibility == ApiVisibility::Public
+    }
+
+    /// Removes the excluded items and members from the model
+    pub fn filter_model(&self, model: &mut ApiModel) {
+        model.classes.retain(|class| {
+            self.is_name_included(&class.full_name)
+                && class
+                    .locations
+                    .iter()
+                    .any(|location| self.is_file_included(&location.file))
+        });
+        model.enums.retain(|e| {
+            self.is_name_included(&e.full_name)
+                && e.locations
+                    .iter()
+                    .any(|location| self.is_file_included(&location.file))
+        });
+        model.aliases.retain(|alias| {
+            self.is_name_included(&alias.full_name)
+                && alias
+                    .locations
+                    .iter()
+                    .any(|location| self.is_file_included(&location.file))
+        });
+        model.modules.retain(|module| {
+            self.is_name_included(&module.name) && self.is_file_included(&module.file)
+        });
+        model
+            .globals
+            .retain(|global| self.is_file_included(&global.location.file));
+
+        for class in &mut model.classes {
+            self.filter_members(&mut class.members);
+        }
+        for e in &mut model.enums {
+            self.filter_members(&mut e.members);
+        }
+        for module in &mut model.modules {
+            self.filter_members(&mut module.members);
+        }
+        for global in &mut model.globals {
+            self.filter_members(&mut global.members);
+        }
+    }
+
+    fn filter_members(&self, members: &mut Vec<ApiMember>) {
+        members.retain(|member| {
+            self.is_api_visibility_included(&member.property.visibility)
+                && self.is_file_included(&member.location.file)
+        });
+    }
+}
+
+fn is_included(include: &[Glob], exclude: &[Glob], path: &Path) -> bool {
+    !exclude.iter().any(|glob| glob.is_match(path))
+        && (include.is_empty() || include.iter().any(|glob| glob.is_match(path)))
+}
+
+fn compile_globs(patterns: &[String]) -> Vec<Glob<'static>> {
+    patterns
+        .iter()
+        .filter_map(|pattern| match Glob::new(pattern) {
+            Ok(glob) => Some(glob.into_owned()),
+            Err(e) => {
+                eprintln!("Invalid glob {}: {}", pattern, e);
+                None
+            }
+        })
+        .collect()
+}
+
+// the parts of the names are matched as the parts of the paths
+fn compile_namespace_globs(patterns: &[String]) -> Vec<Glob<'static>> {
+    let patterns = patterns
+        .iter()
+        .map(|pattern| pattern.replace('.', "/"))
+        .collect::<Vec<_>>();
+    compile_globs(&patterns)
+}
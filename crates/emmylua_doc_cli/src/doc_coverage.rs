@@ -0,0 +1,279 @@
+use std::path::Path;
+
+use emmylua_code_analysis::EmmyLuaAnalysis;
+
+use crate::api_model::{
+    collect_api_model, ApiFunction, ApiLocation, ApiMember, ApiModel, ApiProperty, ApiType,
+    ApiVisibility,
+};
+
+#[derive(Debug)]
+struct MissingDoc {
+    location: ApiLocation,
+    name: String,
+    // eg: `a description`, `the @param a tag`, `a description for @return 1`
+    what: String,
+}
+
+/// The documented items of a class, an enum, an alias, a module or a global
+#[derive(Debug)]
+struct DocGroup {
+    kind: &'static str,
+    name: String,
+    documented: usize,
+    total: usize,
+    missing: Vec<MissingDoc>,
+}
+
+impl DocGroup {
+    fn new(kind: &'static str, name: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            documented: 0,
+            total: 0,
+            missing: Vec::new(),
+        }
+    }
+
+    fn check(&mut self, is_documented: bool, location: &ApiLocation, name: &str, what: String) {
+        self.total += 1;
+        if is_documented {
+            self.documented += 1;
+        } else {
+            self.missing.push(MissingDoc {
+                location: location.clone(),
+                name: name.to_string(),
+                what,
+            });
+        }
+    }
+
+    fn check_property(&mut self, property: &ApiProperty, location: &ApiLocation, name: &str) {
+        self.check(
+            property.description.is_some(),
+            location,
+            name,
+            "a description".to_string(),
+        );
+    }
+
+    // the params and the returns of the function need their own descriptions
+    fn check_function(&mut self, func: &ApiFunction, location: &ApiLocation, name: &str) {
+        for param in &func.params {
+            if param.name == "self" {
+                continue;
+            }
+
+            let what = if !param.has_tag {
+                format!("the @param {} tag", param.name)
+            } else {
+                format!("a description for @param {}", param.name)
+            };
+            self.check(param.description.is_some(), location, name, what);
+        }
+
+        for (idx, ret) in func.returns.iter().enumerate() {
+            // the inferred return of a function without the return statement
+            if ret.name.is_none()
+                && matches!(&ret.typ, ApiType::Primitive { name } if name == "nil")
+            {
+                continue;
+            }
+
+            let ret_name = ret.name.clone().unwrap_or((idx + 1).to_string());
+            let what = if !ret.has_tag {
+                format!("the @return {} tag", ret_name)
+            } else {
+                format!("a description for @return {}", ret_name)
+            };
+            self.check(ret.description.is_some(), location, name, what);
+        }
+    }
+
+    fn check_members(&mut self, owner_name: &str, members: &[ApiMember]) {
+        for member in members {
+            if !is_api_visibility(&member.property) {
+                continue;
+            }
+
+            let name = format!("{}.{}", owner_name, member.name);
+            self.check_property(&member.property, &member.location, &name);
+            if let ApiType::Function(func) = &member.typ {
+                self.check_function(func, &member.location, &name);
+            }
+        }
+    }
+
+    fn get_percent(&self) -> f64 {
+        get_percent(self.documented, self.total)
+    }
+}
+
+/// The documentation coverage of the public api, the private and the package members are not counted
+#[derive(Debug)]
+pub struct DocCoverageReport {
+    groups: Vec<DocGroup>,
+}
+
+impl DocCoverageReport {
+    pub fn collect(analysis: &EmmyLuaAnalysis, input: &Path) -> Self {
+        let db = analysis.compilation.get_db();
+        let model = collect_api_model(db, input);
+        let mut groups = Vec::new();
+        collect_type_groups(&model, &mut groups);
+        collect_module_groups(&model, &mut groups);
+        collect_global_groups(&model, &mut groups);
+
+        Self {
+            groups: groups.into_iter().filter(|group| group.total > 0).collect(),
+        }
+    }
+
+    pub fn get_total_percent(&self) -> f64 {
+        let (documented, total) = self.get_total();
+        get_percent(documented, total)
+    }
+
+    fn get_total(&self) -> (usize, usize) {
+        self.groups
+            .iter()
+            .fold((0, 0), |(documented, total), group| {
+                (documented + group.documented, total + group.total)
+            })
+    }
+
+    pub fn print(&self) {
+        let mut lines = Vec::new();
+        lines.push(format!(
+            "{:<48} {:>8} {:>12}",
+            "item", "coverage", "documented"
+        ));
+        for group in &self.groups {
+            lines.push(format!(
+                "{:<48} {:>7.1}% {:>12}",
+                format!("{} {}", group.kind, group.name),
+                group.get_percent(),
+                format!("{}/{}", group.documented, group.total)
+            ));
+        }
+
+        let missing = self
+            .groups
+            .iter()
+            .flat_map(|group| group.missing.iter())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            lines.push(String::new());
+            lines.push("Missing documentation:".to_string());
+            for doc in missing {
+                lines.push(format!(
+                    "{}:{}:{}: {} lacks {}",
+                    doc.location.file, doc.location.line, doc.location.col, doc.name, doc.what
+                ));
+            }
+        }
+
+        lines.push(String::new());
+        let (documented, total) = self.get_total();
+        lines.push(format!(
+            "Documentation coverage: {:.2}% ({} of {} documented)",
+            get_percent(documented, total),
+            documented,
+            total
+        ));
+        println!("{}", lines.join("\n"));
+    }
+}
+
+fn collect_type_groups(model: &ApiModel, groups: &mut Vec<DocGroup>) {
+    for class in &model.classes {
+        if !is_api_visibility(&class.property) {
+            continue;
+        }
+
+        let mut group = DocGroup::new("class", &class.full_name);
+        if let Some(location) = class.locations.first() {
+            group.check_property(&class.property, location, &class.full_name);
+        }
+        group.check_members(&class.full_name, &class.members);
+        groups.push(group);
+    }
+
+    for e in &model.enums {
+        let mut group = DocGroup::new("enum", &e.full_name);
+        if let Some(location) = e.locations.first() {
+            group.check_property(&e.property, location, &e.full_name);
+        }
+        group.check_members(&e.full_name, &e.members);
+        groups.push(group);
+    }
+
+    for alias in &model.aliases {
+        let mut group = DocGroup::new("alias", &alias.full_name);
+        if let Some(location) = alias.locations.first() {
+            group.check_property(&alias.property, location, &alias.full_name);
+        }
+        groups.push(group);
+    }
+}
+
+// only the exports are the api of the module, the exported classes are counted as the classes
+fn collect_module_groups(model: &ApiModel, groups: &mut Vec<DocGroup>) {
+    for module in &model.modules {
+        let Some(export) = &module.export else {
+            continue;
+        };
+
+        let mut group = DocGroup::new("module", &module.name);
+        let location = ApiLocation {
+            file: module.file.clone(),
+            line: 1,
+            col: 1,
+        };
+        if let ApiType::Function(func) = export {
+            group.check_property(&module.property, &location, &module.name);
+            group.check_function(func, &location, &module.name);
+        }
+        group.check_members(&module.name, &module.members);
+        groups.push(group);
+    }
+}
+
+fn collect_global_groups(model: &ApiModel, groups: &mut Vec<DocGroup>) {
+    for global in &model.globals {
+        // the global table of a class is counted as the class
+        if let ApiType::Ref { name } = &global.typ {
+            if model.classes.iter().any(|class| &class.full_name == name) {
+                continue;
+            }
+        }
+
+        if !is_api_visibility(&global.property) {
+            continue;
+        }
+
+        let mut group = DocGroup::new("global", &global.name);
+        group.check_property(&global.property, &global.location, &global.name);
+        if let ApiType::Function(func) = &global.typ {
+            group.check_function(func, &global.location, &global.name);
+        }
+        group.check_members(&global.name, &global.members);
+        groups.push(group);
+    }
+}
+
+fn is_api_visibility(property: &ApiProperty) -> bool {
+    matches!(
+        property.visibility,
+        ApiVisibility::Public | ApiVisibility::Protected
+    )
+}
+
+fn get_percent(documented: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        documented as f64 * 100.0 / total as f64
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use emmylua_code_analysis::{DbIndex, EmmyLuaAnalysis, LuaSemanticDeclId, LuaType};
+use serde::Serialize;
+
+use crate::{
+    api_diff::{find_semantic_decl, ApiChangeGroup, ApiDiffReport, ApiPath},
+    cmd_args::Format,
+    doc_config::DocConfig,
+    html_generator, init, markdown_generator,
+};
+
+/// The version of the versioned docs, the docs of every version are in `<output>/<label>`
+#[derive(Debug, Clone, Serialize)]
+pub struct DocVersionPage {
+    pub label: String,
+    /// The labels of all versions, the newest first
+    pub versions: Vec<String>,
+    /// The version before this one, the oldest version has none
+    pub previous: Option<String>,
+    /// The changes of the api since the previous version
+    pub changes: Vec<ApiChangeGroup>,
+}
+
+struct DocVersion {
+    label: String,
+    input: PathBuf,
+    analysis: EmmyLuaAnalysis,
+}
+
+/// Parses the `label=path` of `--versions`, the input is the newest version
+pub fn parse_doc_versions(
+    versions: &[String],
+    input: PathBuf,
+    input_label: &str,
+) -> Option<Vec<(String, PathBuf)>> {
+    let mut result = Vec::new();
+    for version in versions {
+        let Some((label, path)) = version.split_once('=') else {
+            eprintln!("The version `{}` must be in the form `label=path`", version);
+            return None;
+        };
+
+        let mut path = PathBuf::from(path);
+        if path.is_relative() {
+            path = std::env::current_dir().ok()?.join(&path);
+        }
+        if !path.is_dir() {
+            eprintln!(
+                "The path {:?} of the version `{}` is not a directory",
+                path, label
+            );
+            return None;
+        }
+        result.push((label.to_string(), path));
+    }
+    result.push((input_label.to_string(), input));
+
+    let mut labels = HashSet::new();
+    for (label, _) in &result {
+        if label.is_empty() || !labels.insert(label.as_str()) {
+            eprintln!("The version label `{}` must be unique and not empty", label);
+            return None;
+        }
+    }
+
+    Some(result)
+}
+
+/// Generates the markdown or the html docs of every version, the versions are from the oldest
+/// to the newest. The items without `@since` get the version in which they appeared.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_versioned_docs(
+    versions: Vec<(String, PathBuf)>,
+    format: Format,
+    output: PathBuf,
+    override_template: Option<PathBuf>,
+    mixin: Option<PathBuf>,
+    source_url: Option<String>,
+    config: &DocConfig,
+) -> Option<()> {
+    if !matches!(format, Format::Markdown | Format::Html) {
+        eprintln!("The versioned docs are only generated as markdown or html");
+        return None;
+    }
+
+    let mut doc_versions = Vec::new();
+    for (label, input) in versions {
+        println!("Loading version {}: {:?}", label, input);
+        let analysis = init::load_workspace(vec![input.to_str()?])?;
+        doc_versions.push(DocVersion {
+            label,
+            input,
+            analysis,
+        });
+    }
+
+    let labels = doc_versions
+        .iter()
+        .rev()
+        .map(|version| version.label.clone())
+        .collect::<Vec<_>>();
+    // the items which were added after the oldest version, the key is the name of the item
+    let mut added_items: HashMap<String, (ApiPath, String)> = HashMap::new();
+    let mut pages = Vec::new();
+    for idx in 0..doc_versions.len() {
+        let mut page = DocVersionPage {
+            label: doc_versions[idx].label.clone(),
+            versions: labels.clone(),
+            previous: None,
+            changes: Vec::new(),
+        };
+        if idx > 0 {
+            let old = &doc_versions[idx - 1];
+            let new = &doc_versions[idx];
+            let report =
+                ApiDiffReport::collect(&old.analysis, &old.input, &new.analysis, &new.input);
+            for name in report.get_removed_names() {
+                added_items.remove(name);
+            }
+            for (name, path) in report.get_added() {
+                added_items.insert(name.clone(), (path.clone(), new.label.clone()));
+            }
+
+            page.previous = Some(old.label.clone());
+            page.changes = report.get_change_groups();
+        }
+
+        let db = doc_versions[idx].analysis.compilation.get_db_mut();
+        for (path, since) in added_items.values() {
+            add_computed_since(db, path, since);
+        }
+        pages.push(page);
+    }
+
+    for (version, page) in doc_versions.iter_mut().zip(pages) {
+        let version_output = output.join(&version.label);
+        match format {
+            Format::Markdown => {
+                markdown_generator::generate_markdown(
+                    &mut version.analysis,
+                    version.input.clone(),
+                    version_output,
+                    override_template.clone(),
+                    mixin.clone(),
+                    config,
+                    Some(&page),
+                );
+            }
+            Format::Html => {
+                html_generator::generate_html(
+                    &version.analysis,
+                    version.input.clone(),
+                    version_output,
+                    override_template.clone(),
+                    source_url.clone(),
+                    config,
+                    Some(&page),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if format == Format::Html {
+        html_generator::generate_version_redirect(&output, labels.first()?)?;
+    }
+
+    Some(())
+}
+
+// the explicit `@since` is kept
+fn add_computed_since(db: &mut DbIndex, path: &ApiPath, since: &str) -> Option<()> {
+    let (file_id, semantic_decl) = find_semantic_decl(db, path)?;
+    if has_since(db, &semantic_decl) {
+        return None;
+    }
+
+    db.get_property_index_mut()
+        .add_since(file_id, semantic_decl, since.to_string())
+}
+
+// the tags before a function statement are owned by the signature
+fn has_since(db: &DbIndex, semantic_decl: &LuaSemanticDeclId) -> bool {
+    let property_index = db.get_property_index();
+    if property_index
+        .get_property(semantic_decl)
+        .is_some_and(|property| property.since.is_some())
+    {
+        return true;
+    }
+
+    let type_cache = match semantic_decl {
+        LuaSemanticDeclId::Member(member_id) => {
+            db.get_type_index().get_type_cache(&(*member_id).into())
+        }
+        LuaSemanticDeclId::LuaDecl(decl_id) => {
+            db.get_type_index().get_type_cache(&(*decl_id).into())
+        }
+        _ => None,
+    };
+    match type_cache.map(|cache| cache.as_type()) {
+        Some(LuaType::Signature(signature_id)) => property_index
+            .get_property(&LuaSemanticDeclId::Signature(*signature_id))
+            .is_some_and(|property| property.since.is_some()),
+        _ => false,
+    }
+}
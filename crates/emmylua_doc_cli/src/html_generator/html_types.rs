@@ -0,0 +1,69 @@
+use serde::Serialize;
+
+use crate::doc_versions::DocVersionPage;
+
+/// The page of a class, an enum, an alias, a module or a global
+#[derive(Debug, Serialize, Default)]
+pub struct HtmlItem {
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    /// html, eg: `class Dog: Animal` with the links of the types
+    pub signature: Option<String>,
+    pub property: HtmlProperty,
+    /// html
+    pub sources: Vec<String>,
+    pub methods: Vec<HtmlMember>,
+    pub fields: Vec<HtmlMember>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HtmlMember {
+    pub name: String,
+    pub anchor: String,
+    /// html
+    pub signature: String,
+    pub property: HtmlProperty,
+    /// html
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct HtmlProperty {
+    pub description: Option<String>,
+    pub deprecated: Option<String>,
+    pub versions: Option<String>,
+    pub since: Option<String>,
+    pub see: Option<String>,
+    pub other: Option<String>,
+    /// The code of the examples without the fences
+    pub examples: Vec<String>,
+    /// Only the members which are not public have it
+    pub visibility: Option<String>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct HtmlIndex {
+    pub types: Vec<HtmlLink>,
+    pub modules: Vec<HtmlLink>,
+    pub globals: Vec<HtmlLink>,
+    /// The version switcher of the versioned docs
+    pub version: Option<DocVersionPage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HtmlLink {
+    pub kind: String,
+    pub name: String,
+    pub url: String,
+    pub summary: Option<String>,
+}
+
+/// The entry of the client-side search index
+#[derive(Debug, Serialize)]
+pub struct SearchEntry {
+    pub name: String,
+    pub kind: String,
+    pub url: String,
+    pub summary: Option<String>,
+}
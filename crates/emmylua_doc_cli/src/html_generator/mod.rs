@@ -0,0 +1,483 @@
+mod html_types;
+mod render;
+mod sidebar;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use emmylua_code_analysis::EmmyLuaAnalysis;
+use html_types::{HtmlIndex, HtmlItem, HtmlLink, HtmlMember, HtmlProperty, SearchEntry};
+use include_dir::{include_dir, Dir};
+use render::{escape_html, render_source_link, TypeRenderer};
+use sidebar::{render_sidebar, SidebarTree};
+use tera::Tera;
+
+use crate::{
+    api_model::{
+        collect_api_model, ApiMember, ApiModel, ApiProperty, ApiType, ApiVersionOp, ApiVisibility,
+    },
+    doc_config::{DocConfig, DocFilter},
+    doc_versions::DocVersionPage,
+    markdown_generator::escape_type_name,
+};
+
+static HTML_TEMPLATE_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/template/html");
+
+/// Writes a static html site which needs no other tool, the pages are in the output directory
+pub fn generate_html(
+    analysis: &EmmyLuaAnalysis,
+    input: PathBuf,
+    output: PathBuf,
+    override_template: Option<PathBuf>,
+    source_url: Option<String>,
+    config: &DocConfig,
+    version: Option<&DocVersionPage>,
+) -> Option<()> {
+    let db = analysis.compilation.get_db();
+    let mut model = collect_api_model(db, &input);
+    DocFilter::new(config).filter_model(&mut model);
+    let (tl, assets) = init_html_tl(override_template)?;
+
+    if !output.exists() {
+        println!("Creating output directory: {:?}", output);
+        std::fs::create_dir_all(&output).ok()?;
+    }
+
+    let mut type_pages = HashMap::new();
+    for full_name in model
+        .classes
+        .iter()
+        .map(|class| &class.full_name)
+        .chain(model.enums.iter().map(|e| &e.full_name))
+        .chain(model.aliases.iter().map(|alias| &alias.full_name))
+    {
+        type_pages.insert(full_name.clone(), get_page_name("type", full_name));
+    }
+
+    let site = HtmlSite {
+        tl,
+        output,
+        sidebar: build_sidebar(&model, &type_pages),
+        source_url,
+    };
+    let renderer = TypeRenderer::new(&type_pages);
+    let mut index = HtmlIndex {
+        version: version.cloned(),
+        ..Default::default()
+    };
+    let mut search_entries = Vec::new();
+    for item in collect_items(&model, &renderer, site.source_url.as_deref()) {
+        let url = match item.kind.as_str() {
+            "module" => get_page_name("module", &item.name),
+            "global" => get_page_name("global", &item.name),
+            _ => get_page_name("type", &item.name),
+        };
+        add_search_entries(&mut search_entries, &item, &url);
+        let link = HtmlLink {
+            kind: item.kind.clone(),
+            name: item.name.clone(),
+            url: url.clone(),
+            summary: get_summary(&item.property),
+        };
+        match item.kind.as_str() {
+            "module" => index.modules.push(link),
+            "global" => index.globals.push(link),
+            _ => index.types.push(link),
+        }
+
+        site.render_page("item.html", &item.name, &url, "item", &item);
+    }
+
+    site.render_page("index.html", "index", "index.html", "index", &index);
+    if let Some(version) = version.filter(|version| version.previous.is_some()) {
+        site.render_page(
+            "changes.html",
+            "changes",
+            "changes.html",
+            "version",
+            version,
+        );
+    }
+    let search_index = serde_json::to_string(&search_entries).ok()?;
+    site.write_file(
+        "search-index.js",
+        &format!("window.SEARCH_INDEX = {};\n", search_index),
+    );
+    for (name, content) in assets {
+        site.write_file(&name, &content);
+    }
+
+    Some(())
+}
+
+/// The index of the versioned docs opens the newest version
+pub fn generate_version_redirect(output: &Path, newest: &str) -> Option<()> {
+    let url = format!("{}/index.html", newest);
+    let content = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n    <meta charset=\"utf-8\">\n    <meta http-equiv=\"refresh\" content=\"0; url={}\">\n</head>\n<body>\n    <a href=\"{}\">{}</a>\n</body>\n</html>\n",
+        url, url, newest
+    );
+    std::fs::write(output.join("index.html"), content).ok()
+}
+
+struct HtmlSite {
+    tl: Tera,
+    output: PathBuf,
+    sidebar: String,
+    source_url: Option<String>,
+}
+
+impl HtmlSite {
+    fn render_page<T: serde::Serialize>(
+        &self,
+        template_name: &str,
+        title: &str,
+        file_name: &str,
+        key: &str,
+        value: &T,
+    ) -> Option<()> {
+        let mut context = tera::Context::new();
+        context.insert("title", title);
+        context.insert("sidebar", &self.sidebar);
+        context.insert(key, value);
+        let render_text = match self.tl.render(template_name, &context) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("Failed to render template: {}", e);
+                return None;
+            }
+        };
+
+        self.write_file(file_name, &render_text)
+    }
+
+    fn write_file(&self, file_name: &str, content: &str) -> Option<()> {
+        let outpath = self.output.join(file_name);
+        println!("output html file: {}", outpath.display());
+        match std::fs::write(outpath, content) {
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Failed to write file: {}", e);
+                return None;
+            }
+        }
+        Some(())
+    }
+}
+
+// the `.html` files are the templates, the other files are copied to the output
+fn init_html_tl(override_template: Option<PathBuf>) -> Option<(Tera, Vec<(String, String)>)> {
+    let mut files: HashMap<String, String> = HTML_TEMPLATE_DIR
+        .files()
+        .map(|file| {
+            let path = file.path().to_string_lossy().into_owned();
+            let content = file.contents_utf8().unwrap().to_string();
+            (path, content)
+        })
+        .collect();
+
+    if let Some(override_template) = override_template {
+        let override_dir = override_template.join("html");
+        if override_dir.is_dir() {
+            for entry in walkdir::WalkDir::new(&override_dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+            {
+                let path = entry.path();
+                let content = std::fs::read_to_string(path).expect("Failed to read file");
+                let template_path = path.file_name().unwrap().to_str().unwrap().to_string();
+                files.insert(template_path, content);
+            }
+        }
+    }
+
+    let (templates, assets): (Vec<_>, Vec<_>) = files
+        .into_iter()
+        .partition(|(path, _)| path.ends_with(".html"));
+    let mut tera = Tera::default();
+    match tera.add_raw_templates(templates) {
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Failed to add templates: {}", e);
+            return None;
+        }
+    }
+
+    Some((tera, assets))
+}
+
+fn get_page_name(kind: &str, name: &str) -> String {
+    format!("{}.{}.html", kind, escape_type_name(name))
+}
+
+fn build_sidebar(model: &ApiModel, type_pages: &HashMap<String, String>) -> String {
+    let mut types = SidebarTree::default();
+    for (full_name, url) in type_pages {
+        types.add(full_name, url.clone());
+    }
+
+    let mut modules = SidebarTree::default();
+    for module in &model.modules {
+        modules.add(&module.name, get_page_name("module", &module.name));
+    }
+
+    let mut globals = SidebarTree::default();
+    for global in &model.globals {
+        globals.add_leaf(&global.name, get_page_name("global", &global.name));
+    }
+
+    render_sidebar(&[
+        ("Types", &types),
+        ("Modules", &modules),
+        ("Globals", &globals),
+    ])
+}
+
+fn collect_items(
+    model: &ApiModel,
+    renderer: &TypeRenderer,
+    source_url: Option<&str>,
+) -> Vec<HtmlItem> {
+    let mut items = Vec::new();
+    for class in &model.classes {
+        let mut signature = format!("class {}", escape_html(&class.full_name));
+        if !class.generics.is_empty() {
+            let generics = class
+                .generics
+                .iter()
+                .map(|generic| escape_html(&generic.name))
+                .collect::<Vec<_>>();
+            signature.push_str(&format!("&lt;{}&gt;", generics.join(", ")));
+        }
+        if !class.supers.is_empty() {
+            let supers = class
+                .supers
+                .iter()
+                .map(|typ| renderer.render_type(typ))
+                .collect::<Vec<_>>();
+            signature.push_str(&format!(": {}", supers.join(", ")));
+        }
+
+        let mut item = HtmlItem {
+            kind: "class".to_string(),
+            name: class.full_name.clone(),
+            namespace: class.namespace.clone(),
+            signature: Some(signature),
+            property: to_html_property(&class.property),
+            sources: class
+                .locations
+                .iter()
+                .map(|location| render_source_link(source_url, location))
+                .collect(),
+            ..Default::default()
+        };
+        add_members(&mut item, &class.name, &class.members, renderer, source_url);
+        items.push(item);
+    }
+
+    for e in &model.enums {
+        let mut signature = format!("enum {}", escape_html(&e.full_name));
+        if let Some(base) = &e.base {
+            signature.push_str(&format!(": {}", renderer.render_type(base)));
+        }
+
+        let mut item = HtmlItem {
+            kind: "enum".to_string(),
+            name: e.full_name.clone(),
+            namespace: e.namespace.clone(),
+            signature: Some(signature),
+            property: to_html_property(&e.property),
+            sources: e
+                .locations
+                .iter()
+                .map(|location| render_source_link(source_url, location))
+                .collect(),
+            ..Default::default()
+        };
+        add_members(&mut item, &e.name, &e.members, renderer, source_url);
+        items.push(item);
+    }
+
+    for alias in &model.aliases {
+        let origin = match &alias.origin {
+            Some(origin) => renderer.render_type(origin),
+            None => "unknown".to_string(),
+        };
+        items.push(HtmlItem {
+            kind: "alias".to_string(),
+            name: alias.full_name.clone(),
+            namespace: alias.namespace.clone(),
+            signature: Some(format!(
+                "alias {} = {}",
+                escape_html(&alias.full_name),
+                origin
+            )),
+            property: to_html_property(&alias.property),
+            sources: alias
+                .locations
+                .iter()
+                .map(|location| render_source_link(source_url, location))
+                .collect(),
+            ..Default::default()
+        });
+    }
+
+    for module in &model.modules {
+        let mut item = HtmlItem {
+            kind: "module".to_string(),
+            name: module.name.clone(),
+            property: to_html_property(&module.property),
+            sources: vec![escape_html(&module.file)],
+            ..Default::default()
+        };
+        // the table exports are listed by their members
+        if let Some(export) = &module.export {
+            if module.members.is_empty() {
+                item.signature = Some(format!("return {}", renderer.render_type(export)));
+            }
+        }
+        add_members(&mut item, "M", &module.members, renderer, source_url);
+        items.push(item);
+    }
+
+    for global in &model.globals {
+        let signature = match &global.typ {
+            ApiType::Function(func) => renderer.render_function("", &global.name, func),
+            typ => format!(
+                "{}: {}",
+                escape_html(&global.name),
+                renderer.render_type(typ)
+            ),
+        };
+        let mut item = HtmlItem {
+            kind: "global".to_string(),
+            name: global.name.clone(),
+            signature: Some(signature),
+            property: to_html_property(&global.property),
+            sources: vec![render_source_link(source_url, &global.location)],
+            ..Default::default()
+        };
+        add_members(
+            &mut item,
+            &global.name,
+            &global.members,
+            renderer,
+            source_url,
+        );
+        items.push(item);
+    }
+
+    items
+}
+
+fn add_members(
+    item: &mut HtmlItem,
+    owner_name: &str,
+    members: &[ApiMember],
+    renderer: &TypeRenderer,
+    source_url: Option<&str>,
+) {
+    for member in members {
+        let html_member = |signature: String| HtmlMember {
+            name: member.name.clone(),
+            anchor: format!("member-{}", member.name),
+            signature,
+            property: to_html_property(&member.property),
+            source: render_source_link(source_url, &member.location),
+        };
+        match &member.typ {
+            ApiType::Function(func) => item.methods.push(html_member(renderer.render_function(
+                owner_name,
+                &member.name,
+                func,
+            ))),
+            typ => item.fields.push(html_member(format!(
+                "{}.{}: {}",
+                escape_html(owner_name),
+                escape_html(&member.name),
+                renderer.render_type(typ)
+            ))),
+        }
+    }
+}
+
+fn to_html_property(property: &ApiProperty) -> HtmlProperty {
+    let versions = property
+        .versions
+        .iter()
+        .map(|cond| match cond.op {
+            ApiVersionOp::Eq => cond.version.clone(),
+            ApiVersionOp::Gte => format!(">= {}", cond.version),
+            ApiVersionOp::Lte => format!("<= {}", cond.version),
+        })
+        .collect::<Vec<_>>();
+
+    HtmlProperty {
+        description: property.description.clone(),
+        deprecated: property.deprecated.as_ref().map(|deprecated| {
+            deprecated
+                .message
+                .clone()
+                .unwrap_or("Deprecated".to_string())
+        }),
+        versions: (!versions.is_empty()).then(|| versions.join(", ")),
+        since: property.since.clone(),
+        see: property.see.clone(),
+        other: property.other.clone(),
+        examples: property
+            .examples
+            .iter()
+            .map(|example| get_example_code(example))
+            .collect(),
+        visibility: match property.visibility {
+            ApiVisibility::Public => None,
+            ApiVisibility::Protected => Some("protected".to_string()),
+            ApiVisibility::Private => Some("private".to_string()),
+            ApiVisibility::Internal => Some("internal".to_string()),
+            ApiVisibility::Package => Some("package".to_string()),
+        },
+    }
+}
+
+// the fences are not shown, the text out of the code blocks is kept
+fn get_example_code(example: &str) -> String {
+    example
+        .lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            !line.starts_with("```") && !line.starts_with("~~~")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn get_summary(property: &HtmlProperty) -> Option<String> {
+    let description = property.description.as_ref()?;
+    description
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.to_string())
+}
+
+fn add_search_entries(search_entries: &mut Vec<SearchEntry>, item: &HtmlItem, url: &str) {
+    search_entries.push(SearchEntry {
+        name: item.name.clone(),
+        kind: item.kind.clone(),
+        url: url.to_string(),
+        summary: get_summary(&item.property),
+    });
+    for (kind, members) in [("method", &item.methods), ("field", &item.fields)] {
+        for member in members {
+            search_entries.push(SearchEntry {
+                name: format!("{}.{}", item.name, member.name),
+                kind: kind.to_string(),
+                url: format!("{}#{}", url, member.anchor),
+                summary: get_summary(&member.property),
+            });
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use crate::api_model::{ApiFunction, ApiLocation, ApiType};
+
+/// Renders the types as html, the names of the documented types link to their pages
+pub struct TypeRenderer<'a> {
+    type_pages: &'a HashMap<String, String>,
+}
+
+impl<'a> TypeRenderer<'a> {
+    pub fn new(type_pages: &'a HashMap<String, String>) -> Self {
+        Self { type_pages }
+    }
+
+    pub fn render_type(&self, typ: &ApiType) -> String {
+        match typ {
+            ApiType::Primitive { name } => format!("<span class=\"primitive\">{}</span>", name),
+            ApiType::Literal { value } => format!(
+                "<span class=\"literal\">{}</span>",
+                escape_html(&value.to_string())
+            ),
+            ApiType::Ref { name } => self.render_type_name(name),
+            ApiType::Array { element } => {
+                let element_text = self.render_type(element);
+                if matches!(
+                    element.as_ref(),
+                    ApiType::Union { .. } | ApiType::Intersection { .. } | ApiType::Function(_)
+                ) {
+                    format!("({})[]", element_text)
+                } else {
+                    format!("{}[]", element_text)
+                }
+            }
+            ApiType::Tuple { types } => format!("[{}]", self.render_types(types, ", ")),
+            ApiType::Function(func) => {
+                format!(
+                    "fun({}){}",
+                    self.render_params(func),
+                    self.render_returns(func)
+                )
+            }
+            ApiType::Object {
+                fields,
+                index_access,
+            } => {
+                let mut items = fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}: {}",
+                            escape_html(&field.name),
+                            self.render_type(&field.typ)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                items.extend(index_access.iter().map(|access| {
+                    format!(
+                        "[{}]: {}",
+                        self.render_type(&access.key),
+                        self.render_type(&access.value)
+                    )
+                }));
+                format!("{{ {} }}", items.join(", "))
+            }
+            ApiType::Union { types } => self.render_types(types, " | "),
+            ApiType::Intersection { types } => self.render_types(types, " &amp; "),
+            ApiType::Generic { name, params } => format!(
+                "{}&lt;{}&gt;",
+                self.render_type_name(name),
+                self.render_types(params, ", ")
+            ),
+            ApiType::TableGeneric { params } => {
+                format!("table&lt;{}&gt;", self.render_types(params, ", "))
+            }
+            ApiType::TemplateRef { name } => {
+                format!("<span class=\"template\">{}</span>", escape_html(name))
+            }
+            ApiType::StringTemplateRef {
+                prefix,
+                name,
+                suffix,
+            } => format!(
+                "{}`<span class=\"template\">{}</span>`{}",
+                escape_html(prefix),
+                escape_html(name),
+                escape_html(suffix)
+            ),
+            ApiType::Variadic { typ } => format!("{}...", self.render_type(typ)),
+            ApiType::MultiReturn { types } => format!("({})", self.render_types(types, ", ")),
+            ApiType::Namespace { name } => format!("namespace {}", escape_html(name)),
+            ApiType::Call { call, operands } => {
+                format!(
+                    "{} {}",
+                    escape_html(call),
+                    self.render_types(operands, ", ")
+                )
+            }
+        }
+    }
+
+    /// eg: `function Animal:speak(times?: integer) -> string`
+    pub fn render_function(&self, owner_name: &str, name: &str, func: &ApiFunction) -> String {
+        let mut result = String::new();
+        if func.is_async {
+            result.push_str("async ");
+        }
+        result.push_str("function ");
+        if !owner_name.is_empty() {
+            result.push_str(&escape_html(owner_name));
+            result.push_str(if func.is_method { ":" } else { "." });
+        }
+        result.push_str(&escape_html(name));
+        if !func.generics.is_empty() {
+            let generics = func
+                .generics
+                .iter()
+                .map(|generic| match &generic.constraint {
+                    Some(constraint) => format!(
+                        "{}: {}",
+                        escape_html(&generic.name),
+                        self.render_type(constraint)
+                    ),
+                    None => escape_html(&generic.name),
+                })
+                .collect::<Vec<_>>();
+            result.push_str(&format!("&lt;{}&gt;", generics.join(", ")));
+        }
+        result.push_str(&format!("({})", self.render_params(func)));
+        result.push_str(&self.render_returns(func));
+        result
+    }
+
+    fn render_params(&self, func: &ApiFunction) -> String {
+        func.params
+            .iter()
+            .map(|param| {
+                let optional = if param.optional && param.name != "..." {
+                    "?"
+                } else {
+                    ""
+                };
+                match &param.typ {
+                    Some(typ) => format!(
+                        "{}{}: {}",
+                        escape_html(&param.name),
+                        optional,
+                        self.render_type(typ)
+                    ),
+                    None => escape_html(&param.name),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn render_returns(&self, func: &ApiFunction) -> String {
+        if func.returns.is_empty() {
+            return String::new();
+        }
+
+        let returns = func
+            .returns
+            .iter()
+            .map(|ret| match &ret.name {
+                Some(name) => format!("{}: {}", escape_html(name), self.render_type(&ret.typ)),
+                None => self.render_type(&ret.typ),
+            })
+            .collect::<Vec<_>>();
+        format!(" -&gt; {}", returns.join(", "))
+    }
+
+    fn render_types(&self, types: &[ApiType], separator: &str) -> String {
+        types
+            .iter()
+            .map(|typ| self.render_type(typ))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn render_type_name(&self, name: &str) -> String {
+        match self.type_pages.get(name) {
+            Some(page) => format!(
+                "<a class=\"type-link\" href=\"{}\">{}</a>",
+                escape_html(page),
+                escape_html(name)
+            ),
+            None => format!("<span class=\"type-name\">{}</span>", escape_html(name)),
+        }
+    }
+}
+
+/// The link of the source location, it is plain text when there is no source url
+pub fn render_source_link(source_url: Option<&str>, location: &ApiLocation) -> String {
+    let text = format!("{}:{}", location.file, location.line);
+    match source_url {
+        Some(source_url) => format!(
+            "<a class=\"source-link\" href=\"{}/{}#L{}\">{}</a>",
+            escape_html(source_url.trim_end_matches('/')),
+            escape_html(&location.file),
+            location.line,
+            escape_html(&text)
+        ),
+        None => format!("<span class=\"source-link\">{}</span>", escape_html(&text)),
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
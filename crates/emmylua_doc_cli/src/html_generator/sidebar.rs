@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use super::render::escape_html;
+
+/// The tree of the sidebar, the names are split by `.` into the namespaces
+#[derive(Debug, Default)]
+pub struct SidebarTree {
+    children: BTreeMap<String, SidebarTree>,
+    url: Option<String>,
+}
+
+impl SidebarTree {
+    pub fn add(&mut self, full_name: &str, url: String) {
+        let mut node = self;
+        for part in full_name.split('.') {
+            node = node.children.entry(part.to_string()).or_default();
+        }
+        node.url = Some(url);
+    }
+
+    /// Adds the name without splitting it
+    pub fn add_leaf(&mut self, name: &str, url: String) {
+        self.children.entry(name.to_string()).or_default().url = Some(url);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    pub fn render(&self) -> String {
+        let mut result = String::new();
+        self.render_children(&mut result);
+        result
+    }
+
+    fn render_children(&self, result: &mut String) {
+        result.push_str("<ul>");
+        for (name, child) in &self.children {
+            result.push_str("<li>");
+            let label = match &child.url {
+                Some(url) => format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(name)),
+                None => escape_html(name),
+            };
+            if child.children.is_empty() {
+                result.push_str(&label);
+            } else {
+                result.push_str("<details open><summary>");
+                result.push_str(&label);
+                result.push_str("</summary>");
+                child.render_children(result);
+                result.push_str("</details>");
+            }
+            result.push_str("</li>");
+        }
+        result.push_str("</ul>");
+    }
+}
+
+/// Renders the sidebar sections of the types, the modules and the globals
+pub fn render_sidebar(sections: &[(&str, &SidebarTree)]) -> String {
+    let mut result = String::new();
+    for (title, tree) in sections {
+        if tree.is_empty() {
+            continue;
+        }
+
+        result.push_str(&format!(
+            "<section><h3>{}</h3>{}</section>",
+            escape_html(title),
+            tree.render()
+        ));
+    }
+    result
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use emmylua_code_analysis::EmmyLuaAnalysis;
+
+use crate::api_model::collect_api_model;
+
+/// Writes the api model of the workspace to `api.json` in the output directory
+pub fn generate_json(analysis: &EmmyLuaAnalysis, input: PathBuf, output: PathBuf) -> Option<()> {
+    let db = analysis.compilation.get_db();
+    let model = collect_api_model(db, &input);
+    let content = match serde_json::to_string_pretty(&model) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Failed to serialize api model: {}", e);
+            return None;
+        }
+    };
+
+    if !output.exists() {
+        println!("Creating output directory: {:?}", output);
+        std::fs::create_dir_all(&output).ok()?;
+    }
+
+    let outpath = output.join("api.json");
+    println!("output api file: {}", outpath.display());
+    match std::fs::write(outpath, content) {
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Failed to write file: {}", e);
+            return None;
+        }
+    }
+    Some(())
+}
@@ -1,9 +1,18 @@
-use cmd_args::CmdArgs;
+use cmd_args::{CmdArgs, Format};
 use structopt::StructOpt;
 
+mod api_diff;
+mod api_model;
 mod cmd_args;
+mod doc_config;
+mod doc_coverage;
+mod doc_versions;
+mod html_generator;
 mod init;
+mod json_generator;
 mod markdown_generator;
+mod stub_generator;
+mod teal_generator;
 
 fn main() {
     let args = CmdArgs::from_args();
@@ -12,14 +21,114 @@ fn main() {
         input = std::env::current_dir().ok().unwrap().join(&input);
     }
 
-    let analysis = init::load_workspace(vec![input.to_str().unwrap()]);
-    if let Some(mut analysis) = analysis {
-        markdown_generator::generate_markdown(
-            &mut analysis,
-            input,
+    let Some(config) = doc_config::load_doc_config(args.config.as_deref()) else {
+        std::process::exit(1);
+    };
+
+    if !args.versions.is_empty() {
+        let Some(versions) =
+            doc_versions::parse_doc_versions(&args.versions, input, &args.version_label)
+        else {
+            std::process::exit(1);
+        };
+        if doc_versions::generate_versioned_docs(
+            versions,
+            args.format,
             args.output,
             args.override_template,
             args.mixin,
-        );
+            args.source_url,
+            &config,
+        )
+        .is_none()
+        {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let analysis = init::load_workspace(vec![input.to_str().unwrap()]);
+    if let Some(mut analysis) = analysis {
+        match args.format {
+            Format::Markdown => {
+                markdown_generator::generate_markdown(
+                    &mut analysis,
+                    input,
+                    args.output,
+                    args.override_template,
+                    args.mixin,
+                    &config,
+                    None,
+                );
+            }
+            Format::Json => {
+                if json_generator::generate_json(&analysis, input, args.output).is_none() {
+                    std::process::exit(1);
+                }
+            }
+            Format::Html => {
+                html_generator::generate_html(
+                    &analysis,
+                    input,
+                    args.output,
+                    args.override_template,
+                    args.source_url,
+                    &config,
+                    None,
+                );
+            }
+            Format::Stubs => {
+                if stub_generator::generate_stubs(&analysis, input, args.output).is_none() {
+                    std::process::exit(1);
+                }
+            }
+            Format::Teal => {
+                if teal_generator::generate_teal(&analysis, input, args.output).is_none() {
+                    std::process::exit(1);
+                }
+            }
+            Format::Coverage => {
+                let report = doc_coverage::DocCoverageReport::collect(&analysis, &input);
+                report.print();
+                if let Some(min_coverage) = args.min_coverage {
+                    let percent = report.get_total_percent();
+                    if percent < min_coverage {
+                        eprintln!(
+                            "Documentation coverage {:.2}% is lower than the minimum of {:.2}%",
+                            percent, min_coverage
+                        );
+                        std::process::exit(1);
+                    }
+                }
+            }
+            Format::Diff => {
+                let Some(mut old_input) = args.compare else {
+                    eprintln!("The old version is required by `--compare`");
+                    std::process::exit(1);
+                };
+                if old_input.is_relative() {
+                    old_input = std::env::current_dir().ok().unwrap().join(&old_input);
+                }
+                if !old_input.is_dir() {
+                    eprintln!(
+                        "The old version {:?} of `--compare` is not a directory",
+                        old_input
+                    );
+                    std::process::exit(1);
+                }
+
+                let Some(old_analysis) = init::load_workspace(vec![old_input.to_str().unwrap()])
+                else {
+                    eprintln!("Failed to load the old version {:?}", old_input);
+                    std::process::exit(1);
+                };
+                let report =
+                    api_diff::ApiDiffReport::collect(&old_analysis, &old_input, &analysis, &input);
+                report.print();
+                if report.get_level() == api_diff::ChangeLevel::Major {
+                    std::process::exit(1);
+                }
+            }
+        }
     }
 }
@@ -6,23 +6,28 @@ use emmylua_code_analysis::{
 };
 use tera::Tera;
 
-use crate::markdown_generator::{
-    escape_type_name,
-    gen::mod_gen::generate_member_owner_module,
-    markdown_types::{Doc, IndexStruct, MkdocsIndex},
-    render::{render_const_type, render_function_type},
+use crate::{
+    doc_config::DocFilter,
+    markdown_generator::{
+        escape_type_name,
+        gen::mod_gen::generate_member_owner_module,
+        markdown_types::{Doc, IndexStruct, MkdocsIndex},
+        render::{render_const_type, render_function_type},
+    },
 };
 
-use super::collect_property;
+use super::{collect_property, get_file_directory, is_file_included};
 
 pub fn generate_global_markdown(
     db: &DbIndex,
     tl: &Tera,
     decl_id: &LuaDeclId,
+    input: &Path,
+    filter: &DocFilter,
     output: &Path,
     mkdocs_index: &mut MkdocsIndex,
 ) -> Option<()> {
-    check_filter(db, decl_id)?;
+    check_filter(db, decl_id, input, filter)?;
 
     let mut context = tera::Context::new();
     let mut doc = Doc::default();
@@ -39,7 +44,7 @@ pub fn generate_global_markdown(
     match decl_type.as_type() {
         LuaType::TableConst(table) => {
             let member_owner = LuaMemberOwner::Element(table.clone());
-            generate_member_owner_module(db, member_owner, name, &mut doc)?;
+            generate_member_owner_module(db, member_owner, name, input, filter, &mut doc)?;
         }
         _ => {
             template_name = "lua_global_template_simple.tl";
@@ -60,6 +65,8 @@ pub fn generate_global_markdown(
     mkdocs_index.globals.push(IndexStruct {
         name: decl.get_name().to_string(),
         file: format!("globals/{}", file_name.clone()),
+        namespace: String::new(),
+        directory: get_file_directory(db, input, decl_id.file_id),
     });
 
     let outpath = output.join(file_name);
@@ -74,10 +81,10 @@ pub fn generate_global_markdown(
     Some(())
 }
 
-fn check_filter(db: &DbIndex, decl_id: &LuaDeclId) -> Option<()> {
+fn check_filter(db: &DbIndex, decl_id: &LuaDeclId, input: &Path, filter: &DocFilter) -> Option<()> {
     let file_id = decl_id.file_id;
     let module = db.get_module_index().get_module(file_id)?;
-    if !module.workspace_id.is_main() {
+    if !module.workspace_id.is_main() || !is_file_included(db, filter, input, file_id) {
         return None;
     };
     let decl_type = db
@@ -1,10 +1,18 @@
+use std::collections::BTreeMap;
+
 use tera::Tera;
 
-use crate::markdown_generator::markdown_types::MkdocsIndex;
+use crate::{
+    doc_config::{DocConfig, DocGroupBy},
+    doc_versions::DocVersionPage,
+    markdown_generator::markdown_types::{IndexGroup, IndexStruct, MkdocsIndex},
+};
 
 pub fn generate_index(
     tl: &Tera,
     mkdocs: &mut MkdocsIndex,
+    config: &DocConfig,
+    version: Option<&DocVersionPage>,
     output: &std::path::PathBuf,
 ) -> Option<()> {
     let mut context = tera::Context::new();
@@ -21,6 +29,40 @@ pub fn generate_index(
     if !mkdocs.globals.is_empty() {
         context.insert("globals", &mkdocs.globals);
     }
+    if config.group_by != DocGroupBy::None {
+        for (key, items) in [
+            ("type_groups", &mkdocs.types),
+            ("module_groups", &mkdocs.modules),
+            ("global_groups", &mkdocs.globals),
+        ] {
+            if !items.is_empty() {
+                context.insert(key, &group_index(items, config.group_by));
+            }
+        }
+    }
+    if let Some(entry_module) = &config.entry_module {
+        match mkdocs
+            .modules
+            .iter()
+            .find(|module| &module.name == entry_module)
+        {
+            Some(entry) => context.insert("entry", entry),
+            None => eprintln!("The entry module {} is not found", entry_module),
+        }
+    }
+    if let Some(version) = version {
+        context.insert("version", version);
+        if version.previous.is_some() {
+            let changes_text = match tl.render("changes_template.tl", &context) {
+                Ok(text) => text,
+                Err(e) => {
+                    eprintln!("Failed to render changes: {}", e);
+                    return None;
+                }
+            };
+            std::fs::write(output.join("docs/changes.md"), changes_text).ok()?;
+        }
+    }
     let index_path = output.join("docs/index.md");
     let index_text = match tl.render("index_template.tl", &context) {
         Ok(text) => text,
@@ -45,3 +87,27 @@ pub fn generate_index(
 
     Some(())
 }
+
+// the items at the root are in the group with the empty name
+fn group_index(items: &[IndexStruct], group_by: DocGroupBy) -> Vec<IndexGroup> {
+    let mut groups: BTreeMap<String, Vec<IndexStruct>> = BTreeMap::new();
+    for item in items {
+        let name = match group_by {
+            DocGroupBy::Directory => &item.directory,
+            _ => &item.namespace,
+        };
+        groups.entry(name.clone()).or_default().push(item.clone());
+    }
+
+    groups
+        .into_iter()
+        .map(|(name, items)| IndexGroup {
+            name: if name.is_empty() {
+                "(root)".to_string()
+            } else {
+                name
+            },
+            items,
+        })
+        .collect()
+}
@@ -3,13 +3,17 @@ mod index_gen;
 mod mod_gen;
 mod typ_gen;
 
-use emmylua_code_analysis::{DbIndex, LuaSemanticDeclId};
+use std::path::Path;
+
+use emmylua_code_analysis::{DbIndex, FileId, LuaMember, LuaSemanticDeclId};
+use emmylua_parser::VisibilityKind;
 pub use global_gen::generate_global_markdown;
 pub use index_gen::generate_index;
 pub use mod_gen::generate_module_markdown;
 pub use typ_gen::generate_type_markdown;
 
 use super::markdown_types::Property;
+use crate::{api_model::get_relative_path, doc_config::DocFilter};
 
 fn collect_property(db: &DbIndex, semantic_decl: LuaSemanticDeclId) -> Property {
     let mut doc_property = Property::default();
@@ -30,10 +34,48 @@ fn collect_property(db: &DbIndex, semantic_decl: LuaSemanticDeclId) -> Property
                 doc_property.deprecated = Some("Deprecated".to_string());
             }
         }
+        doc_property.since = property.since.clone();
         if let Some(other) = property.other_content.clone() {
             doc_property.other = Some(other.to_string());
         }
+        if let Some(examples) = &property.examples {
+            doc_property.examples = examples.to_vec();
+        }
+        doc_property.visibility = match property.visibility {
+            Some(VisibilityKind::Protected) => Some("protected".to_string()),
+            Some(VisibilityKind::Private) => Some("private".to_string()),
+            Some(VisibilityKind::Internal) => Some("internal".to_string()),
+            Some(VisibilityKind::Package) => Some("package".to_string()),
+            _ => None,
+        };
     }
 
     doc_property
 }
+
+fn is_member_included(db: &DbIndex, filter: &DocFilter, input: &Path, member: &LuaMember) -> bool {
+    let visibility = db
+        .get_property_index()
+        .get_property(&LuaSemanticDeclId::Member(member.get_id()))
+        .and_then(|property| property.visibility);
+    filter.is_visibility_included(visibility)
+        && is_file_included(db, filter, input, member.get_file_id())
+}
+
+fn is_file_included(db: &DbIndex, filter: &DocFilter, input: &Path, file_id: FileId) -> bool {
+    db.get_vfs()
+        .get_file_path(&file_id)
+        .is_some_and(|path| filter.is_file_included(&get_relative_path(input, path)))
+}
+
+// the directory of the file relative to the input, it is empty for the root
+fn get_file_directory(db: &DbIndex, input: &Path, file_id: FileId) -> String {
+    let Some(path) = db.get_vfs().get_file_path(&file_id) else {
+        return String::new();
+    };
+    let relative_path = get_relative_path(input, path);
+    match relative_path.rsplit_once('/') {
+        Some((directory, _)) => directory.to_string(),
+        None => String::new(),
+    }
+}
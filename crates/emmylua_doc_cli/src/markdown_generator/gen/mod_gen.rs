@@ -1,28 +1,32 @@
 use std::path::Path;
 
 use emmylua_code_analysis::{
-    humanize_type, DbIndex, FileId, LuaMemberKey, LuaMemberOwner, LuaSemanticDeclId, LuaType,
-    LuaTypeCache, ModuleInfo, RenderLevel,
+    humanize_type, DbIndex, LuaMemberKey, LuaMemberOwner, LuaSemanticDeclId, LuaType, LuaTypeCache,
+    ModuleInfo, RenderLevel,
 };
-use emmylua_parser::VisibilityKind;
 use tera::Tera;
 
-use crate::markdown_generator::{
-    escape_type_name,
-    markdown_types::{Doc, IndexStruct, MemberDoc, MkdocsIndex},
-    render::{render_const_type, render_function_type},
+use crate::{
+    doc_config::DocFilter,
+    markdown_generator::{
+        escape_type_name,
+        markdown_types::{Doc, IndexStruct, MemberDoc, MkdocsIndex},
+        render::{render_const_type, render_function_type},
+    },
 };
 
-use super::collect_property;
+use super::{collect_property, get_file_directory, is_file_included, is_member_included};
 
 pub fn generate_module_markdown(
     db: &DbIndex,
     tl: &Tera,
     module: &ModuleInfo,
+    input: &Path,
+    filter: &DocFilter,
     output: &Path,
     mkdocs_index: &mut MkdocsIndex,
 ) -> Option<()> {
-    check_filter(db, module.file_id)?;
+    check_filter(db, module, input, filter)?;
 
     let mut context = tera::Context::new();
     let mut doc = Doc::default();
@@ -37,15 +41,22 @@ pub fn generate_module_markdown(
         LuaType::Def(type_id) => {
             let member_owner = LuaMemberOwner::Type(type_id.clone());
             let type_simple_name = type_id.get_simple_name();
-            generate_member_owner_module(db, member_owner, type_simple_name, &mut doc);
+            generate_member_owner_module(
+                db,
+                member_owner,
+                type_simple_name,
+                input,
+                filter,
+                &mut doc,
+            );
         }
         LuaType::TableConst(t) => {
             let member_owner = LuaMemberOwner::Element(t.clone());
-            generate_member_owner_module(db, member_owner, "M", &mut doc);
+            generate_member_owner_module(db, member_owner, "M", input, filter, &mut doc);
         }
         LuaType::Instance(i) => {
             let member_owner = LuaMemberOwner::Element(i.get_range().clone());
-            generate_member_owner_module(db, member_owner, "M", &mut doc);
+            generate_member_owner_module(db, member_owner, "M", input, filter, &mut doc);
         }
         _ => {}
     }
@@ -64,6 +75,11 @@ pub fn generate_module_markdown(
     mkdocs_index.modules.push(IndexStruct {
         name: module.full_module_name.clone(),
         file: format!("modules/{}", file_name.clone()),
+        namespace: match module.full_module_name.rsplit_once('.') {
+            Some((parent, _)) => parent.to_string(),
+            None => String::new(),
+        },
+        directory: get_file_directory(db, input, module.file_id),
     });
 
     let outpath = output.join(file_name);
@@ -78,9 +94,11 @@ pub fn generate_module_markdown(
     Some(())
 }
 
-fn check_filter(db: &DbIndex, file_id: FileId) -> Option<()> {
-    let module = db.get_module_index().get_module(file_id)?;
-    if module.workspace_id.is_main() {
+fn check_filter(db: &DbIndex, module: &ModuleInfo, input: &Path, filter: &DocFilter) -> Option<()> {
+    if module.workspace_id.is_main()
+        && filter.is_name_included(&module.full_module_name)
+        && is_file_included(db, filter, input, module.file_id)
+    {
         return Some(());
     }
 
@@ -91,6 +109,8 @@ pub fn generate_member_owner_module(
     db: &DbIndex,
     member_owner: LuaMemberOwner,
     owner_name: &str,
+    input: &Path,
+    filter: &DocFilter,
     doc: &mut Doc,
 ) -> Option<()> {
     let members = db.get_member_index().get_sorted_members(&member_owner);
@@ -104,16 +124,12 @@ pub fn generate_member_owner_module(
                 .get_type_cache(&member.get_id().into())
                 .unwrap_or(&LuaTypeCache::InferType(LuaType::Unknown))
                 .as_type();
+            if !is_member_included(db, filter, input, member) {
+                continue;
+            }
+
             let member_id = member.get_id();
             let member_property_id = LuaSemanticDeclId::Member(member_id.clone());
-            let member_property = db.get_property_index().get_property(&member_property_id);
-            if let Some(member_property) = member_property {
-                if member_property.visibility.unwrap_or(VisibilityKind::Public)
-                    != VisibilityKind::Public
-                {
-                    continue;
-                }
-            }
 
             let member_property = collect_property(db, member_property_id);
             let member_key = member.get_key();
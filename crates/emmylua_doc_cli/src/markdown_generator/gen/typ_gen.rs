@@ -4,45 +4,67 @@ use emmylua_code_analysis::{
     humanize_type, DbIndex, LuaMemberKey, LuaMemberOwner, LuaSemanticDeclId, LuaType, LuaTypeCache,
     LuaTypeDecl, RenderLevel,
 };
-use emmylua_parser::VisibilityKind;
 use tera::{Context, Tera};
 
-use crate::markdown_generator::{
-    escape_type_name,
-    gen::collect_property,
-    markdown_types::{Doc, IndexStruct, MemberDoc, MkdocsIndex},
-    render::{render_const_type, render_function_type},
+use crate::{
+    doc_config::DocFilter,
+    markdown_generator::{
+        escape_type_name,
+        gen::{collect_property, get_file_directory, is_file_included, is_member_included},
+        markdown_types::{Doc, IndexStruct, MemberDoc, MkdocsIndex},
+        render::{render_const_type, render_function_type},
+    },
 };
 
 pub fn generate_type_markdown(
     db: &DbIndex,
     tl: &Tera,
     typ: &LuaTypeDecl,
+    input: &Path,
+    filter: &DocFilter,
     output: &Path,
     mkdocs_index: &mut MkdocsIndex,
 ) -> Option<()> {
-    check_filter(db, typ)?;
+    check_filter(db, typ, input, filter)?;
     let mut context = tera::Context::new();
     let typ_name = typ.get_name();
     let mut doc = Doc::default();
     doc.name = typ_name.to_string();
 
+    let mut index = IndexStruct {
+        name: String::new(),
+        file: format!("types/{}.md", escape_type_name(typ.get_full_name())),
+        namespace: typ.get_namespace().unwrap_or_default().to_string(),
+        directory: typ
+            .get_locations()
+            .first()
+            .map(|location| get_file_directory(db, input, location.file_id))
+            .unwrap_or_default(),
+    };
     if typ.is_class() {
-        generate_class_type_markdown(db, tl, typ, &mut doc, &mut context, output, mkdocs_index);
+        index.name = format!("class {}", typ_name);
+        generate_class_type_markdown(db, tl, typ, input, filter, &mut doc, &mut context, output)?;
     } else if typ.is_enum() {
-        generate_enum_type_markdown(db, tl, typ, &mut doc, &mut context, output, mkdocs_index);
+        index.name = format!("enum {}", typ_name);
+        generate_enum_type_markdown(db, tl, typ, input, filter, &mut doc, &mut context, output)?;
     } else {
-        generate_alias_type_markdown(db, tl, typ, &mut doc, &mut context, output, mkdocs_index);
+        index.name = format!("alias {}", typ_name);
+        generate_alias_type_markdown(db, tl, typ, &mut doc, &mut context, output)?;
     }
+    mkdocs_index.types.push(index);
     Some(())
 }
 
-fn check_filter(db: &DbIndex, typ: &LuaTypeDecl) -> Option<()> {
+fn check_filter(db: &DbIndex, typ: &LuaTypeDecl, input: &Path, filter: &DocFilter) -> Option<()> {
+    if !filter.is_name_included(typ.get_full_name()) {
+        return None;
+    }
+
     let location = typ.get_locations();
     for loc in location {
         let file_id = loc.file_id;
         let module = db.get_module_index().get_module(file_id)?;
-        if module.workspace_id.is_main() {
+        if module.workspace_id.is_main() && is_file_included(db, filter, input, file_id) {
             return Some(());
         }
     }
@@ -50,14 +72,16 @@ fn check_filter(db: &DbIndex, typ: &LuaTypeDecl) -> Option<()> {
     None
 }
 
+#[allow(clippy::too_many_arguments)]
 fn generate_class_type_markdown(
     db: &DbIndex,
     tl: &Tera,
     typ: &LuaTypeDecl,
+    input: &Path,
+    filter: &DocFilter,
     doc: &mut Doc,
     context: &mut Context,
     output: &Path,
-    mkdocs_index: &mut MkdocsIndex,
 ) -> Option<()> {
     let typ_name = typ.get_name();
     let typ_id = typ.get_id();
@@ -90,16 +114,12 @@ fn generate_class_type_markdown(
                 .get_type_cache(&member.get_id().into())
                 .unwrap_or(&LuaTypeCache::InferType(LuaType::Unknown))
                 .as_type();
+            if !is_member_included(db, filter, input, member) {
+                continue;
+            }
+
             let member_id = member.get_id();
             let member_property_id = LuaSemanticDeclId::Member(member_id.clone());
-            let member_property = db.get_property_index().get_property(&member_property_id);
-            if let Some(member_property) = member_property {
-                if member_property.visibility.unwrap_or(VisibilityKind::Public)
-                    != VisibilityKind::Public
-                {
-                    continue;
-                }
-            }
 
             let member_property = collect_property(db, member_property_id);
 
@@ -157,10 +177,6 @@ fn generate_class_type_markdown(
     };
 
     let file_type_name = format!("{}.md", escape_type_name(typ.get_full_name()));
-    mkdocs_index.types.push(IndexStruct {
-        name: format!("class {}", typ_name),
-        file: format!("types/{}", file_type_name.clone()),
-    });
 
     let outpath = output.join(file_type_name);
     println!("output class file: {}", outpath.display());
@@ -174,16 +190,17 @@ fn generate_class_type_markdown(
     Some(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn generate_enum_type_markdown(
     db: &DbIndex,
     tl: &Tera,
     typ: &LuaTypeDecl,
+    input: &Path,
+    filter: &DocFilter,
     doc: &mut Doc,
     context: &mut Context,
     output: &Path,
-    mkdocs_index: &mut MkdocsIndex,
 ) -> Option<()> {
-    let typ_name = typ.get_name();
     let typ_id = typ.get_id();
     let namespace = typ.get_namespace();
     if let Some(namespace) = namespace {
@@ -201,16 +218,12 @@ fn generate_enum_type_markdown(
                 .get_type_cache(&member.get_id().into())
                 .unwrap_or(&LuaTypeCache::InferType(LuaType::Unknown))
                 .as_type();
+            if !is_member_included(db, filter, input, member) {
+                continue;
+            }
+
             let member_id = member.get_id();
             let member_property_id = LuaSemanticDeclId::Member(member_id.clone());
-            let member_property = db.get_property_index().get_property(&member_property_id);
-            if let Some(member_property) = member_property {
-                if member_property.visibility.unwrap_or(VisibilityKind::Public)
-                    != VisibilityKind::Public
-                {
-                    continue;
-                }
-            }
 
             let member_property = collect_property(db, member_property_id);
 
@@ -243,10 +256,6 @@ fn generate_enum_type_markdown(
     };
 
     let file_type_name = format!("{}.md", escape_type_name(typ.get_full_name()));
-    mkdocs_index.types.push(IndexStruct {
-        name: format!("enum {}", typ_name),
-        file: format!("types/{}", file_type_name.clone()),
-    });
 
     let outpath = output.join(file_type_name);
     println!("output enum file: {}", outpath.display());
@@ -267,7 +276,6 @@ fn generate_alias_type_markdown(
     doc: &mut Doc,
     context: &mut Context,
     output: &Path,
-    mkdocs_index: &mut MkdocsIndex,
 ) -> Option<()> {
     let typ_name = typ.get_name();
     let typ_id = typ.get_id();
@@ -299,10 +307,6 @@ fn generate_alias_type_markdown(
     };
 
     let file_type_name = format!("{}.md", escape_type_name(typ.get_full_name()));
-    mkdocs_index.types.push(IndexStruct {
-        name: format!("alias {}", typ_name),
-        file: format!("types/{}", file_type_name.clone()),
-    });
 
     let outpath = output.join(file_type_name);
     println!("output alias file: {}", outpath.display());
@@ -23,7 +23,11 @@ pub struct Property {
     pub description: Option<String>,
     pub see: Option<String>,
     pub deprecated: Option<String>,
+    pub since: Option<String>,
     pub other: Option<String>,
+    pub examples: Vec<String>,
+    /// Only the members which are not public have it
+    pub visibility: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -33,8 +37,20 @@ pub struct MkdocsIndex {
     pub globals: Vec<IndexStruct>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexStruct {
     pub name: String,
     pub file: String,
+    /// The group of the item when the index is grouped by namespace
+    #[serde(skip)]
+    pub namespace: String,
+    /// The group of the item when the index is grouped by directory
+    #[serde(skip)]
+    pub directory: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexGroup {
+    pub name: String,
+    pub items: Vec<IndexStruct>,
 }
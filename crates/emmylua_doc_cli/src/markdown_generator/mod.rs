@@ -12,6 +12,11 @@ use gen::{
 };
 use markdown_types::MkdocsIndex;
 
+use crate::{
+    doc_config::{DocConfig, DocFilter},
+    doc_versions::DocVersionPage,
+};
+
 #[allow(unused)]
 pub fn generate_markdown(
     analysis: &mut EmmyLuaAnalysis,
@@ -19,6 +24,8 @@ pub fn generate_markdown(
     output: PathBuf,
     override_template: Option<PathBuf>,
     mixin: Option<PathBuf>,
+    config: &DocConfig,
+    version: Option<&DocVersionPage>,
 ) -> Option<()> {
     let docs_dir = output.join("docs");
     let types_out = docs_dir.join("types");
@@ -52,27 +59,52 @@ pub fn generate_markdown(
     }
 
     let tl = init_tl::init_tl(override_template)?;
+    let filter = DocFilter::new(config);
     let mut mkdocs_index = MkdocsIndex::default();
     let db = analysis.compilation.get_db();
     let type_index = db.get_type_index();
     let types = type_index.get_all_types();
     for type_decl in types {
-        generate_type_markdown(db, &tl, type_decl, &types_out, &mut mkdocs_index);
+        generate_type_markdown(
+            db,
+            &tl,
+            type_decl,
+            &input,
+            &filter,
+            &types_out,
+            &mut mkdocs_index,
+        );
     }
 
     let module_index = db.get_module_index();
     let modules = module_index.get_module_infos();
     for module in modules {
-        generate_module_markdown(db, &tl, module, &module_out, &mut mkdocs_index);
+        generate_module_markdown(
+            db,
+            &tl,
+            module,
+            &input,
+            &filter,
+            &module_out,
+            &mut mkdocs_index,
+        );
     }
 
     let global_index = db.get_global_index();
     let globals = global_index.get_all_global_decl_ids();
     for global_decl_id in globals {
-        generate_global_markdown(db, &tl, &global_decl_id, &global_out, &mut mkdocs_index);
+        generate_global_markdown(
+            db,
+            &tl,
+            &global_decl_id,
+            &input,
+            &filter,
+            &global_out,
+            &mut mkdocs_index,
+        );
     }
 
-    generate_index(&tl, &mut mkdocs_index, &output);
+    generate_index(&tl, &mut mkdocs_index, config, version, &output);
 
     if let Some(mixin) = mixin {
         mixin_copy::mixin_copy(&output, mixin);
@@ -81,7 +113,7 @@ pub fn generate_markdown(
     Some(())
 }
 
-fn escape_type_name(name: &str) -> String {
+pub fn escape_type_name(name: &str) -> String {
     name.chars()
         .map(|c| {
             // Windows Invalid Characters
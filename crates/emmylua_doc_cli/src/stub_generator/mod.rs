@@ -0,0 +1,480 @@
+mod render;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
+
+use emmylua_code_analysis::EmmyLuaAnalysis;
+pub use render::render_type;
+use render::{render_function_type, render_param_type};
+
+use crate::api_model::{
+    collect_api_model, ApiAlias, ApiClass, ApiEnum, ApiFunction, ApiGlobal, ApiMember, ApiModel,
+    ApiModule, ApiProperty, ApiType, ApiVersionOp, ApiVisibility,
+};
+
+/// Writes a `---@meta` definition file for every file of the workspace which declares an api, the
+/// files keep their paths relative to the input directory
+pub fn generate_stubs(analysis: &EmmyLuaAnalysis, input: PathBuf, output: PathBuf) -> Option<()> {
+    let db = analysis.compilation.get_db();
+    let model = collect_api_model(db, &input);
+
+    for (file, content) in collect_stub_files(&model) {
+        let outpath = output.join(&file);
+        if let Some(parent) = outpath.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent).ok()?;
+            }
+        }
+
+        println!("output stub file: {}", outpath.display());
+        match std::fs::write(outpath, content) {
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Failed to write file: {}", e);
+                return None;
+            }
+        }
+    }
+
+    Some(())
+}
+
+#[derive(Default)]
+struct StubFile<'a> {
+    module: Option<&'a ApiModule>,
+    // the full names and the variables of the classes in the file
+    class_vars: HashMap<&'a str, String>,
+    // the items are sorted by their lines in the source file
+    items: Vec<(usize, String)>,
+}
+
+fn collect_stub_files(model: &ApiModel) -> BTreeMap<String, String> {
+    let mut files: HashMap<&str, StubFile> = HashMap::new();
+    for module in &model.modules {
+        files.entry(&module.file).or_default().module = Some(module);
+    }
+
+    // the globals which hold a class of the same file are written as the class table
+    let mut class_globals = HashMap::new();
+    for global in &model.globals {
+        if let ApiType::Ref { name } = &global.typ {
+            class_globals.insert((global.location.file.as_str(), name.as_str()), global);
+        }
+    }
+
+    let mut global_classes = Vec::new();
+    for class in &model.classes {
+        let Some(location) = class.locations.first() else {
+            continue;
+        };
+        let global = class_globals.get(&(location.file.as_str(), class.full_name.as_str()));
+        if let Some(global) = global {
+            global_classes.push(global.name.as_str());
+        }
+        let var_name = match global {
+            Some(global) => global.name.clone(),
+            None => format!("local {}", class.name),
+        };
+        let stub_file = files.entry(&location.file).or_default();
+        stub_file
+            .items
+            .push((location.line, write_class(class, &var_name)));
+        stub_file.class_vars.insert(
+            &class.full_name,
+            var_name.trim_start_matches("local ").to_string(),
+        );
+    }
+
+    for e in &model.enums {
+        if let Some(location) = e.locations.first() {
+            files
+                .entry(&location.file)
+                .or_default()
+                .items
+                .push((location.line, write_enum(e)));
+        }
+    }
+
+    for alias in &model.aliases {
+        if let Some(location) = alias.locations.first() {
+            files
+                .entry(&location.file)
+                .or_default()
+                .items
+                .push((location.line, write_alias(alias)));
+        }
+    }
+
+    for global in &model.globals {
+        if global_classes.contains(&global.name.as_str()) {
+            continue;
+        }
+
+        files
+            .entry(&global.location.file)
+            .or_default()
+            .items
+            .push((global.location.line, write_global(global)));
+    }
+
+    let mut result = BTreeMap::new();
+    for (file, mut stub_file) in files {
+        let mut content = match stub_file.module {
+            Some(module) => format!("---@meta {}\n", module.name),
+            None => "---@meta\n".to_string(),
+        };
+        stub_file.items.sort_by_key(|(line, _)| *line);
+        for (_, item) in &stub_file.items {
+            content.push('\n');
+            content.push_str(item);
+        }
+
+        if let Some(export) = stub_file
+            .module
+            .and_then(|module| write_module_export(module, &stub_file.class_vars))
+        {
+            content.push('\n');
+            content.push_str(&export);
+        }
+        result.insert(file.to_string(), content);
+    }
+
+    result
+}
+
+fn write_class(class: &ApiClass, var_name: &str) -> String {
+    let mut result = String::new();
+    write_property(&mut result, &class.property);
+    result.push_str(&format!("---@class {}", class.full_name));
+    if !class.generics.is_empty() {
+        let generics = class
+            .generics
+            .iter()
+            .map(|generic| match &generic.constraint {
+                Some(constraint) => format!("{}: {}", generic.name, render_type(constraint)),
+                None => generic.name.clone(),
+            })
+            .collect::<Vec<_>>();
+        result.push_str(&format!("<{}>", generics.join(", ")));
+    }
+    if !class.supers.is_empty() {
+        let supers = class.supers.iter().map(render_type).collect::<Vec<_>>();
+        result.push_str(&format!(": {}", supers.join(", ")));
+    }
+    result.push('\n');
+
+    let (fields, methods) = split_members(&class.members);
+    for field in fields {
+        write_field(&mut result, field);
+    }
+    result.push_str(&format!("{} = {{}}\n", var_name));
+
+    let owner_name = var_name.trim_start_matches("local ");
+    for (method, func) in methods {
+        result.push('\n');
+        write_function(
+            &mut result,
+            &method.property,
+            owner_name,
+            &method.name,
+            func,
+        );
+    }
+    result
+}
+
+fn write_enum(e: &ApiEnum) -> String {
+    let mut result = String::new();
+    write_property(&mut result, &e.property);
+    if e.is_key {
+        result.push_str(&format!("---@enum (key) {}\n", e.full_name));
+    } else {
+        result.push_str(&format!("---@enum {}\n", e.full_name));
+    }
+    result.push_str(&format!("local {} = {{\n", e.name));
+    for member in &e.members {
+        let value = match &member.typ {
+            ApiType::Literal { value } => value.to_string(),
+            _ => "nil".to_string(),
+        };
+        result.push_str(&format!("    {} = {},\n", member.name, value));
+    }
+    result.push_str("}\n");
+    result
+}
+
+fn write_alias(alias: &ApiAlias) -> String {
+    let mut result = String::new();
+    write_property(&mut result, &alias.property);
+    result.push_str(&format!("---@alias {}", alias.full_name));
+    if !alias.generics.is_empty() {
+        let generics = alias
+            .generics
+            .iter()
+            .map(|generic| generic.name.clone())
+            .collect::<Vec<_>>();
+        result.push_str(&format!("<{}>", generics.join(", ")));
+    }
+    match &alias.origin {
+        Some(origin) => result.push_str(&format!(" {}\n", render_type(origin))),
+        None => result.push_str(" any\n"),
+    }
+    result
+}
+
+fn write_global(global: &ApiGlobal) -> String {
+    let mut result = String::new();
+    match &global.typ {
+        ApiType::Function(func) => {
+            write_function(&mut result, &global.property, "", &global.name, func);
+        }
+        _ if !global.members.is_empty() => {
+            write_property(&mut result, &global.property);
+            write_table(&mut result, &global.name, &global.name, &global.members);
+        }
+        typ => {
+            write_property(&mut result, &global.property);
+            result.push_str(&format!("---@type {}\n", render_type(typ)));
+            result.push_str(&format!("{} = nil\n", global.name));
+        }
+    }
+    result
+}
+
+fn write_module_export(module: &ApiModule, class_vars: &HashMap<&str, String>) -> Option<String> {
+    let export = module.export.as_ref()?;
+    // the class of the file is returned by its variable
+    if let ApiType::Ref { name } = export {
+        if let Some(var_name) = class_vars.get(name.as_str()) {
+            return Some(format!("return {}\n", var_name));
+        }
+    }
+
+    let mut result = String::new();
+    if !module.members.is_empty() {
+        write_table(&mut result, "local M", "M", &module.members);
+    } else {
+        result.push_str(&format!("---@type {}\n", render_type(export)));
+        result.push_str("local M\n");
+    }
+    result.push_str("\nreturn M\n");
+    Some(result)
+}
+
+fn write_table(result: &mut String, var_name: &str, owner_name: &str, members: &[ApiMember]) {
+    result.push_str(&format!("{} = {{}}\n", var_name));
+    let (fields, methods) = split_members(members);
+    for field in fields {
+        result.push('\n');
+        write_property(result, &field.property);
+        result.push_str(&format!("---@type {}\n", render_type(&field.typ)));
+        if is_identifier(&field.name) {
+            result.push_str(&format!("{}.{} = nil\n", owner_name, field.name));
+        } else if field.name.starts_with('[') {
+            result.push_str(&format!("{}{} = nil\n", owner_name, field.name));
+        } else {
+            result.push_str(&format!("{}[\"{}\"] = nil\n", owner_name, field.name));
+        }
+    }
+
+    for (method, func) in methods {
+        result.push('\n');
+        write_function(result, &method.property, owner_name, &method.name, func);
+    }
+}
+
+// the functions which are not declared by `---@field` are written as the function statements
+fn split_members(members: &[ApiMember]) -> (Vec<&ApiMember>, Vec<(&ApiMember, &ApiFunction)>) {
+    let mut fields = Vec::new();
+    let mut methods = Vec::new();
+    for member in members {
+        match &member.typ {
+            ApiType::Function(func) if !member.is_doc_field && is_identifier(&member.name) => {
+                methods.push((member, func));
+            }
+            _ => fields.push(member),
+        }
+    }
+    (fields, methods)
+}
+
+fn write_field(result: &mut String, member: &ApiMember) {
+    if let Some(deprecated) = &member.property.deprecated {
+        // the field tag has no deprecated mark
+        result.push_str(&format!(
+            "---{}\n",
+            deprecated.message.as_deref().unwrap_or("Deprecated")
+        ));
+    }
+
+    let visibility = match get_visibility_name(member.property.visibility) {
+        Some(visibility) => format!("{} ", visibility),
+        None => String::new(),
+    };
+    let name = if is_identifier(&member.name) || member.name.starts_with('[') {
+        member.name.clone()
+    } else {
+        format!("[\"{}\"]", member.name)
+    };
+    result.push_str(&format!(
+        "---@field {}{} {}",
+        visibility,
+        name,
+        render_type(&member.typ)
+    ));
+    if let Some(description) = &member.property.description {
+        result.push(' ');
+        result.push_str(&description.lines().collect::<Vec<_>>().join(" "));
+    }
+    result.push('\n');
+}
+
+fn write_function(
+    result: &mut String,
+    property: &ApiProperty,
+    owner_name: &str,
+    name: &str,
+    func: &ApiFunction,
+) {
+    write_property(result, property);
+    if func.is_async {
+        result.push_str("---@async\n");
+    }
+    if func.is_nodiscard {
+        result.push_str("---@nodiscard\n");
+    }
+    for generic in &func.generics {
+        match &generic.constraint {
+            Some(constraint) => result.push_str(&format!(
+                "---@generic {}: {}\n",
+                generic.name,
+                render_type(constraint)
+            )),
+            None => result.push_str(&format!("---@generic {}\n", generic.name)),
+        }
+    }
+    for param in &func.params {
+        // the param of a method is not written
+        if func.is_method && param.name == "self" {
+            continue;
+        }
+
+        let optional = if param.optional && param.name != "..." {
+            "?"
+        } else {
+            ""
+        };
+        let typ = match &param.typ {
+            Some(typ) => render_param_type(typ, param.optional),
+            None => "any".to_string(),
+        };
+        result.push_str(&format!("---@param {}{} {}", param.name, optional, typ));
+        if let Some(description) = &param.description {
+            result.push(' ');
+            result.push_str(&description.lines().collect::<Vec<_>>().join(" "));
+        }
+        result.push('\n');
+    }
+    for ret in &func.returns {
+        result.push_str(&format!("---@return {}", render_type(&ret.typ)));
+        if let Some(name) = &ret.name {
+            result.push(' ');
+            result.push_str(name);
+        }
+        if let Some(description) = &ret.description {
+            result.push(' ');
+            result.push_str(&description.lines().collect::<Vec<_>>().join(" "));
+        }
+        result.push('\n');
+    }
+    for overload in &func.overloads {
+        result.push_str(&format!(
+            "---@overload {}\n",
+            render_function_type(overload)
+        ));
+    }
+
+    let params = func
+        .params
+        .iter()
+        .filter(|param| !(func.is_method && param.name == "self"))
+        .map(|param| param.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let func_name = if owner_name.is_empty() {
+        name.to_string()
+    } else if func.is_method {
+        format!("{}:{}", owner_name, name)
+    } else {
+        format!("{}.{}", owner_name, name)
+    };
+    result.push_str(&format!("function {}({}) end\n", func_name, params));
+}
+
+fn write_property(result: &mut String, property: &ApiProperty) {
+    if let Some(description) = &property.description {
+        for line in description.lines() {
+            result.push_str("---");
+            result.push_str(line);
+            result.push('\n');
+        }
+    }
+    if let Some(visibility) = get_visibility_name(property.visibility) {
+        result.push_str(&format!("---@{}\n", visibility));
+    }
+    if let Some(deprecated) = &property.deprecated {
+        match &deprecated.message {
+            Some(message) => result.push_str(&format!("---@deprecated {}\n", message)),
+            None => result.push_str("---@deprecated\n"),
+        }
+    }
+    if !property.versions.is_empty() {
+        let versions = property
+            .versions
+            .iter()
+            .map(|cond| match cond.op {
+                ApiVersionOp::Eq => cond.version.clone(),
+                ApiVersionOp::Gte => format!(">{}", cond.version),
+                ApiVersionOp::Lte => format!("<{}", cond.version),
+            })
+            .collect::<Vec<_>>();
+        result.push_str(&format!("---@version {}\n", versions.join(", ")));
+    }
+    if let Some(since) = &property.since {
+        result.push_str(&format!("---@since {}\n", since));
+    }
+    if let Some(see) = &property.see {
+        result.push_str(&format!("---@see {}\n", see));
+    }
+    if let Some(source) = &property.source {
+        result.push_str(&format!("---@source {}\n", source));
+    }
+    for example in &property.examples {
+        result.push_str("---@example\n");
+        for line in example.lines() {
+            result.push_str("---");
+            result.push_str(line);
+            result.push('\n');
+        }
+    }
+}
+
+fn get_visibility_name(visibility: ApiVisibility) -> Option<&'static str> {
+    match visibility {
+        ApiVisibility::Public => None,
+        ApiVisibility::Protected => Some("protected"),
+        ApiVisibility::Private => Some("private"),
+        ApiVisibility::Internal => Some("internal"),
+        ApiVisibility::Package => Some("package"),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,147 @@
+use crate::api_model::{ApiFunction, ApiType};
+
+/// Renders the type as the annotation text, eg: `fun(a: integer): string`
+pub fn render_type(typ: &ApiType) -> String {
+    match typ {
+        ApiType::Primitive { name } => match name.as_str() {
+            "global" => "table".to_string(),
+            _ => name.clone(),
+        },
+        ApiType::Literal { value } => value.to_string(),
+        ApiType::Ref { name } => name.clone(),
+        ApiType::Array { element } => format!("{}[]", render_wrapped_type(element)),
+        ApiType::Tuple { types } => format!("[{}]", render_types(types, ", ")),
+        ApiType::Function(func) => render_function_type(func),
+        ApiType::Object {
+            fields,
+            index_access,
+        } => {
+            let mut items = fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, render_type(&field.typ)))
+                .collect::<Vec<_>>();
+            items.extend(index_access.iter().map(|access| {
+                format!(
+                    "[{}]: {}",
+                    render_type(&access.key),
+                    render_type(&access.value)
+                )
+            }));
+            format!("{{ {} }}", items.join(", "))
+        }
+        ApiType::Union { types } => render_union(types),
+        ApiType::Intersection { types } => types
+            .iter()
+            .map(render_wrapped_type)
+            .collect::<Vec<_>>()
+            .join(" & "),
+        ApiType::Generic { name, params } => format!("{}<{}>", name, render_types(params, ", ")),
+        ApiType::TableGeneric { params } => format!("table<{}>", render_types(params, ", ")),
+        ApiType::TemplateRef { name } => name.clone(),
+        ApiType::StringTemplateRef {
+            prefix,
+            name,
+            suffix,
+        } => format!("{}`{}`{}", prefix, name, suffix),
+        ApiType::Variadic { typ } => format!("{}...", render_wrapped_type(typ)),
+        ApiType::MultiReturn { types } => render_types(types, ", "),
+        ApiType::Call { call, operands } if call == "keyof" && operands.len() == 1 => {
+            format!("keyof {}", render_wrapped_type(&operands[0]))
+        }
+        // the other type operators can not be written in the annotations
+        ApiType::Namespace { .. } | ApiType::Call { .. } => "any".to_string(),
+    }
+}
+
+/// eg: `fun(self: A, b?: string): integer`
+pub fn render_function_type(func: &ApiFunction) -> String {
+    let params = func
+        .params
+        .iter()
+        .map(|param| {
+            let optional = if param.optional && param.name != "..." {
+                "?"
+            } else {
+                ""
+            };
+            match &param.typ {
+                Some(typ) => format!(
+                    "{}{}: {}",
+                    param.name,
+                    optional,
+                    render_param_type(typ, param.optional)
+                ),
+                None => format!("{}{}: any", param.name, optional),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = String::new();
+    if func.is_async {
+        result.push_str("async ");
+    }
+    result.push_str(&format!("fun({})", params.join(", ")));
+    if !func.returns.is_empty() {
+        let returns = func
+            .returns
+            .iter()
+            .map(|ret| render_type(&ret.typ))
+            .collect::<Vec<_>>();
+        result.push_str(&format!(": {}", returns.join(", ")));
+    }
+    result
+}
+
+/// The `nil` of an optional param is written as `name?`
+pub fn render_param_type(typ: &ApiType, optional: bool) -> String {
+    match typ {
+        ApiType::Union { types } if optional => {
+            let types = types
+                .iter()
+                .filter(|typ| !is_nil(typ))
+                .cloned()
+                .collect::<Vec<_>>();
+            match types.len() {
+                0 => "nil".to_string(),
+                1 => render_type(&types[0]),
+                _ => render_union(&types),
+            }
+        }
+        _ => render_type(typ),
+    }
+}
+
+// `T?` for the optional types
+fn render_union(types: &[ApiType]) -> String {
+    let non_nil_types = types.iter().filter(|typ| !is_nil(typ)).collect::<Vec<_>>();
+    if non_nil_types.len() == 1 && non_nil_types.len() < types.len() {
+        return format!("{}?", render_wrapped_type(non_nil_types[0]));
+    }
+
+    types
+        .iter()
+        .map(render_wrapped_type)
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn is_nil(typ: &ApiType) -> bool {
+    matches!(typ, ApiType::Primitive { name } if name == "nil")
+}
+
+fn render_wrapped_type(typ: &ApiType) -> String {
+    match typ {
+        ApiType::Union { .. } | ApiType::Intersection { .. } | ApiType::Function(_) => {
+            format!("({})", render_type(typ))
+        }
+        _ => render_type(typ),
+    }
+}
+
+fn render_types(types: &[ApiType], separator: &str) -> String {
+    types
+        .iter()
+        .map(render_type)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
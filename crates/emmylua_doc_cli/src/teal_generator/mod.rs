@@ -0,0 +1,690 @@
+mod render;
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+};
+
+use emmylua_code_analysis::EmmyLuaAnalysis;
+use render::{
+    get_require_name, is_teal_identifier, to_teal_name, TealIssue, TealRenderer, TealScope,
+    TealTypeName,
+};
+
+use crate::{
+    api_model::{
+        collect_api_model, ApiAlias, ApiClass, ApiEnum, ApiGlobal, ApiLocation, ApiMember,
+        ApiModel, ApiModule, ApiType,
+    },
+    stub_generator::render_type as render_lua_type,
+};
+
+/// The types of the files which return nothing and the globals are declared in this file, it is
+/// used by `tl --global-env-def globals`
+const GLOBAL_ENV_FILE: &str = "globals.d.tl";
+const INDENT: &str = "   ";
+
+/// Writes a Teal `.d.tl` declaration file for every module which returns a value, the file of the
+/// module `a.b` is `a/b.d.tl`. The constructs which have no Teal equivalent are reported.
+pub fn generate_teal(analysis: &EmmyLuaAnalysis, input: PathBuf, output: PathBuf) -> Option<()> {
+    let db = analysis.compilation.get_db();
+    let model = collect_api_model(db, &input);
+    let (files, mut issues) = collect_teal_files(&model);
+
+    for (file, content) in files {
+        let outpath = output.join(&file);
+        if let Some(parent) = outpath.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent).ok()?;
+            }
+        }
+
+        println!("output teal file: {}", outpath.display());
+        match std::fs::write(outpath, content) {
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Failed to write file: {}", e);
+                return None;
+            }
+        }
+    }
+
+    issues.sort();
+    issues.dedup();
+    if !issues.is_empty() {
+        eprintln!("{} constructs have no Teal equivalent:", issues.len());
+        for issue in &issues {
+            eprintln!("  {}: {}", issue.location, issue.message);
+        }
+    }
+
+    Some(())
+}
+
+enum TealExport<'a> {
+    /// The module returns a class of its file, the class is the record of the module
+    Class(&'a ApiClass),
+    /// The module returns a table, its members are the fields of the record
+    Table,
+    Value(&'a ApiType),
+}
+
+#[derive(Default)]
+struct TealFile<'a> {
+    module: Option<(&'a ApiModule, TealExport<'a>)>,
+    classes: Vec<&'a ApiClass>,
+    enums: Vec<&'a ApiEnum>,
+    aliases: Vec<&'a ApiAlias>,
+}
+
+struct TealContext<'a> {
+    names: HashMap<String, TealTypeName>,
+    classes: HashMap<&'a str, &'a ApiClass>,
+    issues: Vec<TealIssue>,
+}
+
+fn collect_teal_files(model: &ApiModel) -> (BTreeMap<String, String>, Vec<TealIssue>) {
+    let mut files: HashMap<&str, TealFile> = HashMap::new();
+    for module in &model.modules {
+        if let Some(export) = get_module_export(model, module) {
+            files.entry(&module.file).or_default().module = Some((module, export));
+        }
+    }
+
+    let mut context = TealContext {
+        names: HashMap::new(),
+        classes: model
+            .classes
+            .iter()
+            .map(|class| (class.full_name.as_str(), class))
+            .collect(),
+        issues: Vec::new(),
+    };
+    let mut global_file = TealFile::default();
+    for class in &model.classes {
+        let (scope, teal_file) = get_type_scope(&mut files, &mut global_file, &class.locations);
+        let is_export = matches!(
+            teal_file.module,
+            Some((_, TealExport::Class(export))) if export.full_name == class.full_name
+        );
+        teal_file.classes.push(class);
+        context.add_type_name(
+            scope,
+            (&class.name, &class.full_name),
+            &class.locations,
+            is_export,
+            true,
+        );
+    }
+
+    for e in &model.enums {
+        let (scope, teal_file) = get_type_scope(&mut files, &mut global_file, &e.locations);
+        teal_file.enums.push(e);
+        context.add_type_name(scope, (&e.name, &e.full_name), &e.locations, false, false);
+    }
+
+    for alias in &model.aliases {
+        let (scope, teal_file) = get_type_scope(&mut files, &mut global_file, &alias.locations);
+        teal_file.aliases.push(alias);
+        context.add_type_name(
+            scope,
+            (&alias.name, &alias.full_name),
+            &alias.locations,
+            false,
+            false,
+        );
+    }
+
+    let mut issues = std::mem::take(&mut context.issues);
+    let mut result = BTreeMap::new();
+    for teal_file in files.values() {
+        let Some((module, _)) = &teal_file.module else {
+            continue;
+        };
+
+        let (content, file_issues) = write_module_file(&context, teal_file);
+        let file = format!("{}.d.tl", module.name.replace('.', "/"));
+        result.insert(file, content);
+        issues.extend(file_issues);
+    }
+
+    let (content, global_issues) = write_global_env_file(&context, &global_file, &model.globals);
+    if let Some(content) = content {
+        result.insert(GLOBAL_ENV_FILE.to_string(), content);
+    }
+    issues.extend(global_issues);
+
+    (result, issues)
+}
+
+fn get_module_export<'a>(model: &'a ApiModel, module: &'a ApiModule) -> Option<TealExport<'a>> {
+    let export = module.export.as_ref()?;
+    match export {
+        ApiType::Primitive { name } if name == "nil" || name == "unknown" => None,
+        ApiType::Ref { name } => {
+            let class = model.classes.iter().find(|class| {
+                &class.full_name == name
+                    && class
+                        .locations
+                        .first()
+                        .is_some_and(|location| location.file == module.file)
+            });
+            match class {
+                Some(class) => Some(TealExport::Class(class)),
+                None => Some(TealExport::Value(export)),
+            }
+        }
+        ApiType::Primitive { name } if name == "table" => Some(TealExport::Table),
+        _ if !module.members.is_empty() => Some(TealExport::Table),
+        _ => Some(TealExport::Value(export)),
+    }
+}
+
+// the types are declared in the record of the module of their file, the types of the files which
+// return nothing are global
+fn get_type_scope<'a, 'b>(
+    files: &'b mut HashMap<&'a str, TealFile<'a>>,
+    global_file: &'b mut TealFile<'a>,
+    locations: &[ApiLocation],
+) -> (TealScope, &'b mut TealFile<'a>) {
+    let teal_file = locations
+        .first()
+        .and_then(|location| files.get_mut(location.file.as_str()));
+    let Some(teal_file) = teal_file else {
+        return (TealScope::Global, global_file);
+    };
+
+    match &teal_file.module {
+        Some((module, TealExport::Value(_))) => (TealScope::Local(module.file.clone()), teal_file),
+        Some((module, _)) => (TealScope::Module(module.name.clone()), teal_file),
+        None => (TealScope::Global, global_file),
+    }
+}
+
+impl TealContext<'_> {
+    // the global types keep their namespaces in their names
+    fn add_type_name(
+        &mut self,
+        scope: TealScope,
+        (name, full_name): (&str, &str),
+        locations: &[ApiLocation],
+        is_export: bool,
+        is_record: bool,
+    ) {
+        let origin_name = match scope {
+            TealScope::Global => full_name,
+            _ => name,
+        };
+        let teal_name = to_teal_name(origin_name);
+        if teal_name != origin_name {
+            self.issues.push(TealIssue {
+                location: locations
+                    .first()
+                    .map(|location| format!("{}:{}", location.file, location.line))
+                    .unwrap_or_default(),
+                message: format!("the type `{}` is written as `{}`", origin_name, teal_name),
+            });
+        }
+
+        self.names.insert(
+            full_name.to_string(),
+            TealTypeName {
+                name: teal_name,
+                scope,
+                is_export,
+                is_record,
+            },
+        );
+    }
+}
+
+fn write_module_file(context: &TealContext, teal_file: &TealFile) -> (String, Vec<TealIssue>) {
+    let Some((module, export)) = &teal_file.module else {
+        return (String::new(), Vec::new());
+    };
+
+    let mut r = TealRenderer::new(&context.names, Some(&module.name), &module.file);
+    let record_name = match export {
+        TealExport::Class(class) => get_teal_name(context, &class.full_name),
+        _ => to_teal_name(module.name.rsplit('.').next().unwrap_or(&module.name)),
+    };
+
+    let mut body = String::new();
+    match export {
+        TealExport::Class(class) => {
+            let nested = write_file_types(&mut r, context, teal_file, INDENT, "", Some(class));
+            write_class(&mut r, &mut body, context, "", "local ", class, &nested);
+        }
+        TealExport::Table => {
+            let nested = write_file_types(&mut r, context, teal_file, INDENT, "", None);
+            write_description(&mut body, "", module.property.description.as_deref());
+            body.push_str(&format!("local record {}\n", record_name));
+            body.push_str(&nested.content);
+            r.self_type = Some(record_name.clone());
+            write_members(
+                &mut r,
+                &mut body,
+                INDENT,
+                &module.members.iter().collect::<Vec<_>>(),
+                &nested.names,
+            );
+            body.push_str("end\n");
+        }
+        TealExport::Value(typ) => {
+            let types = write_file_types(&mut r, context, teal_file, "", "local ", None);
+            body.push_str(&types.content);
+            r.set_location(&ApiLocation {
+                file: module.file.clone(),
+                line: 1,
+                col: 1,
+            });
+            write_description(&mut body, "", module.property.description.as_deref());
+            let typ = r.render_param_type(typ);
+            body.push_str(&format!("local {}: {}\n", record_name, typ));
+        }
+    }
+    body.push_str(&format!("\nreturn {}\n", record_name));
+
+    let mut content = String::new();
+    for require in &r.requires {
+        content.push_str(&format!(
+            "local {} = require(\"{}\")\n",
+            get_require_name(require),
+            require
+        ));
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str(&body);
+    (content, r.issues)
+}
+
+fn write_global_env_file(
+    context: &TealContext,
+    global_file: &TealFile,
+    globals: &[ApiGlobal],
+) -> (Option<String>, Vec<TealIssue>) {
+    let mut r = TealRenderer::new(&context.names, None, "");
+    let types = write_file_types(&mut r, context, global_file, "", "global ", None);
+    let mut content = types.content;
+    for global in globals {
+        r.set_location(&global.location);
+        if !is_teal_identifier(&global.name) {
+            r.report(format!("the global `{}` is not a Teal name", global.name));
+            continue;
+        }
+        // the global record of the class is the value of the global
+        if let ApiType::Ref { name } = &global.typ {
+            if context.names.get(name).is_some_and(|teal_name| {
+                teal_name.scope == TealScope::Global && teal_name.name == global.name
+            }) {
+                continue;
+            }
+        }
+
+        write_description(&mut content, "", global.property.description.as_deref());
+        match &global.typ {
+            ApiType::Function(func) => {
+                if !func.overloads.is_empty() {
+                    r.report(format!(
+                        "the overloads of the global function `{}` are dropped",
+                        global.name
+                    ));
+                }
+                let signature = r.render_function_signature(func, None);
+                content.push_str(&format!("global {}: function{}\n", global.name, signature));
+            }
+            _ if !global.members.is_empty() => {
+                content.push_str(&format!("global record {}\n", global.name));
+                r.self_type = Some(global.name.clone());
+                let members = global.members.iter().collect::<Vec<_>>();
+                write_members(&mut r, &mut content, INDENT, &members, &HashSet::new());
+                r.self_type = None;
+                content.push_str("end\n");
+            }
+            typ => {
+                let typ = r.render_value_type(typ);
+                content.push_str(&format!("global {}: {}\n", global.name, typ));
+            }
+        }
+        content.push('\n');
+    }
+
+    if content.is_empty() {
+        (None, r.issues)
+    } else {
+        (Some(content.trim_end().to_string() + "\n"), r.issues)
+    }
+}
+
+#[derive(Default)]
+struct TealTypes {
+    content: String,
+    /// The names of the nested types, the fields of a record can not have the same names
+    names: HashSet<String>,
+}
+
+// the classes, the enums and the aliases of the file except the exported class
+fn write_file_types(
+    r: &mut TealRenderer,
+    context: &TealContext,
+    teal_file: &TealFile,
+    indent: &str,
+    prefix: &str,
+    export: Option<&ApiClass>,
+) -> TealTypes {
+    let mut types = TealTypes::default();
+    for class in &teal_file.classes {
+        if export.is_some_and(|export| export.full_name == class.full_name) {
+            continue;
+        }
+
+        write_class(
+            r,
+            &mut types.content,
+            context,
+            indent,
+            prefix,
+            class,
+            &TealTypes::default(),
+        );
+        types.content.push('\n');
+        types.names.insert(get_teal_name(context, &class.full_name));
+    }
+
+    for e in &teal_file.enums {
+        write_enum(r, &mut types.content, context, indent, prefix, e);
+        types.content.push('\n');
+        types.names.insert(get_teal_name(context, &e.full_name));
+    }
+
+    for alias in &teal_file.aliases {
+        write_alias(r, &mut types.content, context, indent, prefix, alias);
+        types.content.push('\n');
+        types.names.insert(get_teal_name(context, &alias.full_name));
+    }
+
+    types
+}
+
+fn write_class(
+    r: &mut TealRenderer,
+    result: &mut String,
+    context: &TealContext,
+    indent: &str,
+    prefix: &str,
+    class: &ApiClass,
+    nested: &TealTypes,
+) {
+    if let Some(location) = class.locations.first() {
+        r.set_location(location);
+    }
+    let name = get_teal_name(context, &class.full_name);
+    let generics = r.render_generic_params(&class.generics);
+    write_description(result, indent, class.property.description.as_deref());
+    result.push_str(&format!(
+        "{}{}record {}{}\n",
+        indent, prefix, name, generics
+    ));
+    result.push_str(&nested.content);
+
+    let generic_names = class
+        .generics
+        .iter()
+        .map(|generic| generic.name.as_str())
+        .collect::<Vec<_>>();
+    let self_type = if generic_names.is_empty() {
+        name
+    } else {
+        format!("{}<{}>", name, generic_names.join(", "))
+    };
+    let old_self_type = r.self_type.replace(self_type);
+    let members = collect_class_members(r, context, class);
+    write_members(
+        r,
+        result,
+        &format!("{}{}", indent, INDENT),
+        &members,
+        &nested.names,
+    );
+    r.self_type = old_self_type;
+    result.push_str(&format!("{}end\n", indent));
+}
+
+// Teal records have no inheritance, the members of the super classes are copied
+fn collect_class_members<'a>(
+    r: &mut TealRenderer,
+    context: &TealContext<'a>,
+    class: &'a ApiClass,
+) -> Vec<&'a ApiMember> {
+    let mut members = class.members.iter().collect::<Vec<_>>();
+    let mut visited = HashSet::new();
+    visited.insert(class.full_name.as_str());
+    let mut supers = class.supers.iter().collect::<Vec<_>>();
+    let mut idx = 0;
+    while idx < supers.len() {
+        let super_type = supers[idx];
+        idx += 1;
+        match super_type {
+            ApiType::Ref { name } => {
+                let Some(super_class) = context.classes.get(name.as_str()) else {
+                    r.report(format!(
+                        "the super type `{}` of `{}` is not a class of the workspace, its members are not inherited",
+                        name, class.full_name
+                    ));
+                    continue;
+                };
+                if !visited.insert(super_class.full_name.as_str()) {
+                    continue;
+                }
+
+                for member in &super_class.members {
+                    if !members.iter().any(|it| it.name == member.name) {
+                        members.push(member);
+                    }
+                }
+                supers.extend(super_class.supers.iter());
+            }
+            ApiType::Primitive { name } if name == "table" => {}
+            _ => r.report(format!(
+                "the super type `{}` of `{}` is not supported, its members are not inherited",
+                render_lua_type(super_type),
+                class.full_name
+            )),
+        }
+    }
+    members
+}
+
+fn write_members(
+    r: &mut TealRenderer,
+    result: &mut String,
+    indent: &str,
+    members: &[&ApiMember],
+    nested_names: &HashSet<String>,
+) {
+    for member in members {
+        r.set_location(&member.location);
+        if !is_teal_identifier(&member.name) {
+            r.report(format!("the member `{}` is not a Teal name", member.name));
+            continue;
+        }
+        if nested_names.contains(&member.name) {
+            // the nested record is the value of the field already, eg: `M.Buffer = Buffer`
+            if !matches!(member.typ, ApiType::Ref { .. }) {
+                r.report(format!(
+                    "the member `{}` has the name of a nested type",
+                    member.name
+                ));
+            }
+            continue;
+        }
+
+        write_description(result, indent, member.property.description.as_deref());
+        match &member.typ {
+            ApiType::Function(func) => {
+                let self_type = match (func.is_method, &r.self_type) {
+                    (true, Some(self_type)) => Some(self_type.clone()),
+                    _ => None,
+                };
+                // the field declared several times is the overloaded function
+                for func in std::iter::once(func).chain(func.overloads.iter()) {
+                    let signature = r.render_function_signature(func, self_type.as_deref());
+                    result.push_str(&format!(
+                        "{}{}: function{}\n",
+                        indent, member.name, signature
+                    ));
+                }
+            }
+            typ if member.is_doc_field => {
+                let typ = r.render_param_type(typ);
+                result.push_str(&format!("{}{}: {}\n", indent, member.name, typ));
+            }
+            typ => {
+                let typ = r.render_value_type(typ);
+                result.push_str(&format!("{}{}: {}\n", indent, member.name, typ));
+            }
+        }
+    }
+}
+
+// Teal enums are the string enums, the other enums are written as their base types
+fn write_enum(
+    r: &mut TealRenderer,
+    result: &mut String,
+    context: &TealContext,
+    indent: &str,
+    prefix: &str,
+    e: &ApiEnum,
+) {
+    if let Some(location) = e.locations.first() {
+        r.set_location(location);
+    }
+    let name = get_teal_name(context, &e.full_name);
+    write_description(result, indent, e.property.description.as_deref());
+    let values = if e.is_key {
+        Some(e.members.iter().map(|member| member.name.clone()).collect())
+    } else {
+        e.members
+            .iter()
+            .map(|member| match &member.typ {
+                ApiType::Literal {
+                    value: serde_json::Value::String(value),
+                } => Some(value.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+
+    match values {
+        Some(values) => write_string_enum(result, indent, prefix, &name, &values),
+        None => {
+            let base = match &e.base {
+                Some(base) => r.render_type(base),
+                None => get_enum_base_type(e).to_string(),
+            };
+            r.report(format!(
+                "the enum `{}` has values which are not strings, it is written as `{}`",
+                e.full_name, base
+            ));
+            result.push_str(&format!("{}{}type {} = {}\n", indent, prefix, name, base));
+        }
+    }
+}
+
+fn get_enum_base_type(e: &ApiEnum) -> &'static str {
+    let mut base = "integer";
+    for member in &e.members {
+        match &member.typ {
+            ApiType::Literal {
+                value: serde_json::Value::Number(n),
+            } => {
+                if !n.is_i64() && !n.is_u64() {
+                    base = "number";
+                }
+            }
+            _ => return "any",
+        }
+    }
+    base
+}
+
+// the alias of the string literals is the enum
+fn write_alias(
+    r: &mut TealRenderer,
+    result: &mut String,
+    context: &TealContext,
+    indent: &str,
+    prefix: &str,
+    alias: &ApiAlias,
+) {
+    if let Some(location) = alias.locations.first() {
+        r.set_location(location);
+    }
+    let name = get_teal_name(context, &alias.full_name);
+    write_description(result, indent, alias.property.description.as_deref());
+    if let Some(ApiType::Union { types }) = &alias.origin {
+        let values = types
+            .iter()
+            .map(|typ| match typ {
+                ApiType::Literal {
+                    value: serde_json::Value::String(value),
+                } => Some(value.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(values) = values {
+            write_string_enum(result, indent, prefix, &name, &values);
+            return;
+        }
+    }
+
+    let generics = r.render_generic_params(&alias.generics);
+    let origin = match &alias.origin {
+        Some(origin) => r.render_param_type(origin),
+        None => "any".to_string(),
+    };
+    result.push_str(&format!(
+        "{}{}type {}{} = {}\n",
+        indent, prefix, name, generics, origin
+    ));
+}
+
+fn write_string_enum(
+    result: &mut String,
+    indent: &str,
+    prefix: &str,
+    name: &str,
+    values: &[String],
+) {
+    result.push_str(&format!("{}{}enum {}\n", indent, prefix, name));
+    for value in values {
+        result.push_str(&format!(
+            "{}{}{}\n",
+            indent,
+            INDENT,
+            serde_json::to_string(value).unwrap_or_default()
+        ));
+    }
+    result.push_str(&format!("{}end\n", indent));
+}
+
+fn write_description(result: &mut String, indent: &str, description: Option<&str>) {
+    if let Some(description) = description {
+        for line in description.lines() {
+            if line.is_empty() {
+                result.push_str(&format!("{}--\n", indent));
+            } else {
+                result.push_str(&format!("{}-- {}\n", indent, line));
+            }
+        }
+    }
+}
+
+fn get_teal_name(context: &TealContext, full_name: &str) -> String {
+    match context.names.get(full_name) {
+        Some(teal_name) => teal_name.name.clone(),
+        None => to_teal_name(full_name),
+    }
+}
@@ -0,0 +1,395 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    api_model::{ApiFunction, ApiGenericParam, ApiLocation, ApiType},
+    stub_generator::render_type as render_lua_type,
+};
+
+/// The construct which has no Teal equivalent, it is written as the nearest Teal type
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TealIssue {
+    pub location: String,
+    pub message: String,
+}
+
+/// Where the Teal declaration of a class, an enum or an alias is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TealScope {
+    /// In the global environment file
+    Global,
+    /// In the record of the module, it is referenced by `require` from the other modules
+    Module(String),
+    /// The local type of the file, its module does not return a record
+    Local(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct TealTypeName {
+    pub name: String,
+    pub scope: TealScope,
+    /// The class is the record returned by the module
+    pub is_export: bool,
+    pub is_record: bool,
+}
+
+/// Renders the api types as the Teal types, the unsupported types are reported and written as
+/// `any`
+pub struct TealRenderer<'a> {
+    names: &'a HashMap<String, TealTypeName>,
+    module: Option<&'a str>,
+    file: &'a str,
+    location: String,
+    /// The type of `self` in the current record
+    pub self_type: Option<String>,
+    /// The modules which are required by the types of the file
+    pub requires: BTreeSet<String>,
+    pub issues: Vec<TealIssue>,
+}
+
+impl<'a> TealRenderer<'a> {
+    pub fn new(
+        names: &'a HashMap<String, TealTypeName>,
+        module: Option<&'a str>,
+        file: &'a str,
+    ) -> Self {
+        Self {
+            names,
+            module,
+            file,
+            location: file.to_string(),
+            self_type: None,
+            requires: BTreeSet::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    pub fn set_location(&mut self, location: &ApiLocation) {
+        self.location = format!("{}:{}", location.file, location.line);
+    }
+
+    pub fn report(&mut self, message: String) {
+        self.issues.push(TealIssue {
+            location: self.location.clone(),
+            message,
+        });
+    }
+
+    /// eg: `{string: integer}`, `function(a: string, b?: integer): boolean`
+    pub fn render_type(&mut self, typ: &ApiType) -> String {
+        match typ {
+            ApiType::Primitive { name } => self.render_primitive(name),
+            ApiType::Literal { value } => {
+                let base = get_literal_base_type(value);
+                self.report(format!(
+                    "the literal type `{}` is written as `{}`",
+                    value, base
+                ));
+                base.to_string()
+            }
+            ApiType::Ref { name } => self
+                .render_type_name(name)
+                .unwrap_or_else(|| "any".to_string()),
+            ApiType::Array { element } => format!("{{{}}}", self.render_type(element)),
+            ApiType::Tuple { types } if !types.is_empty() => {
+                format!("{{{}}}", self.render_types(types).join(", "))
+            }
+            ApiType::Function(func) => {
+                if !func.overloads.is_empty() {
+                    self.report(format!(
+                        "the overloads of the function type `{}` are dropped",
+                        render_lua_type(typ)
+                    ));
+                }
+                format!("function{}", self.render_function_signature(func, None))
+            }
+            ApiType::Object {
+                fields,
+                index_access,
+            } if fields.is_empty() && index_access.len() == 1 => format!(
+                "{{{}: {}}}",
+                self.render_type(&index_access[0].key),
+                self.render_type(&index_access[0].value)
+            ),
+            ApiType::Union { types } => self.render_union(types),
+            ApiType::Generic { name, params } => {
+                let Some(name) = self.render_type_name(name) else {
+                    return "any".to_string();
+                };
+                format!("{}<{}>", name, self.render_types(params).join(", "))
+            }
+            ApiType::TableGeneric { params } if params.len() == 2 => format!(
+                "{{{}: {}}}",
+                self.render_type(&params[0]),
+                self.render_type(&params[1])
+            ),
+            ApiType::TemplateRef { name } => name.clone(),
+            _ => self.render_unsupported(typ),
+        }
+    }
+
+    /// The params and the returns of the function, eg: `<T>(a: T, ...: any): T...`. The `self`
+    /// param of a method is typed as `self_type`.
+    pub fn render_function_signature(
+        &mut self,
+        func: &ApiFunction,
+        self_type: Option<&str>,
+    ) -> String {
+        let mut result = self.render_generic_params(&func.generics);
+
+        let mut params = Vec::new();
+        if let Some(self_type) = self_type {
+            params.push(format!("self: {}", self_type));
+        }
+        for param in &func.params {
+            if self_type.is_some() && param.name == "self" {
+                continue;
+            }
+
+            let typ = match &param.typ {
+                Some(ApiType::Variadic { typ }) => self.render_type(typ),
+                Some(typ) => self.render_param_type(typ),
+                None => "any".to_string(),
+            };
+            if param.name == "..." {
+                params.push(format!("...: {}", typ));
+            } else if param.optional {
+                params.push(format!("{}?: {}", param.name, typ));
+            } else {
+                params.push(format!("{}: {}", param.name, typ));
+            }
+        }
+        result.push_str(&format!("({})", params.join(", ")));
+
+        let returns = func
+            .returns
+            .iter()
+            .flat_map(|ret| match &ret.typ {
+                ApiType::MultiReturn { types } => types.iter().collect::<Vec<_>>(),
+                typ => vec![typ],
+            })
+            .collect::<Vec<_>>();
+        let mut rendered_returns = Vec::new();
+        for (idx, typ) in returns.iter().enumerate() {
+            match typ {
+                ApiType::Variadic { typ } if idx + 1 == returns.len() => {
+                    let typ = self.render_param_type(typ);
+                    rendered_returns.push(format!("{}...", typ));
+                }
+                typ => rendered_returns.push(self.render_param_type(typ)),
+            }
+        }
+        match rendered_returns.len() {
+            0 => {}
+            1 => result.push_str(&format!(": {}", rendered_returns[0])),
+            _ => result.push_str(&format!(": ({})", rendered_returns.join(", "))),
+        }
+        result
+    }
+
+    /// eg: `<K, V>`, Teal has no constraints of the generics
+    pub fn render_generic_params(&mut self, generics: &[ApiGenericParam]) -> String {
+        if generics.is_empty() {
+            return String::new();
+        }
+
+        let mut names = Vec::new();
+        for generic in generics {
+            if let Some(constraint) = &generic.constraint {
+                self.report(format!(
+                    "the constraint `{}` of the generic `{}` is dropped",
+                    render_lua_type(constraint),
+                    generic.name
+                ));
+            }
+            names.push(generic.name.clone());
+        }
+        format!("<{}>", names.join(", "))
+    }
+
+    /// Every Teal type accepts `nil`, so the `nil` of the optional types is dropped
+    pub fn render_param_type(&mut self, typ: &ApiType) -> String {
+        match typ {
+            ApiType::Union { types } => self.render_union(types),
+            _ => self.render_type(typ),
+        }
+    }
+
+    /// The inferred literal types of the values are written as their base types
+    pub fn render_value_type(&mut self, typ: &ApiType) -> String {
+        match typ {
+            ApiType::Literal { value } => get_literal_base_type(value).to_string(),
+            _ => self.render_param_type(typ),
+        }
+    }
+
+    pub fn render_type_name(&mut self, name: &str) -> Option<String> {
+        let Some(teal_name) = self.names.get(name) else {
+            self.report(format!(
+                "the type `{}` is not declared in the workspace",
+                name
+            ));
+            return None;
+        };
+
+        match &teal_name.scope {
+            TealScope::Global => Some(teal_name.name.clone()),
+            TealScope::Module(module) if self.module == Some(module.as_str()) => {
+                Some(teal_name.name.clone())
+            }
+            TealScope::Module(module) => {
+                self.requires.insert(module.clone());
+                let require_name = get_require_name(module);
+                if teal_name.is_export {
+                    Some(require_name)
+                } else {
+                    Some(format!("{}.{}", require_name, teal_name.name))
+                }
+            }
+            TealScope::Local(file) if file == self.file => Some(teal_name.name.clone()),
+            TealScope::Local(_) => {
+                self.report(format!(
+                    "the type `{}` is local to its file, because its module does not return a table",
+                    name
+                ));
+                None
+            }
+        }
+    }
+
+    fn render_primitive(&mut self, name: &str) -> String {
+        match name {
+            "any" | "unknown" => "any".to_string(),
+            "nil" | "boolean" | "string" | "integer" | "number" | "thread" => name.to_string(),
+            "table" => "{any: any}".to_string(),
+            "global" => "{string: any}".to_string(),
+            "function" => "function(...: any): any...".to_string(),
+            "self" => match self.self_type.clone() {
+                Some(self_type) => self_type,
+                None => {
+                    self.report("the type `self` is outside of a record".to_string());
+                    "any".to_string()
+                }
+            },
+            _ => {
+                self.report(format!("the type `{}` is not supported", name));
+                "any".to_string()
+            }
+        }
+    }
+
+    // Teal allows only one table type and one function type in a union
+    fn render_union(&mut self, types: &[ApiType]) -> String {
+        let types = types
+            .iter()
+            .filter(|typ| !matches!(typ, ApiType::Primitive { name } if name == "nil"))
+            .collect::<Vec<_>>();
+        if types.is_empty() {
+            return "nil".to_string();
+        }
+
+        let table_count = types.iter().filter(|typ| self.is_table_type(typ)).count();
+        let function_count = types
+            .iter()
+            .filter(|typ| {
+                matches!(typ, ApiType::Function(_))
+                    || matches!(typ, ApiType::Primitive { name } if name == "function")
+            })
+            .count();
+        if table_count > 1 || function_count > 1 {
+            self.report(format!(
+                "the union `{}` has several table or function types",
+                render_lua_type(&ApiType::Union {
+                    types: types.into_iter().cloned().collect()
+                })
+            ));
+            return "any".to_string();
+        }
+
+        let mut rendered = Vec::new();
+        for typ in types {
+            let text = match typ {
+                ApiType::Function(_) => format!("({})", self.render_type(typ)),
+                _ => self.render_type(typ),
+            };
+            if !rendered.contains(&text) {
+                rendered.push(text);
+            }
+        }
+        rendered.join(" | ")
+    }
+
+    fn is_table_type(&self, typ: &ApiType) -> bool {
+        match typ {
+            ApiType::Array { .. }
+            | ApiType::Tuple { .. }
+            | ApiType::Object { .. }
+            | ApiType::TableGeneric { .. } => true,
+            ApiType::Primitive { name } => name == "table" || name == "global",
+            ApiType::Ref { name } | ApiType::Generic { name, .. } => self
+                .names
+                .get(name)
+                .is_some_and(|teal_name| teal_name.is_record),
+            _ => false,
+        }
+    }
+
+    fn render_unsupported(&mut self, typ: &ApiType) -> String {
+        let message = match typ {
+            ApiType::Object { .. } => format!(
+                "the table type `{}` has no Teal equivalent, declare it as a class",
+                render_lua_type(typ)
+            ),
+            ApiType::Call { call, .. } => {
+                format!("the type operator `{}` is not supported", call)
+            }
+            _ => format!("the type `{}` is not supported", render_lua_type(typ)),
+        };
+        self.report(message);
+        "any".to_string()
+    }
+
+    fn render_types(&mut self, types: &[ApiType]) -> Vec<String> {
+        types.iter().map(|typ| self.render_type(typ)).collect()
+    }
+}
+
+fn get_literal_base_type(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        serde_json::Value::Number(_) => "number",
+        _ => "any",
+    }
+}
+
+/// The local name of the required module, eg: `a.b` is `local a_b = require("a.b")`
+pub fn get_require_name(module: &str) -> String {
+    to_teal_name(module)
+}
+
+/// Replaces the characters which are not allowed in the Teal names
+pub fn to_teal_name(name: &str) -> String {
+    let mut result = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+    result
+}
+
+pub fn is_teal_identifier(name: &str) -> bool {
+    const KEYWORDS: [&str; 22] = [
+        "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if",
+        "in", "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+    ];
+    to_teal_name(name) == name && !KEYWORDS.contains(&name)
+}
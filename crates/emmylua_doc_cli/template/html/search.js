@@ -0,0 +1,37 @@
+(function () {
+    var input = document.getElementById("search");
+    var results = document.getElementById("search-results");
+    var tree = document.getElementById("tree");
+    var entries = window.SEARCH_INDEX || [];
+
+    input.addEventListener("input", function () {
+        var query = input.value.trim().toLowerCase();
+        results.innerHTML = "";
+        tree.style.display = query ? "none" : "";
+        if (!query) {
+            return;
+        }
+
+        var matches = entries.filter(function (entry) {
+            return entry.name.toLowerCase().indexOf(query) !== -1;
+        });
+        matches.sort(function (a, b) {
+            var aStarts = a.name.toLowerCase().indexOf(query) === 0 ? 0 : 1;
+            var bStarts = b.name.toLowerCase().indexOf(query) === 0 ? 0 : 1;
+            return aStarts - bStarts || a.name.length - b.name.length;
+        });
+        matches.slice(0, 50).forEach(function (entry) {
+            var item = document.createElement("li");
+            var link = document.createElement("a");
+            link.href = entry.url;
+            link.textContent = entry.name;
+            link.title = entry.summary || "";
+            var kind = document.createElement("span");
+            kind.className = "kind";
+            kind.textContent = " " + entry.kind;
+            item.appendChild(link);
+            item.appendChild(kind);
+            results.appendChild(item);
+        });
+    });
+})();
@@ -1,9 +1,9 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
-use emmylua_code_analysis::{DiagnosticCode, FileId, SemanticModel};
+use emmylua_code_analysis::{build_quick_fix, DiagnosticCode, FileId, SemanticModel};
 use lsp_types::{
     CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionResponse, Diagnostic,
-    NumberOrString, Range, WorkspaceEdit,
+    NumberOrString, Range, TextEdit, WorkspaceEdit,
 };
 
 use crate::handlers::command::{make_disable_code_command, DisableAction};
@@ -29,7 +29,12 @@ pub fn build_actions(
         if let Some(code) = diagnostic.code {
             if let NumberOrString::String(action_string) = code {
                 if let Some(diagnostic_code) = DiagnosticCode::from_str(&action_string).ok() {
-                    add_fix_code_action(&mut actions, diagnostic_code, file_id, diagnostic.range);
+                    add_fix_code_action(
+                        semantic_model,
+                        &mut actions,
+                        diagnostic_code,
+                        diagnostic.range,
+                    );
                     add_disable_code_action(
                         &semantic_model,
                         &mut actions,
@@ -49,13 +54,40 @@ pub fn build_actions(
     Some(actions)
 }
 
-#[allow(unused_variables)]
 fn add_fix_code_action(
+    semantic_model: &SemanticModel,
     actions: &mut Vec<CodeActionOrCommand>,
     diagnostic_code: DiagnosticCode,
-    file_id: FileId,
     range: Range,
 ) -> Option<()> {
+    let document = semantic_model.get_document();
+    let quick_fix = build_quick_fix(
+        semantic_model,
+        diagnostic_code,
+        document.to_rowan_range(range)?,
+    )?;
+
+    let mut text_edits = Vec::new();
+    for edit in quick_fix.edits {
+        text_edits.push(TextEdit {
+            range: document.to_lsp_range(edit.range)?,
+            new_text: edit.new_text,
+        });
+    }
+    let mut changes = HashMap::new();
+    changes.insert(document.get_uri(), text_edits);
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: quick_fix.title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }));
+
     Some(())
 }
 
@@ -23,10 +23,14 @@ pub struct HoverBuilder<'a> {
     pub annotation_description: Vec<MarkedString>,
     /// Type expansion, often used for alias types
     pub type_expansion: Option<Vec<String>>,
+    /// since
+    pub since: Option<String>,
     /// see
     pub see_content: Option<String>,
     /// other
     pub other_content: Option<String>,
+    /// example and usage
+    pub examples: Vec<String>,
 
     pub is_completion: bool,
     trigger_token: Option<LuaSyntaxToken>,
@@ -48,8 +52,10 @@ impl<'a> HoverBuilder<'a> {
             is_completion,
             trigger_token: token,
             type_expansion: None,
+            since: None,
             see_content: None,
             other_content: None,
+            examples: Vec::new(),
         }
     }
 
@@ -174,12 +180,18 @@ impl<'a> HoverBuilder<'a> {
             self.add_annotation_description(description);
         }
 
+        if let Some(since) = &property.since {
+            self.since = Some(since.clone());
+        }
         if let Some(see) = &property.see_content {
             self.see_content = Some(see.to_string());
         }
         if let Some(other) = &property.other_content {
             self.other_content = Some(other.to_string());
         }
+        if let Some(examples) = &property.examples {
+            self.examples = examples.to_vec();
+        }
 
         Some(())
     }
@@ -262,6 +274,10 @@ impl<'a> HoverBuilder<'a> {
             }
         }
 
+        if let Some(since) = &self.since {
+            result.push_str(&format!("\n@*since* {}\n", since));
+        }
+
         if let Some(see_content) = &self.see_content {
             result.push_str(&format!("\n@*see* {}\n", see_content));
         }
@@ -271,6 +287,10 @@ impl<'a> HoverBuilder<'a> {
             result.push_str(other);
         }
 
+        for example in &self.examples {
+            result.push_str(&format!("\n\n@*example*\n\n{}\n", example));
+        }
+
         if let Some(signature_overload) = &self.signature_overload {
             result.push_str("\n---\n");
             for signature in signature_overload {
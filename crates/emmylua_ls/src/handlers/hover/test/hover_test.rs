@@ -103,4 +103,44 @@ mod tests {
             },
         ));
     }
+
+    #[test]
+    fn test_hover_example() {
+        let mut ws = HoverVirtualWorkspace::new();
+        assert!(ws.check_hover(
+            r#"
+                ---Adds two numbers
+                ---@param a number
+                ---@param b number
+                ---@example
+                --- ```lua
+                --- local c = add(1, 2)
+                --- ```
+                ---@usage print(add(3, 4))
+                local function <??>add(a, b)
+                    return a + b
+                end
+            "#,
+            VirtualHoverResult {
+                value: "\n```lua\nlocal function add(a: number, b: number)\n  -> number\n\n```\n\n---\n\nAdds two numbers\n\n\n\n\n@*example*\n\n```lua\nlocal c = add(1, 2)\n```\n\n\n@*example*\n\n```lua\nprint(add(3, 4))\n```\n".to_string(),
+            },
+        ));
+    }
+
+    #[test]
+    fn test_hover_since() {
+        let mut ws = HoverVirtualWorkspace::new();
+        assert!(ws.check_hover(
+            r#"
+                ---Moves the point
+                ---@since 1.2 the offset is optional
+                ---@param dx number
+                local function <??>move(dx)
+                end
+            "#,
+            VirtualHoverResult {
+                value: "\n```lua\nlocal function move(dx: number)\n```\n\n---\n\nMoves the point\n\n\n\n@*since* 1.2\n".to_string(),
+            },
+        ));
+    }
 }
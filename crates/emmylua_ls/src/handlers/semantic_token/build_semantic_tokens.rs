@@ -1,13 +1,15 @@
 use emmylua_code_analysis::{
-    LuaMemberId, LuaMemberOwner, LuaSemanticDeclId, LuaType, SemanticDeclLevel, SemanticModel,
+    classify_string_literal, get_string_value_range, parse_format_string, parse_lua_pattern,
+    LuaMemberId, LuaMemberOwner, LuaPatternItemKind, LuaSemanticDeclId, LuaStringArgKind, LuaType,
+    SemanticDeclLevel, SemanticModel,
 };
 use emmylua_parser::{
     LuaAst, LuaAstNode, LuaAstToken, LuaDocFieldKey, LuaDocObjectFieldKey, LuaExpr,
-    LuaGeneralToken, LuaLiteralToken, LuaNameToken, LuaSyntaxNode, LuaSyntaxToken, LuaTokenKind,
-    LuaVarExpr,
+    LuaGeneralToken, LuaLiteralToken, LuaNameToken, LuaStringToken, LuaSyntaxNode, LuaSyntaxToken,
+    LuaTokenKind, LuaVarExpr,
 };
 use lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType};
-use rowan::NodeOrToken;
+use rowan::{NodeOrToken, TextRange};
 
 use crate::context::ClientId;
 
@@ -52,7 +54,7 @@ fn build_tokens_semantic_token(
 ) {
     match token.kind().into() {
         LuaTokenKind::TkLongString | LuaTokenKind::TkString => {
-            builder.push(token, SemanticTokenType::STRING);
+            build_string_semantic_token(semantic_model, builder, token);
         }
         LuaTokenKind::TkAnd
         | LuaTokenKind::TkBreak
@@ -135,7 +137,11 @@ fn build_tokens_semantic_token(
         | LuaTokenKind::TkTagMapping
         | LuaTokenKind::TkTagNamespace
         | LuaTokenKind::TkTagUsing
-        | LuaTokenKind::TkTagSource => {
+        | LuaTokenKind::TkTagSource
+        | LuaTokenKind::TkTagExample
+        | LuaTokenKind::TkTagSince
+        | LuaTokenKind::TkTagTParam
+        | LuaTokenKind::TkTagTReturn => {
             builder.push_with_modifier(
                 token,
                 SemanticTokenType::KEYWORD,
@@ -200,6 +206,74 @@ fn build_tokens_semantic_token(
     }
 }
 
+fn build_string_semantic_token(
+    semantic_model: &SemanticModel,
+    builder: &mut SemanticBuilder,
+    token: &LuaSyntaxToken,
+) {
+    if build_string_syntax_semantic_token(semantic_model, builder, token).is_none() {
+        builder.push(token, SemanticTokenType::STRING);
+    }
+}
+
+// split the format string and the pattern into the specifiers, the character classes and so on
+fn build_string_syntax_semantic_token(
+    semantic_model: &SemanticModel,
+    builder: &mut SemanticBuilder,
+    token: &LuaSyntaxToken,
+) -> Option<()> {
+    let string_token = LuaStringToken::cast(token.clone())?;
+    let kind = classify_string_literal(semantic_model, &string_token)?;
+    let value = string_token.get_value();
+    if value.contains('\n') {
+        return None;
+    }
+
+    let mut items: Vec<(TextRange, SemanticTokenType)> = Vec::new();
+    match kind {
+        LuaStringArgKind::FormatString => {
+            for spec in parse_format_string(&value).specifiers {
+                let range = get_string_value_range(&string_token, spec.range)?;
+                items.push((range, SemanticTokenType::REGEXP));
+            }
+        }
+        LuaStringArgKind::Pattern => {
+            for item in parse_lua_pattern(&value).items {
+                let range = get_string_value_range(&string_token, item.range)?;
+                let ty = match item.kind {
+                    LuaPatternItemKind::Anchor
+                    | LuaPatternItemKind::Quantifier
+                    | LuaPatternItemKind::Capture => SemanticTokenType::OPERATOR,
+                    _ => SemanticTokenType::REGEXP,
+                };
+                items.push((range, ty));
+            }
+        }
+    }
+    if items.is_empty() {
+        return None;
+    }
+
+    // the text between the items is still the string
+    let mut start = token.text_range().start();
+    for (range, ty) in items {
+        if start < range.start() {
+            builder.push_at_range(
+                TextRange::new(start, range.start()),
+                SemanticTokenType::STRING,
+            );
+        }
+        builder.push_at_range(range, ty);
+        start = range.end();
+    }
+    let end = token.text_range().end();
+    if start < end {
+        builder.push_at_range(TextRange::new(start, end), SemanticTokenType::STRING);
+    }
+
+    Some(())
+}
+
 fn build_node_semantic_token(
     semantic_model: &SemanticModel,
     builder: &mut SemanticBuilder,
@@ -0,0 +1,83 @@
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, VirtualUrlGenerator};
+use lsp_types::SemanticTokenType;
+
+mod semantic_token_test;
+use super::{build_semantic_tokens::build_semantic_tokens, SEMANTIC_TOKEN_TYPES};
+use crate::context::ClientId;
+
+/// A virtual workspace for testing.
+#[allow(unused)]
+#[derive(Debug)]
+struct SemanticTokenVirtualWorkspace {
+    pub virtual_url_generator: VirtualUrlGenerator,
+    pub analysis: EmmyLuaAnalysis,
+    id_counter: u32,
+}
+
+#[allow(unused)]
+impl SemanticTokenVirtualWorkspace {
+    pub fn new_with_init_std_lib() -> Self {
+        let gen = VirtualUrlGenerator::new();
+        let mut analysis = EmmyLuaAnalysis::new();
+        analysis.init_std_lib(None);
+        let base = &gen.base;
+        analysis.add_main_workspace(base.clone());
+        SemanticTokenVirtualWorkspace {
+            virtual_url_generator: gen,
+            analysis,
+            id_counter: 0,
+        }
+    }
+
+    pub fn def(&mut self, content: &str) -> FileId {
+        let id = self.id_counter;
+        self.id_counter += 1;
+        let uri = self
+            .virtual_url_generator
+            .new_uri(&format!("virtual_{}.lua", id));
+        self.analysis
+            .update_file_by_uri(&uri, Some(content.to_string()))
+            .unwrap()
+    }
+
+    /// The tokens inside the first occurrence of `literal` in the content, as
+    /// `(offset in the literal, length, type)`
+    pub fn get_literal_tokens(
+        &mut self,
+        content: &str,
+        literal: &str,
+    ) -> Vec<(u32, u32, SemanticTokenType)> {
+        let offset = content.find(literal).unwrap();
+        let line = content[..offset].matches('\n').count() as u32;
+        let line_start = content[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let start = (offset - line_start) as u32;
+        let end = start + literal.len() as u32;
+
+        let file_id = self.def(content);
+        let mut semantic_model = self
+            .analysis
+            .compilation
+            .get_semantic_model(file_id)
+            .unwrap();
+        let tokens = build_semantic_tokens(&mut semantic_model, false, ClientId::Other).unwrap();
+
+        let mut result = Vec::new();
+        let (mut token_line, mut token_start) = (0, 0);
+        for token in tokens {
+            if token.delta_line != 0 {
+                token_line += token.delta_line;
+                token_start = token.delta_start;
+            } else {
+                token_start += token.delta_start;
+            }
+            if token_line == line && token_start >= start && token_start < end {
+                result.push((
+                    token_start - start,
+                    token.length,
+                    SEMANTIC_TOKEN_TYPES[token.token_type as usize].clone(),
+                ));
+            }
+        }
+        result
+    }
+}
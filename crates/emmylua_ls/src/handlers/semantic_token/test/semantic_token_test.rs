@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use lsp_types::SemanticTokenType;
+
+    use crate::handlers::semantic_token::test::SemanticTokenVirtualWorkspace;
+
+    #[test]
+    fn test_format_string_tokens() {
+        let mut ws = SemanticTokenVirtualWorkspace::new_with_init_std_lib();
+        let tokens = ws.get_literal_tokens(
+            r#"
+            local a = string.format("%d items", 1)
+            "#,
+            r#""%d items""#,
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                (0, 1, SemanticTokenType::STRING),
+                (1, 2, SemanticTokenType::REGEXP),
+                (3, 7, SemanticTokenType::STRING),
+            ]
+        );
+
+        let tokens = ws.get_literal_tokens(
+            r#"
+            local b = ("%-5s|"):format("x")
+            "#,
+            r#""%-5s|""#,
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                (0, 1, SemanticTokenType::STRING),
+                (1, 4, SemanticTokenType::REGEXP),
+                (5, 2, SemanticTokenType::STRING),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pattern_tokens() {
+        let mut ws = SemanticTokenVirtualWorkspace::new_with_init_std_lib();
+        let tokens = ws.get_literal_tokens(
+            r#"
+            local s = "key=value"
+            local k = s:match("^(%a+)=")
+            "#,
+            r#""^(%a+)=""#,
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                (0, 1, SemanticTokenType::STRING),
+                (1, 1, SemanticTokenType::OPERATOR),
+                (2, 1, SemanticTokenType::OPERATOR),
+                (3, 2, SemanticTokenType::REGEXP),
+                (5, 1, SemanticTokenType::OPERATOR),
+                (6, 1, SemanticTokenType::OPERATOR),
+                (7, 2, SemanticTokenType::STRING),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pattern_param_tokens() {
+        let mut ws = SemanticTokenVirtualWorkspace::new_with_init_std_lib();
+        // the classification follows the param type, not the name of the function
+        let tokens = ws.get_literal_tokens(
+            r#"
+            ---@param pattern std.Pattern
+            local function match_all(pattern) end
+
+            match_all("%d")
+            "#,
+            r#""%d""#,
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                (0, 1, SemanticTokenType::STRING),
+                (1, 2, SemanticTokenType::REGEXP),
+                (3, 1, SemanticTokenType::STRING),
+            ]
+        );
+
+        let tokens = ws.get_literal_tokens(
+            r#"
+            print("%d")
+            "#,
+            r#""%d""#,
+        );
+        assert_eq!(tokens, vec![(0, 4, SemanticTokenType::STRING)]);
+
+        let tokens = ws.get_literal_tokens(
+            r#"
+            string.find("a.b", "%.", 1, true)
+            "#,
+            r#""%.""#,
+        );
+        assert_eq!(tokens, vec![(0, 4, SemanticTokenType::STRING)]);
+    }
+}
@@ -39,6 +39,8 @@ fn parse_tag_detail(p: &mut LuaDocParser) -> ParseResult {
         LuaTokenKind::TkTagType => parse_tag_type(p),
         LuaTokenKind::TkTagParam => parse_tag_param(p),
         LuaTokenKind::TkTagReturn => parse_tag_return(p),
+        LuaTokenKind::TkTagTParam => parse_tag_tparam(p),
+        LuaTokenKind::TkTagTReturn => parse_tag_treturn(p),
         // other tag
         LuaTokenKind::TkTagModule => parse_tag_module(p),
         LuaTokenKind::TkTagSee => parse_tag_see(p),
@@ -61,6 +63,8 @@ fn parse_tag_detail(p: &mut LuaDocParser) -> ParseResult {
         LuaTokenKind::TkTagDeprecated => parse_tag_simple(p, LuaSyntaxKind::DocTagDeprecated),
         LuaTokenKind::TkTagAsync => parse_tag_simple(p, LuaSyntaxKind::DocTagAsync),
         LuaTokenKind::TkTagNodiscard => parse_tag_simple(p, LuaSyntaxKind::DocTagNodiscard),
+        LuaTokenKind::TkTagExample => parse_tag_simple(p, LuaSyntaxKind::DocTagExample),
+        LuaTokenKind::TkTagSince => parse_tag_simple(p, LuaSyntaxKind::DocTagSince),
         LuaTokenKind::TkTagOther => parse_tag_simple(p, LuaSyntaxKind::DocTagOther),
         _ => Ok(CompleteMarker::empty()),
     }
@@ -342,6 +346,50 @@ fn parse_tag_return(p: &mut LuaDocParser) -> ParseResult {
     Ok(m.complete(p))
 }
 
+// -- @tparam string name
+// -- @tparam[opt] string name
+// -- @tparam ?string|number name
+fn parse_tag_tparam(p: &mut LuaDocParser) -> ParseResult {
+    p.set_state(LuaDocLexerState::Normal);
+    let m = p.mark(LuaSyntaxKind::DocTagParam);
+    p.bump();
+    if_token_bump(p, LuaTokenKind::TkDocQuestion);
+    parse_type(p)?;
+
+    if matches!(
+        p.current_token(),
+        LuaTokenKind::TkName | LuaTokenKind::TkDots
+    ) {
+        p.bump();
+    } else {
+        return Err(LuaParseError::from_source_range(
+            &t!(
+                "expect param name or '...', but get %{current}",
+                current = p.current_token()
+            ),
+            p.current_token_range(),
+        ));
+    }
+
+    p.set_state(LuaDocLexerState::Description);
+    parse_description(p);
+    Ok(m.complete(p))
+}
+
+// -- @treturn string
+// -- @treturn[1] ?string the description
+fn parse_tag_treturn(p: &mut LuaDocParser) -> ParseResult {
+    p.set_state(LuaDocLexerState::Normal);
+    let m = p.mark(LuaSyntaxKind::DocTagReturn);
+    p.bump();
+    if_token_bump(p, LuaTokenKind::TkDocQuestion);
+    parse_type(p)?;
+
+    p.set_state(LuaDocLexerState::Description);
+    parse_description(p);
+    Ok(m.complete(p))
+}
+
 // ---@generic T
 // ---@generic T, R
 // ---@generic T, R : number
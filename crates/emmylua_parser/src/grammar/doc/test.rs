@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use crate::{parser::ParserConfig, LuaParser};
+    use std::collections::HashMap;
+
+    use crate::{parser::ParserConfig, LuaLanguageLevel, LuaParser};
 
     macro_rules! assert_ast_eq {
         ($lua_code:expr, $expected:expr) => {
@@ -1229,6 +1231,53 @@ Syntax(Chunk)@0..127
         assert_ast_eq!(code, result);
     }
 
+    #[test]
+    fn test_example_doc() {
+        let code = r#"
+        ---@example
+        --- ```lua
+        --- add(1, 2)
+        --- ```
+        ---@usage add(3, 4)
+        "#;
+
+        let result = r#"
+Syntax(Chunk)@0..114
+  Syntax(Block)@0..114
+    Token(TkEndOfLine)@0..1 "\n"
+    Token(TkWhitespace)@1..9 "        "
+    Syntax(Comment)@9..105
+      Token(TkDocStart)@9..13 "---@"
+      Syntax(DocTagExample)@13..77
+        Token(TkTagExample)@13..20 "example"
+        Token(TkEndOfLine)@20..21 "\n"
+        Token(TkWhitespace)@21..29 "        "
+        Syntax(DocDescription)@29..77
+          Token(TkNormalStart)@29..33 "--- "
+          Token(TkDocDetail)@33..39 "```lua"
+          Token(TkEndOfLine)@39..40 "\n"
+          Token(TkWhitespace)@40..48 "        "
+          Token(TkNormalStart)@48..52 "--- "
+          Token(TkDocDetail)@52..61 "add(1, 2)"
+          Token(TkEndOfLine)@61..62 "\n"
+          Token(TkWhitespace)@62..70 "        "
+          Token(TkNormalStart)@70..74 "--- "
+          Token(TkDocDetail)@74..77 "```"
+      Token(TkEndOfLine)@77..78 "\n"
+      Token(TkWhitespace)@78..86 "        "
+      Token(TkDocStart)@86..90 "---@"
+      Syntax(DocTagExample)@90..105
+        Token(TkTagExample)@90..95 "usage"
+        Token(TkWhitespace)@95..96 " "
+        Syntax(DocDescription)@96..105
+          Token(TkDocDetail)@96..105 "add(3, 4)"
+    Token(TkEndOfLine)@105..106 "\n"
+    Token(TkWhitespace)@106..114 "        "
+        "#;
+
+        assert_ast_eq!(code, result);
+    }
+
     #[test]
     fn test_as_doc() {
         let code = r#"
@@ -2519,4 +2568,70 @@ Syntax(Chunk)@0..135
 
     //         assert_ast_eq!(code, result);
     //     }
+
+    #[test]
+    fn test_ldoc_doc() {
+        let code = r#"
+        --- Adds the numbers
+        -- @tparam number a
+        -- @tparam[opt] ?number b the second
+        -- @treturn number
+        -- @param c not typed
+        "#;
+        let config =
+            ParserConfig::new(LuaLanguageLevel::Lua54, None, HashMap::new()).with_ldoc(true);
+        let tree = LuaParser::parse(code, config);
+        let result = format!("{:#?}", tree.get_red_root()).trim().to_string();
+        let expected = r#"
+Syntax(Chunk)@0..168
+  Syntax(Block)@0..168
+    Token(TkEndOfLine)@0..1 "\n"
+    Token(TkWhitespace)@1..9 "        "
+    Syntax(Comment)@9..159
+      Token(TkNormalStart)@9..13 "--- "
+      Syntax(DocDescription)@13..29
+        Token(TkDocDetail)@13..29 "Adds the numbers"
+      Token(TkEndOfLine)@29..30 "\n"
+      Token(TkWhitespace)@30..38 "        "
+      Token(TkDocStart)@38..42 "-- @"
+      Syntax(DocTagParam)@42..57
+        Token(TkTagTParam)@42..48 "tparam"
+        Token(TkWhitespace)@48..49 " "
+        Syntax(TypeName)@49..55
+          Token(TkName)@49..55 "number"
+        Token(TkWhitespace)@55..56 " "
+        Token(TkName)@56..57 "a"
+      Token(TkEndOfLine)@57..58 "\n"
+      Token(TkWhitespace)@58..66 "        "
+      Token(TkDocStart)@66..70 "-- @"
+      Syntax(DocTagParam)@70..102
+        Token(TkTagTParam)@70..81 "tparam[opt]"
+        Token(TkWhitespace)@81..82 " "
+        Token(TkDocQuestion)@82..83 "?"
+        Syntax(TypeName)@83..89
+          Token(TkName)@83..89 "number"
+        Token(TkWhitespace)@89..90 " "
+        Token(TkName)@90..91 "b"
+        Token(TkWhitespace)@91..92 " "
+        Syntax(DocDescription)@92..102
+          Token(TkDocDetail)@92..102 "the second"
+      Token(TkEndOfLine)@102..103 "\n"
+      Token(TkWhitespace)@103..111 "        "
+      Token(TkDocStart)@111..115 "-- @"
+      Syntax(DocTagReturn)@115..159
+        Token(TkTagTReturn)@115..122 "treturn"
+        Token(TkWhitespace)@122..123 " "
+        Syntax(TypeName)@123..129
+          Token(TkName)@123..129 "number"
+        Token(TkEndOfLine)@129..130 "\n"
+        Token(TkWhitespace)@130..138 "        "
+        Syntax(DocDescription)@138..159
+          Token(TkNormalStart)@138..140 "--"
+          Token(TkWhitespace)@140..141 " "
+          Token(TkDocDetail)@141..159 "@param c not typed"
+    Token(TkEndOfLine)@159..160 "\n"
+    Token(TkWhitespace)@160..168 "        "
+"#;
+        assert_eq!(result, expected.trim());
+    }
 }
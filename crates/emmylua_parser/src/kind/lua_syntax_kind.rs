@@ -87,6 +87,8 @@ pub enum LuaSyntaxKind {
     DocTagNamespace,
     DocTagUsing,
     DocTagSource,
+    DocTagExample,
+    DocTagSince,
     DocTagReadonly,
 
     // doc Type
@@ -116,6 +116,10 @@ pub enum LuaTokenKind {
     TkTagNamespace,  // namespace
     TkTagUsing,      // using
     TkTagSource,     // source
+    TkTagExample,    // example usage
+    TkTagSince,      // since
+    TkTagTParam,     // ldoc tparam
+    TkTagTReturn,    // ldoc treturn
 
     TkDocOr,              // |
     TkDocAnd,             // &
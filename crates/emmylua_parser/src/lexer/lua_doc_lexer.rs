@@ -11,6 +11,10 @@ pub struct LuaDocLexer<'a> {
     origin_token_kind: LuaTokenKind,
     pub state: LuaDocLexerState,
     pub reader: Option<Reader<'a>>,
+    /// The ldoc tags are the aliases of the emmylua tags
+    pub ldoc: bool,
+    // the tag is after `-- @`, so it is an ldoc tag
+    ldoc_tag: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +39,8 @@ impl LuaDocLexer<'_> {
             reader: None,
             origin_token_kind: LuaTokenKind::None,
             state: LuaDocLexerState::Init,
+            ldoc: false,
+            ldoc_tag: false,
         }
     }
 
@@ -97,7 +103,7 @@ impl LuaDocLexer<'_> {
                                 _ => return LuaTokenKind::TkLongCommentStart,
                             }
                         } else {
-                            return LuaTokenKind::TkNormalStart;
+                            self.lex_ldoc_start()
                         }
                     }
                     3 => {
@@ -124,6 +130,8 @@ impl LuaDocLexer<'_> {
     }
 
     fn lex_tag(&mut self) -> LuaTokenKind {
+        let ldoc_tag = std::mem::take(&mut self.ldoc_tag);
+        let ldoc = self.ldoc;
         let reader = self.reader.as_mut().unwrap();
         match reader.current_char() {
             ch if is_doc_whitespace(ch) => {
@@ -134,7 +142,19 @@ impl LuaDocLexer<'_> {
                 reader.bump();
                 reader.eat_while(is_name_continue);
                 let text = reader.current_saved_text();
-                to_tag(text)
+                let kind = match to_tag(text) {
+                    _ if ldoc_tag => to_ldoc_tag(text),
+                    LuaTokenKind::TkTagOther if ldoc => to_ldoc_tag(text),
+                    kind => kind,
+                };
+                // the modifiers are a part of the tag, eg: `@tparam[opt]`, `@treturn[1]`
+                if matches!(kind, LuaTokenKind::TkTagTParam | LuaTokenKind::TkTagTReturn)
+                    && reader.current_char() == '['
+                {
+                    reader.eat_while(|ch| ch != ']');
+                    reader.bump();
+                }
+                kind
             }
             _ => {
                 reader.eat_while(|_| true);
@@ -143,6 +163,19 @@ impl LuaDocLexer<'_> {
         }
     }
 
+    // the ldoc tags start with two dashes, eg: `-- @tparam string name`
+    fn lex_ldoc_start(&mut self) -> LuaTokenKind {
+        let reader = self.reader.as_mut().unwrap();
+        if !self.ldoc || !is_ldoc_tag_start(reader.clone()) {
+            return LuaTokenKind::TkNormalStart;
+        }
+
+        reader.eat_while(is_doc_whitespace);
+        reader.bump();
+        self.ldoc_tag = true;
+        LuaTokenKind::TkDocStart
+    }
+
     fn lex_normal(&mut self) -> LuaTokenKind {
         let reader = self.reader.as_mut().unwrap();
         match reader.current_char() {
@@ -326,7 +359,7 @@ impl LuaDocLexer<'_> {
                                 _ => return LuaTokenKind::TkLongCommentStart,
                             }
                         } else {
-                            return LuaTokenKind::TkNormalStart;
+                            self.lex_ldoc_start()
                         }
                     }
                     3 => {
@@ -511,7 +544,7 @@ impl LuaDocLexer<'_> {
                                 _ => return LuaTokenKind::TkLongCommentStart,
                             }
                         } else {
-                            return LuaTokenKind::TkNormalStart;
+                            self.lex_ldoc_start()
                         }
                     }
                     3 => {
@@ -567,10 +600,39 @@ fn to_tag(text: &str) -> LuaTokenKind {
         "namespace" => LuaTokenKind::TkTagNamespace,
         "using" => LuaTokenKind::TkTagUsing,
         "source" => LuaTokenKind::TkTagSource,
+        "example" | "usage" => LuaTokenKind::TkTagExample,
+        "since" => LuaTokenKind::TkTagSince,
+        _ => LuaTokenKind::TkTagOther,
+    }
+}
+
+// only the ldoc tags which are known by their names are the tags of the `-- @` lines
+fn to_ldoc_tag(text: &str) -> LuaTokenKind {
+    match text {
+        "tparam" => LuaTokenKind::TkTagTParam,
+        "treturn" => LuaTokenKind::TkTagTReturn,
+        "module" | "classmod" => LuaTokenKind::TkTagClass,
+        "usage" => LuaTokenKind::TkTagExample,
+        "see" => LuaTokenKind::TkTagSee,
         _ => LuaTokenKind::TkTagOther,
     }
 }
 
+fn is_ldoc_tag_start(mut reader: Reader) -> bool {
+    reader.eat_while(is_doc_whitespace);
+    if reader.current_char() != '@' {
+        return false;
+    }
+
+    reader.bump();
+    let mut name = String::new();
+    while is_name_continue(reader.current_char()) {
+        name.push(reader.current_char());
+        reader.bump();
+    }
+    to_ldoc_tag(&name) != LuaTokenKind::TkTagOther
+}
+
 fn to_modification_or_name(text: &str) -> LuaTokenKind {
     match text {
         "private" | "protected" | "public" | "package" => LuaTokenKind::TkDocVisibility,
@@ -37,7 +37,8 @@ impl MarkerEventContainer for LuaDocParser<'_, '_> {
 
 impl LuaDocParser<'_, '_> {
     pub fn parse<'a, 'b>(lua_parser: &'a mut LuaParser<'b>, tokens: &[LuaTokenData]) {
-        let lexer = LuaDocLexer::new(lua_parser.origin_text());
+        let mut lexer = LuaDocLexer::new(lua_parser.origin_text());
+        lexer.ldoc = lua_parser.parse_config.support_ldoc();
 
         let mut parser = LuaDocParser {
             lua_parser,
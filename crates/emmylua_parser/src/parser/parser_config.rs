@@ -9,6 +9,7 @@ pub struct ParserConfig<'cache> {
     lexer_config: LexerConfig,
     node_cache: Option<&'cache mut NodeCache>,
     special_like: HashMap<String, SpecialFunction>,
+    ldoc: bool,
 }
 
 impl<'cache> ParserConfig<'cache> {
@@ -24,9 +25,16 @@ impl<'cache> ParserConfig<'cache> {
             },
             node_cache,
             special_like,
+            ldoc: false,
         }
     }
 
+    /// Parses the ldoc tags as the aliases of the emmylua tags
+    pub fn with_ldoc(mut self, ldoc: bool) -> Self {
+        self.ldoc = ldoc;
+        self
+    }
+
     pub fn lexer_config(&self) -> LexerConfig {
         self.lexer_config
     }
@@ -35,6 +43,11 @@ impl<'cache> ParserConfig<'cache> {
         self.level == LuaLanguageLevel::Lua54
     }
 
+    /// The ldoc tags are the aliases of the emmylua tags, eg: `-- @tparam string name`
+    pub fn support_ldoc(&self) -> bool {
+        self.ldoc
+    }
+
     pub fn node_cache(&mut self) -> Option<&mut NodeCache> {
         self.node_cache.as_deref_mut()
     }
@@ -63,6 +76,7 @@ impl<'cache> Default for ParserConfig<'cache> {
             },
             node_cache: None,
             special_like: HashMap::new(),
+            ldoc: false,
         }
     }
 }
@@ -27,6 +27,8 @@ pub enum LuaDocTag {
     Version(LuaDocTagVersion),
     Cast(LuaDocTagCast),
     Source(LuaDocTagSource),
+    Example(LuaDocTagExample),
+    Since(LuaDocTagSince),
     Other(LuaDocTagOther),
     Namespace(LuaDocTagNamespace),
     Using(LuaDocTagUsing),
@@ -58,6 +60,8 @@ impl LuaAstNode for LuaDocTag {
             LuaDocTag::Version(it) => it.syntax(),
             LuaDocTag::Cast(it) => it.syntax(),
             LuaDocTag::Source(it) => it.syntax(),
+            LuaDocTag::Example(it) => it.syntax(),
+            LuaDocTag::Since(it) => it.syntax(),
             LuaDocTag::Other(it) => it.syntax(),
             LuaDocTag::Namespace(it) => it.syntax(),
             LuaDocTag::Using(it) => it.syntax(),
@@ -91,6 +95,8 @@ impl LuaAstNode for LuaDocTag {
             || kind == LuaSyntaxKind::DocTagVersion
             || kind == LuaSyntaxKind::DocTagCast
             || kind == LuaSyntaxKind::DocTagSource
+            || kind == LuaSyntaxKind::DocTagExample
+            || kind == LuaSyntaxKind::DocTagSince
             || kind == LuaSyntaxKind::DocTagOther
             || kind == LuaSyntaxKind::DocTagNamespace
             || kind == LuaSyntaxKind::DocTagUsing
@@ -152,6 +158,12 @@ impl LuaAstNode for LuaDocTag {
             LuaSyntaxKind::DocTagSource => {
                 Some(LuaDocTag::Source(LuaDocTagSource::cast(syntax).unwrap()))
             }
+            LuaSyntaxKind::DocTagExample => {
+                Some(LuaDocTag::Example(LuaDocTagExample::cast(syntax).unwrap()))
+            }
+            LuaSyntaxKind::DocTagSince => {
+                Some(LuaDocTag::Since(LuaDocTagSince::cast(syntax).unwrap()))
+            }
             LuaSyntaxKind::DocTagOther => {
                 Some(LuaDocTag::Other(LuaDocTagOther::cast(syntax).unwrap()))
             }
@@ -451,6 +463,9 @@ impl LuaDocTagParam {
 
     pub fn is_nullable(&self) -> bool {
         self.token_by_kind(LuaTokenKind::TkDocQuestion).is_some()
+            || self
+                .token_by_kind(LuaTokenKind::TkTagTParam)
+                .is_some_and(|token| token.get_text().contains("[opt"))
     }
 
     pub fn get_type(&self) -> Option<LuaDocType> {
@@ -1026,6 +1041,78 @@ impl LuaDocTagSource {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LuaDocTagExample {
+    syntax: LuaSyntaxNode,
+}
+
+impl LuaAstNode for LuaDocTagExample {
+    fn syntax(&self) -> &LuaSyntaxNode {
+        &self.syntax
+    }
+
+    fn can_cast(kind: LuaSyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == LuaSyntaxKind::DocTagExample
+    }
+
+    fn cast(syntax: LuaSyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind().into()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+}
+
+impl LuaDocDescriptionOwner for LuaDocTagExample {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LuaDocTagSince {
+    syntax: LuaSyntaxNode,
+}
+
+impl LuaAstNode for LuaDocTagSince {
+    fn syntax(&self) -> &LuaSyntaxNode {
+        &self.syntax
+    }
+
+    fn can_cast(kind: LuaSyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == LuaSyntaxKind::DocTagSince
+    }
+
+    fn cast(syntax: LuaSyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind().into()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+}
+
+impl LuaDocDescriptionOwner for LuaDocTagSince {}
+
+impl LuaDocTagSince {
+    /// The first word of the description, eg: `1.2` of `---@since 1.2 the notes`
+    pub fn get_version_text(&self) -> Option<String> {
+        let text = self.get_description()?.get_description_text();
+        text.split_whitespace()
+            .next()
+            .map(|version| version.to_string())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LuaDocTagOther {
     syntax: LuaSyntaxNode,
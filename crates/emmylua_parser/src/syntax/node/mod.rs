@@ -75,6 +75,8 @@ pub enum LuaAst {
     LuaDocTagVersion(LuaDocTagVersion),
     LuaDocTagCast(LuaDocTagCast),
     LuaDocTagSource(LuaDocTagSource),
+    LuaDocTagExample(LuaDocTagExample),
+    LuaDocTagSince(LuaDocTagSince),
     LuaDocTagOther(LuaDocTagOther),
     LuaDocTagNamespace(LuaDocTagNamespace),
     LuaDocTagUsing(LuaDocTagUsing),
@@ -157,6 +159,8 @@ impl LuaAstNode for LuaAst {
             LuaAst::LuaDocTagVersion(node) => node.syntax(),
             LuaAst::LuaDocTagCast(node) => node.syntax(),
             LuaAst::LuaDocTagSource(node) => node.syntax(),
+            LuaAst::LuaDocTagExample(node) => node.syntax(),
+            LuaAst::LuaDocTagSince(node) => node.syntax(),
             LuaAst::LuaDocTagOther(node) => node.syntax(),
             LuaAst::LuaDocTagNamespace(node) => node.syntax(),
             LuaAst::LuaDocTagUsing(node) => node.syntax(),
@@ -246,6 +250,8 @@ impl LuaAstNode for LuaAst {
             LuaSyntaxKind::DocTagVersion => true,
             LuaSyntaxKind::DocTagCast => true,
             LuaSyntaxKind::DocTagSource => true,
+            LuaSyntaxKind::DocTagExample => true,
+            LuaSyntaxKind::DocTagSince => true,
             LuaSyntaxKind::DocTagOther => true,
             LuaSyntaxKind::DocTagNamespace => true,
             LuaSyntaxKind::DocTagUsing => true,
@@ -368,6 +374,10 @@ impl LuaAstNode for LuaAst {
             LuaSyntaxKind::DocTagSource => {
                 LuaDocTagSource::cast(syntax).map(LuaAst::LuaDocTagSource)
             }
+            LuaSyntaxKind::DocTagExample => {
+                LuaDocTagExample::cast(syntax).map(LuaAst::LuaDocTagExample)
+            }
+            LuaSyntaxKind::DocTagSince => LuaDocTagSince::cast(syntax).map(LuaAst::LuaDocTagSince),
             LuaSyntaxKind::DocTagOther => LuaDocTagOther::cast(syntax).map(LuaAst::LuaDocTagOther),
             LuaSyntaxKind::DocTagNamespace => {
                 LuaDocTagNamespace::cast(syntax).map(LuaAst::LuaDocTagNamespace)